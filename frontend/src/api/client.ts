@@ -24,6 +24,15 @@ const API_BASE_URL =
 const USE_MOCK_DATA = import.meta.env.VITE_USE_MOCK_DATA === "true";
 
 // Create a ky instance with default configuration
+/**
+ * If-Match header for a report version, so the API can reject stale updates (412).
+ * Without a known version no header is sent, rather than `*`, which would
+ * silently overwrite a concurrent change.
+ */
+function ifMatch(version?: number): Record<string, string> {
+  return version === undefined ? {} : { "If-Match": `"${version}"` };
+}
+
 const api = ky.create({
   prefixUrl: API_BASE_URL,
   timeout: 5000,
@@ -114,6 +123,7 @@ export const reportsApi = {
   async updateStatus(
     id: string,
     data: UpdateStatusRequest,
+    version?: number,
   ): Promise<UpdateStatusResponse> {
     if (USE_MOCK_DATA) {
       return Promise.resolve({
@@ -125,7 +135,7 @@ export const reportsApi = {
     }
 
     return api
-      .patch(`reports/${id}/status`, { json: data })
+      .patch(`reports/${id}/status`, { json: data, headers: ifMatch(version) })
       .json<UpdateStatusResponse>();
   },
 
@@ -136,12 +146,17 @@ export const reportsApi = {
     id: string,
     reviewedBy: string,
     notes = "",
+    version?: number,
   ): Promise<UpdateStatusResponse> {
-    return this.updateStatus(id, {
-      status: "approved",
-      reviewed_by: reviewedBy,
-      review_notes: notes,
-    });
+    return this.updateStatus(
+      id,
+      {
+        status: "approved",
+        reviewed_by: reviewedBy,
+        review_notes: notes,
+      },
+      version,
+    );
   },
 
   /**
@@ -151,23 +166,28 @@ export const reportsApi = {
     id: string,
     reviewedBy: string,
     notes: string,
+    version?: number,
   ): Promise<UpdateStatusResponse> {
-    return this.updateStatus(id, {
-      status: "rejected",
-      reviewed_by: reviewedBy,
-      review_notes: notes,
-    });
+    return this.updateStatus(
+      id,
+      {
+        status: "rejected",
+        reviewed_by: reviewedBy,
+        review_notes: notes,
+      },
+      version,
+    );
   },
 
   /**
    * Delete a report
    */
-  async delete(id: string): Promise<void> {
+  async delete(id: string, version?: number): Promise<void> {
     if (USE_MOCK_DATA) {
       return Promise.resolve();
     }
 
-    await api.delete(`reports/${id}`);
+    await api.delete(`reports/${id}`, { headers: ifMatch(version) });
   },
 };
 
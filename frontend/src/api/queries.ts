@@ -63,8 +63,15 @@ export function useUpdateReportStatus() {
   const queryClient = useQueryClient();
 
   return useMutation({
-    mutationFn: ({ id, data }: { id: string; data: UpdateStatusRequest }) =>
-      reportsApi.updateStatus(id, data),
+    mutationFn: ({
+      id,
+      data,
+      version,
+    }: {
+      id: string;
+      data: UpdateStatusRequest;
+      version?: number;
+    }) => reportsApi.updateStatus(id, data, version),
     onSuccess: (_, variables) => {
       // Invalidate the specific report and all lists
       queryClient.invalidateQueries({
@@ -86,11 +93,13 @@ export function useApproveReport() {
       id,
       reviewedBy,
       notes,
+      version,
     }: {
       id: string;
       reviewedBy: string;
       notes?: string;
-    }) => reportsApi.approve(id, reviewedBy, notes),
+      version?: number;
+    }) => reportsApi.approve(id, reviewedBy, notes, version),
     onSuccess: (_, variables) => {
       queryClient.invalidateQueries({
         queryKey: queryKeys.reports.detail(variables.id),
@@ -111,11 +120,13 @@ export function useRejectReport() {
       id,
       reviewedBy,
       notes,
+      version,
     }: {
       id: string;
       reviewedBy: string;
       notes: string;
-    }) => reportsApi.reject(id, reviewedBy, notes),
+      version?: number;
+    }) => reportsApi.reject(id, reviewedBy, notes, version),
     onSuccess: (_, variables) => {
       queryClient.invalidateQueries({
         queryKey: queryKeys.reports.detail(variables.id),
@@ -132,7 +143,8 @@ export function useDeleteReport() {
   const queryClient = useQueryClient();
 
   return useMutation({
-    mutationFn: (id: string) => reportsApi.delete(id),
+    mutationFn: ({ id, version }: { id: string; version?: number }) =>
+      reportsApi.delete(id, version),
     onSuccess: () => {
       // Invalidate all lists after deletion
       queryClient.invalidateQueries({ queryKey: queryKeys.reports.lists() });
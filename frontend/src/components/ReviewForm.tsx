@@ -34,9 +34,13 @@ export function ReviewForm({
     onReject(reviewedBy, notes);
   };
 
-  const isPending = report.status === "pending_review";
+  const isPending =
+    report.status === "pending_review" || report.status === "in_review";
   const isReviewed =
-    report.status === "approved" || report.status === "rejected";
+    report.status === "approved" ||
+    report.status === "approved_with_conditions" ||
+    report.status === "rejected" ||
+    report.status === "expired";
 
   return (
     <div style={{ display: "flex", flexDirection: "column", gap: "1rem" }}>
@@ -12,23 +12,43 @@ interface StatusBadgeProps {
 export function StatusBadge({ status }: StatusBadgeProps) {
   const getState = (status: ReportStatus) => {
     switch (status) {
+      case "draft":
+        return "neutral" as const;
       case "pending_review":
         return "warning" as const;
+      case "in_review":
+        return "chill" as const;
       case "approved":
         return "success" as const;
+      case "approved_with_conditions":
+        return "success" as const;
       case "rejected":
         return "alert" as const;
+      case "expired":
+        return "neutral" as const;
+      case "withdrawn":
+        return "neutral" as const;
     }
   };
 
   const getLabel = (status: ReportStatus) => {
     switch (status) {
+      case "draft":
+        return "Draft";
       case "pending_review":
         return "Pending Review";
+      case "in_review":
+        return "In Review";
       case "approved":
         return "Approved";
+      case "approved_with_conditions":
+        return "Approved with Conditions";
       case "rejected":
         return "Rejected";
+      case "expired":
+        return "Expired";
+      case "withdrawn":
+        return "Withdrawn";
     }
   };
 
@@ -40,7 +40,7 @@ export default function ReportDetail() {
     if (!window.confirm("Are you sure you want to delete this report?")) return;
 
     try {
-      await deleteReport.mutateAsync(id);
+      await deleteReport.mutateAsync({ id, version: report?.version });
       navigate("/");
     } catch (error) {
       alert(`Failed to delete report: ${error}`);
@@ -53,7 +53,12 @@ export default function ReportDetail() {
     if (notes === null) return; // User cancelled
 
     try {
-      await approveReport.mutateAsync({ id, reviewedBy: reviewerEmail, notes });
+      await approveReport.mutateAsync({
+        id,
+        reviewedBy: reviewerEmail,
+        notes,
+        version: report?.version,
+      });
     } catch (error) {
       alert(`Failed to approve report: ${error}`);
     }
@@ -65,7 +70,12 @@ export default function ReportDetail() {
     if (!notes) return; // User cancelled or empty
 
     try {
-      await rejectReport.mutateAsync({ id, reviewedBy: reviewerEmail, notes });
+      await rejectReport.mutateAsync({
+        id,
+        reviewedBy: reviewerEmail,
+        notes,
+        version: report?.version,
+      });
     } catch (error) {
       alert(`Failed to reject report: ${error}`);
     }
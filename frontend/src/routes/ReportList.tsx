@@ -106,7 +106,10 @@ export default function ReportList() {
                 <option value="all">All Submissions</option>
                 <option value="approved">✅ Approved</option>
                 <option value="pending_review">⏳ Pending Review</option>
+                <option value="in_review">🔍 In Review</option>
                 <option value="rejected">❌ Needs Work</option>
+                <option value="expired">⌛ Expired</option>
+                <option value="withdrawn">↩️ Withdrawn</option>
               </select>
             </label>
           </div>
@@ -2,7 +2,15 @@
  * API types for MCP Go-Live service
  */
 
-export type ReportStatus = "pending_review" | "approved" | "rejected";
+export type ReportStatus =
+  | "draft"
+  | "pending_review"
+  | "in_review"
+  | "approved"
+  | "approved_with_conditions"
+  | "rejected"
+  | "expired"
+  | "withdrawn";
 
 export interface SecurityReviewItem {
   type: string;
@@ -42,6 +50,10 @@ export interface Report {
   reviewed_at: string | null;
   reviewed_by: string | null;
   review_notes: string | null;
+  /** Row version, sent back as If-Match on updates */
+  version?: number;
+  assignee?: string | null;
+  claim_expires_at?: string | null;
 }
 
 export interface ReportSummary {
@@ -86,5 +98,8 @@ export interface UpdateStatusResponse {
 
 export interface ApiError {
   error: string;
+  /** Stable machine-readable error code, e.g. "not_found" or "validation_failed" */
+  code?: string;
+  request_id?: string;
   details?: string;
 }
@@ -1,5 +1,7 @@
 """HTTP client for communicating with the Rust API backend."""
 
+import asyncio
+import uuid
 from typing import Any
 
 import httpx
@@ -13,6 +15,7 @@ class GoLiveAPIClient:
     def __init__(self):
         self.base_url = settings.api_base_url
         self.timeout = settings.api_timeout
+        self.retries = settings.api_retries
 
     async def submit_report(
         self,
@@ -21,6 +24,7 @@ class GoLiveAPIClient:
         developer_email: str,
         report_data: str,
         report_json: dict[str, Any] | None = None,
+        idempotency_key: str | None = None,
     ) -> Report:
         """Submit a go-live report to the API.
 
@@ -30,12 +34,16 @@ class GoLiveAPIClient:
             developer_email: Developer's email address
             report_data: Full markdown report
             report_json: Optional structured JSON data
+            idempotency_key: Key identifying this submission. Pass the same key
+                when retrying a submission that may already have gone through;
+                a fresh one is generated when omitted.
 
         Returns:
             Report object with ID and status
 
         Raises:
             httpx.HTTPStatusError: If API returns error status
+            httpx.TransportError: If the API stays unreachable after retries
         """
         payload = {
             "server_name": server_name,
@@ -47,12 +55,27 @@ class GoLiveAPIClient:
         if report_json is not None:
             payload["report_json"] = report_json
 
+        # The same key is sent on every retry, including after a timeout where
+        # the first attempt may have been stored, so the API replays the
+        # original response instead of creating a duplicate.
+        headers = {
+            "Content-Type": "application/json",
+            "Idempotency-Key": idempotency_key or str(uuid.uuid4()),
+        }
+
         async with httpx.AsyncClient(timeout=self.timeout) as client:
-            response = await client.post(
-                f"{self.base_url}/reports",
-                json=payload,
-                headers={"Content-Type": "application/json"},
-            )
+            for attempt in range(self.retries + 1):
+                try:
+                    response = await client.post(
+                        f"{self.base_url}/reports",
+                        json=payload,
+                        headers=headers,
+                    )
+                    break
+                except httpx.TransportError:
+                    if attempt == self.retries:
+                        raise
+                    await asyncio.sleep(2**attempt)
             response.raise_for_status()
             return Report(**response.json())
 
@@ -22,6 +22,10 @@ class Settings(BaseSettings):
         default=30,
         description="HTTP timeout for API requests (seconds)"
     )
+    api_retries: int = Field(
+        default=2,
+        description="Retries of a report submission after a timeout or connection error"
+    )
 
     # Server Configuration
     environment: str = Field(
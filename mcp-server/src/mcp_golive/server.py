@@ -26,6 +26,7 @@ async def submit_report(
     developer_email: str,
     report_markdown: str,
     report_json: str,
+    idempotency_key: str | None = None,
 ) -> str:
     """Submit an MCP server go-live report to the platform team for review.
 
@@ -43,6 +44,9 @@ async def submit_report(
         developer_email: Email address of the developer
         report_markdown: Complete go-live report in markdown format
         report_json: Structured JSON data as a JSON string (REQUIRED)
+        idempotency_key: Optional key for this submission. When retrying a
+            submission that failed or timed out, pass the same key so the
+            report is not stored twice.
 
     Returns:
         Formatted success message with report details
@@ -66,6 +70,7 @@ The report_json parameter must be a valid JSON string.
             developer_email=developer_email,
             report_data=report_markdown,
             report_json=json_data,
+            idempotency_key=idempotency_key,
         )
 
         return f"""# Report Submitted Successfully! ✅
@@ -173,6 +178,7 @@ Use `submit_report` to submit a go-live report.
             result += "```bash\n"
             result += 'curl -X PATCH http://localhost:8080/api/v1/reports/{id}/status \\\n'
             result += '  -H "Content-Type: application/json" \\\n'
+            result += '  -H "X-User-Email: platform@intility.no" \\\n'
             result += '  -d \'{"status":"approved","reviewed_by":"platform@intility.no","review_notes":"LGTM"}\'\n'
             result += "```\n"
 
@@ -0,0 +1,4 @@
+-- Findings from secret/PII scanning of report_data, report_json and raw_json.
+-- Each entry: {detector, field, location, preview, redacted}. Previews are masked.
+ALTER TABLE mcp_server_reports
+ADD COLUMN scan_findings JSONB NOT NULL DEFAULT '[]'::jsonb;
@@ -0,0 +1,18 @@
+-- SHA-256 of the submitted content, used to detect exact re-submissions
+ALTER TABLE mcp_server_reports
+ADD COLUMN content_hash VARCHAR(64);
+
+CREATE INDEX idx_reports_content_hash ON mcp_server_reports(content_hash);
+
+-- Responses stored per Idempotency-Key so client retries get the original answer
+CREATE TABLE idempotency_keys (
+    key VARCHAR(255) PRIMARY KEY,
+    request_hash VARCHAR(64) NOT NULL,
+    response_status SMALLINT,
+    response_body JSONB,
+    report_id UUID REFERENCES mcp_server_reports(id) ON DELETE CASCADE,
+    created_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    completed_at TIMESTAMPTZ
+);
+
+CREATE INDEX idx_idempotency_keys_created_at ON idempotency_keys(created_at);
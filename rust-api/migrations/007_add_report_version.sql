@@ -0,0 +1,4 @@
+-- Row version for optimistic concurrency; exposed as the report's ETag and
+-- incremented by every mutation.
+ALTER TABLE mcp_server_reports
+ADD COLUMN version INTEGER NOT NULL DEFAULT 1;
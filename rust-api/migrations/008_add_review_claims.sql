@@ -0,0 +1,16 @@
+-- Review claiming: a reviewer takes a report into 'in_review' for a limited lease
+ALTER TABLE mcp_server_reports
+DROP CONSTRAINT mcp_server_reports_status_check;
+
+ALTER TABLE mcp_server_reports
+ADD CONSTRAINT mcp_server_reports_status_check
+    CHECK (status IN ('pending_review', 'in_review', 'approved', 'rejected'));
+
+ALTER TABLE mcp_server_reports
+ADD COLUMN assignee VARCHAR(255),
+ADD COLUMN claimed_at TIMESTAMPTZ,
+ADD COLUMN claim_expires_at TIMESTAMPTZ;
+
+CREATE INDEX idx_reports_assignee ON mcp_server_reports(assignee);
+CREATE INDEX idx_reports_claim_expires_at ON mcp_server_reports(claim_expires_at)
+    WHERE status = 'in_review';
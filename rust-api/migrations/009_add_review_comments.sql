@@ -0,0 +1,20 @@
+-- Threaded review comments between developers and reviewers
+CREATE TABLE review_comments (
+    id UUID PRIMARY KEY DEFAULT gen_random_uuid(),
+    report_id UUID NOT NULL REFERENCES mcp_server_reports(id) ON DELETE CASCADE,
+    -- The opening comment of the thread; equal to id for the opening comment itself
+    thread_id UUID NOT NULL REFERENCES review_comments(id) ON DELETE CASCADE,
+    anchor_type VARCHAR(30) NOT NULL DEFAULT 'report'
+        CHECK (anchor_type IN ('report', 'phase', 'security_review_item', 'issue')),
+    anchor_ref VARCHAR(255),
+    author VARCHAR(255) NOT NULL,
+    author_role VARCHAR(20) NOT NULL CHECK (author_role IN ('developer', 'reviewer')),
+    body TEXT NOT NULL,
+    -- Only set on the opening comment
+    resolved_at TIMESTAMPTZ,
+    resolved_by VARCHAR(255),
+    created_at TIMESTAMPTZ NOT NULL DEFAULT NOW()
+);
+
+CREATE INDEX idx_review_comments_report ON review_comments(report_id, created_at);
+CREATE INDEX idx_review_comments_thread ON review_comments(thread_id);
@@ -0,0 +1,17 @@
+-- Per-stage sign-off votes for multi-stage approvals
+CREATE TABLE approval_votes (
+    id UUID PRIMARY KEY DEFAULT gen_random_uuid(),
+    report_id UUID NOT NULL REFERENCES mcp_server_reports(id) ON DELETE CASCADE,
+    stage VARCHAR(50) NOT NULL,
+    voter VARCHAR(255) NOT NULL,
+    decision VARCHAR(10) NOT NULL CHECK (decision IN ('approve', 'reject')),
+    notes TEXT,
+    created_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    -- Set when the report is rejected or reset; the round's votes stop counting
+    invalidated_at TIMESTAMPTZ
+);
+
+CREATE INDEX idx_approval_votes_report ON approval_votes(report_id) WHERE invalidated_at IS NULL;
+-- One vote per person per round, across all stages
+CREATE UNIQUE INDEX idx_approval_votes_one_per_voter
+    ON approval_votes(report_id, voter) WHERE invalidated_at IS NULL;
@@ -0,0 +1,13 @@
+-- Admin overrides of the four-eyes rule
+CREATE TABLE break_glass_audit (
+    id UUID PRIMARY KEY DEFAULT gen_random_uuid(),
+    report_id UUID NOT NULL REFERENCES mcp_server_reports(id) ON DELETE CASCADE,
+    actor VARCHAR(255) NOT NULL,
+    action VARCHAR(20) NOT NULL,
+    conflict TEXT NOT NULL,
+    reason TEXT NOT NULL,
+    created_at TIMESTAMPTZ NOT NULL DEFAULT NOW()
+);
+
+CREATE INDEX idx_break_glass_audit_report ON break_glass_audit(report_id);
+CREATE INDEX idx_break_glass_audit_created_at ON break_glass_audit(created_at DESC);
@@ -0,0 +1,25 @@
+-- Conditional approvals: approved provided that tracked conditions are met by a deadline
+ALTER TABLE mcp_server_reports
+DROP CONSTRAINT mcp_server_reports_status_check;
+
+ALTER TABLE mcp_server_reports
+ADD CONSTRAINT mcp_server_reports_status_check
+    CHECK (status IN ('pending_review', 'in_review', 'approved', 'approved_with_conditions', 'rejected'));
+
+CREATE TABLE report_conditions (
+    id UUID PRIMARY KEY DEFAULT gen_random_uuid(),
+    report_id UUID NOT NULL REFERENCES mcp_server_reports(id) ON DELETE CASCADE,
+    description TEXT NOT NULL,
+    owner VARCHAR(255) NOT NULL,
+    due_at TIMESTAMPTZ NOT NULL,
+    fulfilled_at TIMESTAMPTZ,
+    fulfilled_by VARCHAR(255),
+    evidence TEXT,
+    overdue_at TIMESTAMPTZ,
+    withdrawn_at TIMESTAMPTZ,
+    created_at TIMESTAMPTZ NOT NULL DEFAULT NOW()
+);
+
+CREATE INDEX idx_report_conditions_report ON report_conditions(report_id);
+CREATE INDEX idx_report_conditions_open_due ON report_conditions(due_at)
+    WHERE fulfilled_at IS NULL AND withdrawn_at IS NULL AND overdue_at IS NULL;
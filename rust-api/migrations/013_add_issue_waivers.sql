@@ -0,0 +1,23 @@
+-- Risk acceptance: waivers that exempt a known issue from review
+CREATE TABLE issue_waivers (
+    id UUID PRIMARY KEY DEFAULT gen_random_uuid(),
+    -- The report the waiver was granted on
+    report_id UUID NOT NULL REFERENCES mcp_server_reports(id) ON DELETE CASCADE,
+    server_name VARCHAR(255) NOT NULL,
+    scope VARCHAR(10) NOT NULL CHECK (scope IN ('report', 'server')),
+    -- Issue identity: phase plus normalized description hash
+    phase_id VARCHAR(255),
+    description_hash VARCHAR(64) NOT NULL,
+    issue_description TEXT NOT NULL,
+    severity VARCHAR(50) NOT NULL,
+    approver VARCHAR(255) NOT NULL,
+    justification TEXT NOT NULL,
+    expires_at TIMESTAMPTZ NOT NULL,
+    effective_from TIMESTAMPTZ NOT NULL,
+    revoked_at TIMESTAMPTZ,
+    revoked_by VARCHAR(255),
+    created_at TIMESTAMPTZ NOT NULL DEFAULT NOW()
+);
+
+CREATE INDEX idx_issue_waivers_report ON issue_waivers(report_id);
+CREATE INDEX idx_issue_waivers_server ON issue_waivers(server_name) WHERE scope = 'server';
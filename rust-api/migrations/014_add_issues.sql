@@ -0,0 +1,43 @@
+-- Issues tracked across submissions, keyed by a stable per-server fingerprint
+CREATE TABLE issues (
+    id UUID PRIMARY KEY DEFAULT gen_random_uuid(),
+    server_name VARCHAR(255) NOT NULL,
+    fingerprint VARCHAR(64) NOT NULL,
+    description_hash VARCHAR(64) NOT NULL,
+    category VARCHAR(30) NOT NULL,
+    severity VARCHAR(50) NOT NULL,
+    phase_id VARCHAR(255),
+    description TEXT NOT NULL,
+    impact TEXT,
+    recommendation TEXT,
+    status VARCHAR(20) NOT NULL DEFAULT 'open'
+        CHECK (status IN ('open', 'fixed', 'waived', 'reopened')),
+    first_seen_report_id UUID NOT NULL REFERENCES mcp_server_reports(id) ON DELETE CASCADE,
+    last_seen_report_id UUID NOT NULL REFERENCES mcp_server_reports(id) ON DELETE CASCADE,
+    first_seen_at TIMESTAMPTZ NOT NULL,
+    last_seen_at TIMESTAMPTZ NOT NULL,
+    fixed_at TIMESTAMPTZ,
+    fixed_in_report_id UUID REFERENCES mcp_server_reports(id) ON DELETE SET NULL,
+    reopened_at TIMESTAMPTZ,
+    status_changed_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    UNIQUE (server_name, fingerprint)
+);
+
+CREATE INDEX idx_issues_status_severity ON issues(status, severity);
+CREATE INDEX idx_issues_last_seen_report ON issues(last_seen_report_id);
+
+-- Each report's issues as submitted
+CREATE TABLE report_issues (
+    report_id UUID NOT NULL REFERENCES mcp_server_reports(id) ON DELETE CASCADE,
+    issue_id UUID NOT NULL REFERENCES issues(id) ON DELETE CASCADE,
+    list VARCHAR(30) NOT NULL,
+    position INTEGER NOT NULL,
+    severity VARCHAR(50) NOT NULL,
+    description TEXT NOT NULL,
+    impact TEXT,
+    recommendation TEXT,
+    phase_id VARCHAR(255),
+    PRIMARY KEY (report_id, list, position)
+);
+
+CREATE INDEX idx_report_issues_issue ON report_issues(issue_id);
@@ -0,0 +1,13 @@
+-- Approvals are valid for a limited time and then need re-certification
+ALTER TABLE mcp_server_reports
+DROP CONSTRAINT mcp_server_reports_status_check;
+
+ALTER TABLE mcp_server_reports
+ADD CONSTRAINT mcp_server_reports_status_check
+    CHECK (status IN ('pending_review', 'in_review', 'approved', 'approved_with_conditions', 'rejected', 'expired'));
+
+ALTER TABLE mcp_server_reports
+ADD COLUMN expires_at TIMESTAMPTZ;
+
+CREATE INDEX idx_reports_expires_at ON mcp_server_reports(expires_at)
+    WHERE status IN ('approved', 'approved_with_conditions');
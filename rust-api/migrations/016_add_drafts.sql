@@ -0,0 +1,8 @@
+-- Developers can keep reports as drafts and withdraw submissions
+ALTER TABLE mcp_server_reports
+DROP CONSTRAINT mcp_server_reports_status_check;
+
+ALTER TABLE mcp_server_reports
+ADD CONSTRAINT mcp_server_reports_status_check
+    CHECK (status IN ('draft', 'pending_review', 'in_review', 'approved', 'approved_with_conditions',
+                      'rejected', 'expired', 'withdrawn'));
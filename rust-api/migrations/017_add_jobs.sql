@@ -0,0 +1,34 @@
+-- Postgres-backed job queue with cron schedules
+CREATE TABLE jobs (
+    id UUID PRIMARY KEY DEFAULT gen_random_uuid(),
+    kind VARCHAR(100) NOT NULL,
+    payload JSONB NOT NULL DEFAULT '{}',
+    status VARCHAR(20) NOT NULL DEFAULT 'queued'
+        CHECK (status IN ('queued', 'running', 'succeeded', 'dead')),
+    attempts INTEGER NOT NULL DEFAULT 0,
+    max_attempts INTEGER NOT NULL DEFAULT 5,
+    run_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    dedupe_key VARCHAR(255),
+    locked_by VARCHAR(255),
+    locked_at TIMESTAMPTZ,
+    last_error TEXT,
+    created_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    finished_at TIMESTAMPTZ
+);
+
+-- Workers poll for due queued jobs
+CREATE INDEX idx_jobs_queued ON jobs(run_at) WHERE status = 'queued';
+CREATE INDEX idx_jobs_status_created ON jobs(status, created_at DESC);
+
+-- At most one pending job per dedupe key
+CREATE UNIQUE INDEX idx_jobs_dedupe_pending ON jobs(dedupe_key)
+    WHERE status IN ('queued', 'running');
+
+CREATE TABLE job_schedules (
+    name VARCHAR(100) PRIMARY KEY,
+    kind VARCHAR(100) NOT NULL,
+    cron VARCHAR(255) NOT NULL,
+    enabled BOOLEAN NOT NULL DEFAULT TRUE,
+    next_run_at TIMESTAMPTZ NOT NULL,
+    last_run_at TIMESTAMPTZ
+);
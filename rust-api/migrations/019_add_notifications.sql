@@ -0,0 +1,14 @@
+-- Email notifications: per-user opt-out and reminder bookkeeping
+CREATE TABLE notification_preferences (
+    email VARCHAR(255) PRIMARY KEY,
+    email_enabled BOOLEAN NOT NULL DEFAULT TRUE,
+    -- Templates the user does not want even with email enabled
+    muted TEXT[] NOT NULL DEFAULT '{}',
+    updated_at TIMESTAMPTZ NOT NULL DEFAULT NOW()
+);
+
+-- Each open condition is reminded about once before its deadline
+ALTER TABLE report_conditions ADD COLUMN reminded_at TIMESTAMPTZ;
+
+-- The expires_at last reminded about; a re-approval with a new expiry is reminded again
+ALTER TABLE mcp_server_reports ADD COLUMN expiry_reminded_for TIMESTAMPTZ;
@@ -0,0 +1,47 @@
+-- Report change feed for the live event stream. A trigger records every
+-- creation, status change and deletion, however it happened, and notifies
+-- listeners on all replicas when the change commits.
+CREATE TABLE report_events (
+    id BIGSERIAL PRIMARY KEY,
+    event_type VARCHAR(50) NOT NULL,
+    report_id UUID NOT NULL,
+    server_name VARCHAR(255) NOT NULL,
+    developer_email VARCHAR(255) NOT NULL,
+    status VARCHAR(50) NOT NULL,
+    previous_status VARCHAR(50),
+    version INTEGER NOT NULL,
+    created_at TIMESTAMPTZ NOT NULL DEFAULT NOW()
+);
+
+CREATE INDEX idx_report_events_created_at ON report_events(created_at);
+
+CREATE FUNCTION record_report_event() RETURNS trigger AS $$
+DECLARE
+    event report_events;
+BEGIN
+    IF TG_OP = 'INSERT' THEN
+        INSERT INTO report_events (event_type, report_id, server_name, developer_email, status, version)
+        VALUES ('report.created', NEW.id, NEW.server_name, NEW.developer_email, NEW.status, NEW.version)
+        RETURNING * INTO event;
+    ELSIF TG_OP = 'UPDATE' THEN
+        IF NEW.status IS NOT DISTINCT FROM OLD.status THEN
+            RETURN NULL;
+        END IF;
+        INSERT INTO report_events
+            (event_type, report_id, server_name, developer_email, status, previous_status, version)
+        VALUES ('report.status_changed', NEW.id, NEW.server_name, NEW.developer_email, NEW.status,
+                OLD.status, NEW.version)
+        RETURNING * INTO event;
+    ELSE
+        INSERT INTO report_events (event_type, report_id, server_name, developer_email, status, version)
+        VALUES ('report.deleted', OLD.id, OLD.server_name, OLD.developer_email, OLD.status, OLD.version)
+        RETURNING * INTO event;
+    END IF;
+    PERFORM pg_notify('report_events', row_to_json(event)::text);
+    RETURN NULL;
+END;
+$$ LANGUAGE plpgsql;
+
+CREATE TRIGGER report_events_trigger
+    AFTER INSERT OR UPDATE OF status OR DELETE ON mcp_server_reports
+    FOR EACH ROW EXECUTE FUNCTION record_report_event();
@@ -0,0 +1,39 @@
+-- Review SLAs: when a report entered its status and its current review, and
+-- how far reminders about an overdue review have escalated
+ALTER TABLE mcp_server_reports
+    ADD COLUMN status_changed_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    ADD COLUMN review_started_at TIMESTAMPTZ,
+    ADD COLUMN sla_reminder_level SMALLINT NOT NULL DEFAULT 0;
+
+UPDATE mcp_server_reports
+SET status_changed_at = CASE status
+        WHEN 'pending_review' THEN submitted_at
+        WHEN 'in_review' THEN COALESCE(claimed_at, submitted_at)
+        ELSE COALESCE(reviewed_at, submitted_at)
+    END,
+    review_started_at = CASE
+        WHEN status IN ('pending_review', 'in_review') THEN submitted_at
+    END;
+
+-- Claiming and releasing a report moves it between pending_review and
+-- in_review without restarting its review clock.
+CREATE FUNCTION track_status_times() RETURNS trigger AS $$
+BEGIN
+    IF TG_OP = 'INSERT' OR NEW.status IS DISTINCT FROM OLD.status THEN
+        NEW.status_changed_at := NOW();
+        IF NEW.status IN ('pending_review', 'in_review')
+           AND (TG_OP = 'INSERT' OR OLD.status NOT IN ('pending_review', 'in_review')) THEN
+            NEW.review_started_at := NOW();
+            NEW.sla_reminder_level := 0;
+        END IF;
+    END IF;
+    RETURN NEW;
+END;
+$$ LANGUAGE plpgsql;
+
+CREATE TRIGGER track_status_times_trigger
+    BEFORE INSERT OR UPDATE OF status ON mcp_server_reports
+    FOR EACH ROW EXECUTE FUNCTION track_status_times();
+
+CREATE INDEX idx_reports_open_reviews ON mcp_server_reports(review_started_at)
+    WHERE status IN ('pending_review', 'in_review');
@@ -0,0 +1,23 @@
+-- Decisions of the Kubernetes admission webhook, including those audit mode
+-- let through, so a rollout can see what enforcement would refuse. Dry runs
+-- are not recorded (the webhook is registered with sideEffects: NoneOnDryRun).
+CREATE TABLE admission_decisions (
+    id UUID PRIMARY KEY DEFAULT gen_random_uuid(),
+    request_uid VARCHAR(255) NOT NULL,
+    operation VARCHAR(20) NOT NULL,
+    kind VARCHAR(100) NOT NULL,
+    namespace VARCHAR(255),
+    name VARCHAR(255),
+    server_name VARCHAR(255),
+    approval_id UUID,
+    -- What the approval check decided, before audit mode lets it through
+    approved BOOLEAN NOT NULL,
+    allowed BOOLEAN NOT NULL,
+    mode VARCHAR(20) NOT NULL,
+    reason TEXT NOT NULL,
+    created_at TIMESTAMPTZ NOT NULL DEFAULT NOW()
+);
+
+CREATE INDEX idx_admission_decisions_created_at ON admission_decisions(created_at DESC);
+CREATE INDEX idx_admission_decisions_denied ON admission_decisions(created_at DESC)
+    WHERE NOT approved;
@@ -0,0 +1,12 @@
+-- Signed approval attestations: a DSSE envelope per approval, so approvals
+-- can be verified without trusting the database row
+CREATE TABLE report_attestations (
+    id UUID PRIMARY KEY DEFAULT gen_random_uuid(),
+    report_id UUID NOT NULL REFERENCES mcp_server_reports(id) ON DELETE CASCADE,
+    status VARCHAR(50) NOT NULL,
+    key_id VARCHAR(64) NOT NULL,
+    envelope JSONB NOT NULL,
+    created_at TIMESTAMPTZ NOT NULL DEFAULT NOW()
+);
+
+CREATE INDEX idx_report_attestations_report ON report_attestations(report_id, created_at DESC);
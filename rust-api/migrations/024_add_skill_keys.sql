@@ -0,0 +1,17 @@
+-- Public keys of the go-live skill and MCP-server installations allowed to
+-- sign submissions. A key can be restricted to one server's reports.
+CREATE TABLE skill_keys (
+    key_id VARCHAR(64) PRIMARY KEY,
+    name VARCHAR(255) NOT NULL,
+    public_key TEXT NOT NULL,
+    server_name VARCHAR(255),
+    created_by VARCHAR(255) NOT NULL,
+    created_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    revoked_at TIMESTAMPTZ,
+    revoked_by VARCHAR(255)
+);
+
+-- Whether the submitted content carried a valid signature from a registered key
+ALTER TABLE mcp_server_reports
+    ADD COLUMN verified_origin BOOLEAN NOT NULL DEFAULT FALSE,
+    ADD COLUMN origin_key_id VARCHAR(64) REFERENCES skill_keys(key_id);
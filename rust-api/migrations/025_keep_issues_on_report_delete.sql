@@ -0,0 +1,11 @@
+-- Deleting a report must not delete the issues it first or last reported,
+-- nor, through report_issues, their occurrences in every other report.
+ALTER TABLE issues
+    ALTER COLUMN first_seen_report_id DROP NOT NULL,
+    ALTER COLUMN last_seen_report_id DROP NOT NULL,
+    DROP CONSTRAINT issues_first_seen_report_id_fkey,
+    DROP CONSTRAINT issues_last_seen_report_id_fkey,
+    ADD CONSTRAINT issues_first_seen_report_id_fkey
+        FOREIGN KEY (first_seen_report_id) REFERENCES mcp_server_reports(id) ON DELETE SET NULL,
+    ADD CONSTRAINT issues_last_seen_report_id_fkey
+        FOREIGN KEY (last_seen_report_id) REFERENCES mcp_server_reports(id) ON DELETE SET NULL;
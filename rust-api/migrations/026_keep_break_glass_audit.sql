@@ -0,0 +1,3 @@
+-- Audit entries must outlive the report they are about: keep the report id
+-- as a plain value instead of a foreign key that cascades on delete.
+ALTER TABLE break_glass_audit DROP CONSTRAINT break_glass_audit_report_id_fkey;
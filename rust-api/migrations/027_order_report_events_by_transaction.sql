@@ -0,0 +1,8 @@
+-- Event ids are drawn when a change is made, not when it commits, so a
+-- transaction can commit an event with a lower id than one already streamed.
+-- Record the writing transaction so events can be delivered in transaction
+-- order, once no transaction that could still add an earlier one is open.
+ALTER TABLE report_events
+    ADD COLUMN xact_id BIGINT NOT NULL DEFAULT pg_current_xact_id()::text::bigint;
+
+CREATE INDEX idx_report_events_xact_id ON report_events(xact_id, id);
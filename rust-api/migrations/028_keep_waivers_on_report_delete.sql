@@ -0,0 +1,7 @@
+-- A waiver records who accepted a risk and why; deleting the report it was
+-- granted on must not erase it, nor lift a server-scoped waiver.
+ALTER TABLE issue_waivers
+    ALTER COLUMN report_id DROP NOT NULL,
+    DROP CONSTRAINT issue_waivers_report_id_fkey,
+    ADD CONSTRAINT issue_waivers_report_id_fkey
+        FOREIGN KEY (report_id) REFERENCES mcp_server_reports(id) ON DELETE SET NULL;
@@ -0,0 +1,3 @@
+-- The four-eyes rule compares approvers with the submitter. developer_email
+-- comes from the request body, so record the authenticated caller as well.
+ALTER TABLE mcp_server_reports ADD COLUMN submitted_by VARCHAR(255);
@@ -0,0 +1,15 @@
+-- Scan findings used to keep the first characters of each match as a
+-- preview. Replace the preview with the match length it ended with.
+UPDATE mcp_server_reports
+SET scan_findings = (
+    SELECT jsonb_agg(
+        (f - 'preview')
+        || jsonb_build_object(
+            'length',
+            COALESCE(substring(f->>'preview' FROM '\((\d+) chars\)$')::int, 0)
+        )
+        ORDER BY ordinality
+    )
+    FROM jsonb_array_elements(scan_findings) WITH ORDINALITY AS t(f, ordinality)
+)
+WHERE scan_findings <> '[]'::jsonb;
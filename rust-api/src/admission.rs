@@ -0,0 +1,464 @@
+use axum::{
+    extract::{Query, State},
+    response::Json,
+};
+use chrono::{DateTime, Duration, Utc};
+use serde::{Deserialize, Serialize};
+use serde_json::{json, Value as JsonValue};
+use sqlx::PgPool;
+use std::str::FromStr;
+use std::sync::Arc;
+use uuid::Uuid;
+
+use crate::allowlist::{self, AllowedServer};
+use crate::auth::CurrentUser;
+use crate::config::Config;
+use crate::error::AppError;
+
+// ============================================================================
+// Kubernetes Admission Webhook
+// ============================================================================
+
+pub const API_VERSION: &str = "admission.k8s.io/v1";
+
+/// Whether the webhook refuses unapproved workloads (`ADMISSION_MODE`).
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum AdmissionMode {
+    /// Deny workloads of servers without a valid approval.
+    #[default]
+    Enforce,
+    /// Allow everything, warn and record what enforcement would deny.
+    Audit,
+}
+
+impl AdmissionMode {
+    fn as_str(self) -> &'static str {
+        match self {
+            AdmissionMode::Enforce => "enforce",
+            AdmissionMode::Audit => "audit",
+        }
+    }
+}
+
+impl FromStr for AdmissionMode {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.to_ascii_lowercase().as_str() {
+            "enforce" => Ok(AdmissionMode::Enforce),
+            "audit" => Ok(AdmissionMode::Audit),
+            other => Err(format!("unknown admission mode: {}", other)),
+        }
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct AdmissionConfig {
+    pub mode: AdmissionMode,
+    /// Annotation or label naming the MCP server a workload runs
+    /// (`ADMISSION_SERVER_KEY`).
+    pub server_key: String,
+    /// How long decisions are kept (`ADMISSION_LOG_RETENTION_DAYS`).
+    pub log_retention_days: i64,
+}
+
+impl Default for AdmissionConfig {
+    fn default() -> Self {
+        Self {
+            mode: AdmissionMode::default(),
+            server_key: "golive.io/mcp-server".to_string(),
+            log_retention_days: 30,
+        }
+    }
+}
+
+/// The parts of an `admission.k8s.io/v1` `AdmissionReview` the webhook reads.
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct AdmissionReview {
+    api_version: String,
+    request: Option<AdmissionRequest>,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct AdmissionRequest {
+    uid: String,
+    kind: GroupVersionKind,
+    operation: String,
+    namespace: Option<String>,
+    name: Option<String>,
+    object: Option<JsonValue>,
+    #[serde(default)]
+    dry_run: bool,
+}
+
+#[derive(Debug, Deserialize)]
+struct GroupVersionKind {
+    kind: String,
+}
+
+/// Metadata that can name the server: the object's own, then its pod
+/// template's (Deployments, StatefulSets, Jobs, ...) and a CronJob's.
+const METADATA_PATHS: [&[&str]; 3] = [
+    &["metadata"],
+    &["spec", "template", "metadata"],
+    &["spec", "jobTemplate", "spec", "template", "metadata"],
+];
+
+/// The MCP server `object` runs, from the `key` annotation or label.
+fn server_name(object: &JsonValue, key: &str) -> Option<String> {
+    METADATA_PATHS
+        .iter()
+        .filter_map(|path| {
+            path.iter()
+                .try_fold(object, |value, field| value.get(field))
+        })
+        .flat_map(|metadata| ["annotations", "labels"].map(|field| metadata.get(field)))
+        .flatten()
+        .find_map(|values| values.get(key)?.as_str())
+        .map(str::trim)
+        .filter(|name| !name.is_empty())
+        .map(String::from)
+}
+
+/// Whether a workload of `server_name` may run, before the mode applies.
+#[derive(Debug)]
+struct Decision {
+    approved: bool,
+    reason: String,
+    approval_id: Option<Uuid>,
+    warnings: Vec<String>,
+}
+
+fn decide(
+    server_name: &str,
+    approval: Option<AllowedServer>,
+    now: DateTime<Utc>,
+    notice_days: i64,
+) -> Decision {
+    let Some(approval) = approval else {
+        return Decision {
+            approved: false,
+            reason: format!(
+                "MCP server {} has no valid go-live approval; submit a report and have it approved",
+                server_name
+            ),
+            approval_id: None,
+            warnings: Vec::new(),
+        };
+    };
+    let mut warnings = Vec::new();
+    if approval.status == "approved_with_conditions" {
+        warnings.push(format!(
+            "MCP server {} is approved with conditions (report {})",
+            server_name, approval.approval_id
+        ));
+    }
+    if let Some(expires_at) = approval
+        .expires_at
+        .filter(|at| *at <= now + Duration::days(notice_days))
+    {
+        warnings.push(format!(
+            "The go-live approval of {} expires {}; submit a fresh report to re-certify",
+            server_name,
+            expires_at.format("%Y-%m-%d")
+        ));
+    }
+    Decision {
+        approved: true,
+        reason: format!(
+            "MCP server {} is approved by report {}",
+            server_name, approval.approval_id
+        ),
+        approval_id: Some(approval.approval_id),
+        warnings,
+    }
+}
+
+/// The `AdmissionReview` answering `uid`.
+fn review_response(uid: &str, allowed: bool, message: &str, warnings: &[String]) -> JsonValue {
+    let mut response = json!({
+        "uid": uid,
+        "allowed": allowed,
+        "auditAnnotations": { "decision": message },
+    });
+    if !allowed {
+        response["status"] = json!({ "code": 403, "reason": "Forbidden", "message": message });
+    }
+    if !warnings.is_empty() {
+        response["warnings"] = json!(warnings);
+    }
+    json!({
+        "apiVersion": API_VERSION,
+        "kind": "AdmissionReview",
+        "response": response,
+    })
+}
+
+/// `POST /api/v1/admission/validate`: the `ValidatingAdmissionWebhook`
+/// backend. Workloads naming an MCP server are admitted only while that
+/// server has a valid, unexpired approval; other workloads pass untouched.
+pub async fn validate(
+    State(pool): State<PgPool>,
+    State(config): State<Arc<Config>>,
+    Json(review): Json<AdmissionReview>,
+) -> Result<Json<JsonValue>, AppError> {
+    if review.api_version != API_VERSION {
+        return Err(AppError::BadRequest(format!(
+            "Unsupported AdmissionReview version {}; expected {}",
+            review.api_version, API_VERSION
+        )));
+    }
+    let request = review
+        .request
+        .ok_or_else(|| AppError::BadRequest("AdmissionReview has no request".to_string()))?;
+
+    let server = match request.operation.as_str() {
+        "CREATE" | "UPDATE" => request
+            .object
+            .as_ref()
+            .and_then(|object| server_name(object, &config.admission.server_key)),
+        _ => None,
+    };
+    let Some(server) = server else {
+        return Ok(Json(review_response(
+            &request.uid,
+            true,
+            "not an MCP server workload",
+            &[],
+        )));
+    };
+
+    let approval = allowlist::find(&pool, &config, &server).await?;
+    let mut decision = decide(&server, approval, Utc::now(), config.validity.notice_days);
+    let mode = config.admission.mode;
+    let allowed = decision.approved || mode == AdmissionMode::Audit;
+    if !decision.approved && allowed {
+        decision
+            .warnings
+            .insert(0, format!("[audit] would be denied: {}", decision.reason));
+    }
+
+    // The webhook declares no side effects on dry runs.
+    if !request.dry_run {
+        sqlx::query(
+            r#"
+            INSERT INTO admission_decisions
+                (request_uid, operation, kind, namespace, name, server_name, approval_id,
+                 approved, allowed, mode, reason)
+            VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9, $10, $11)
+            "#,
+        )
+        .bind(&request.uid)
+        .bind(&request.operation)
+        .bind(&request.kind.kind)
+        .bind(&request.namespace)
+        .bind(request.name.as_deref().filter(|n| !n.is_empty()))
+        .bind(&server)
+        .bind(decision.approval_id)
+        .bind(decision.approved)
+        .bind(allowed)
+        .bind(mode.as_str())
+        .bind(&decision.reason)
+        .execute(&pool)
+        .await
+        .map_err(AppError::db("Failed to record admission decision"))?;
+    }
+
+    if decision.approved {
+        tracing::info!(
+            "Admitted {} {}/{}: {}",
+            request.kind.kind,
+            request.namespace.as_deref().unwrap_or("-"),
+            request.name.as_deref().unwrap_or("-"),
+            decision.reason
+        );
+    } else {
+        tracing::warn!(
+            "{} {} {}/{} ({} mode): {}",
+            if allowed { "Would deny" } else { "Denied" },
+            request.kind.kind,
+            request.namespace.as_deref().unwrap_or("-"),
+            request.name.as_deref().unwrap_or("-"),
+            mode.as_str(),
+            decision.reason
+        );
+    }
+    Ok(Json(review_response(
+        &request.uid,
+        allowed,
+        &decision.reason,
+        &decision.warnings,
+    )))
+}
+
+#[derive(Debug, Serialize, sqlx::FromRow)]
+pub struct AdmissionDecision {
+    id: Uuid,
+    request_uid: String,
+    operation: String,
+    kind: String,
+    namespace: Option<String>,
+    name: Option<String>,
+    server_name: Option<String>,
+    approval_id: Option<Uuid>,
+    approved: bool,
+    allowed: bool,
+    mode: String,
+    reason: String,
+    created_at: DateTime<Utc>,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct DecisionQuery {
+    /// Only workloads without a valid approval, whether or not they were let through.
+    #[serde(default)]
+    unapproved: bool,
+    server_name: Option<String>,
+    limit: Option<i64>,
+}
+
+/// Admin-only log of admission decisions, newest first.
+pub async fn list_decisions(
+    State(pool): State<PgPool>,
+    State(config): State<Arc<Config>>,
+    user: CurrentUser,
+    Query(query): Query<DecisionQuery>,
+) -> Result<Json<Vec<AdmissionDecision>>, AppError> {
+    if !user.is_admin(&config.auth) {
+        return Err(AppError::Forbidden(
+            "The admission decision log is restricted to admins".to_string(),
+        ));
+    }
+    let limit = query.limit.unwrap_or(100);
+    if !(1..=1000).contains(&limit) {
+        return Err(AppError::BadRequest(
+            "limit must be between 1 and 1000".to_string(),
+        ));
+    }
+    let decisions = sqlx::query_as::<_, AdmissionDecision>(
+        r#"
+        SELECT * FROM admission_decisions
+        WHERE (NOT $1 OR NOT approved) AND ($2::text IS NULL OR server_name = $2)
+        ORDER BY created_at DESC
+        LIMIT $3
+        "#,
+    )
+    .bind(query.unapproved)
+    .bind(&query.server_name)
+    .bind(limit)
+    .fetch_all(&pool)
+    .await
+    .map_err(AppError::db("Failed to list admission decisions"))?;
+    Ok(Json(decisions))
+}
+
+/// Drops decisions older than `retention_days`.
+pub async fn purge_decisions(pool: &PgPool, retention_days: i64) -> Result<u64, sqlx::Error> {
+    let purged = sqlx::query(
+        "DELETE FROM admission_decisions WHERE created_at < NOW() - make_interval(days => $1::int)",
+    )
+    .bind(retention_days as i32)
+    .execute(pool)
+    .await?;
+    Ok(purged.rows_affected())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const KEY: &str = "golive.io/mcp-server";
+
+    fn approval(status: &str, expires_in_days: i64) -> AllowedServer {
+        AllowedServer {
+            server_name: "weather-mcp".to_string(),
+            repository_url: "https://github.com/acme/weather-mcp".to_string(),
+            approval_id: Uuid::nil(),
+            status: status.to_string(),
+            approved_at: None,
+            approved_by: None,
+            expires_at: Some(Utc::now() + Duration::days(expires_in_days)),
+            risk_level: None,
+            permitted_content_types: vec!["text".to_string()],
+        }
+    }
+
+    #[test]
+    fn test_server_name_from_metadata() {
+        let deployment = json!({
+            "metadata": {"name": "weather", "labels": {"app": "weather"}},
+            "spec": {"template": {"metadata": {"annotations": {KEY: " weather-mcp "}}}}
+        });
+        assert_eq!(
+            server_name(&deployment, KEY).as_deref(),
+            Some("weather-mcp")
+        );
+
+        let cron_job = json!({
+            "spec": {"jobTemplate": {"spec": {"template": {"metadata": {"labels": {KEY: "sync-mcp"}}}}}}
+        });
+        assert_eq!(server_name(&cron_job, KEY).as_deref(), Some("sync-mcp"));
+
+        let own = json!({
+            "metadata": {"labels": {KEY: "own"}},
+            "spec": {"template": {"metadata": {"labels": {KEY: "template"}}}}
+        });
+        assert_eq!(server_name(&own, KEY).as_deref(), Some("own"));
+        assert_eq!(
+            server_name(&json!({"metadata": {"labels": {KEY: ""}}}), KEY),
+            None
+        );
+        assert_eq!(server_name(&json!({"kind": "ConfigMap"}), KEY), None);
+    }
+
+    #[test]
+    fn test_decide() {
+        let now = Utc::now();
+        let denied = decide("weather-mcp", None, now, 30);
+        assert!(!denied.approved);
+        assert!(denied.reason.contains("no valid go-live approval"));
+
+        let approved = decide("weather-mcp", Some(approval("approved", 90)), now, 30);
+        assert!(approved.approved);
+        assert_eq!(approved.approval_id, Some(Uuid::nil()));
+        assert!(approved.warnings.is_empty());
+
+        let lapsing = decide(
+            "weather-mcp",
+            Some(approval("approved_with_conditions", 10)),
+            now,
+            30,
+        );
+        assert!(lapsing.approved);
+        assert_eq!(lapsing.warnings.len(), 2);
+    }
+
+    #[test]
+    fn test_review_round_trip() {
+        let review: AdmissionReview = serde_json::from_value(json!({
+            "apiVersion": API_VERSION,
+            "kind": "AdmissionReview",
+            "request": {
+                "uid": "705ab4f5-6393-11e8-b7cc-42010a800002",
+                "kind": {"group": "apps", "version": "v1", "kind": "Deployment"},
+                "operation": "CREATE",
+                "namespace": "mcp",
+                "name": "weather",
+                "object": {"metadata": {"labels": {KEY: "weather-mcp"}}},
+                "dryRun": true
+            }
+        }))
+        .unwrap();
+        let request = review.request.unwrap();
+        assert!(request.dry_run);
+        assert_eq!(request.kind.kind, "Deployment");
+
+        let response = review_response(&request.uid, false, "no approval", &[]);
+        assert_eq!(response["response"]["uid"], request.uid);
+        assert_eq!(response["response"]["allowed"], false);
+        assert_eq!(response["response"]["status"]["code"], 403);
+        assert!(response["response"].get("warnings").is_none());
+    }
+}
@@ -0,0 +1,345 @@
+use axum::{
+    extract::{Query, State},
+    http::{header, HeaderMap, HeaderValue, StatusCode},
+    response::{IntoResponse, Response},
+};
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use serde_json::Value as JsonValue;
+use sha2::{Digest, Sha256};
+use sqlx::PgPool;
+use std::sync::Arc;
+use uuid::Uuid;
+
+use crate::concurrency;
+use crate::config::Config;
+use crate::error::AppError;
+use crate::signing::{self, Signer};
+use crate::webhooks::SIGNATURE_HEADER;
+
+// ============================================================================
+// Gateway Allow-List
+// ============================================================================
+
+/// Content types an MCP tool result can carry.
+pub const CONTENT_TYPES: [&str; 5] = ["text", "image", "audio", "resource_link", "resource"];
+
+/// Bumped when the allow-list shape changes incompatibly.
+const SCHEMA_VERSION: u32 = 1;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Format {
+    Json,
+    Yaml,
+}
+
+impl Format {
+    /// `?format=` wins over `Accept`; JSON unless YAML is asked for.
+    fn negotiate(query: Option<&str>, headers: &HeaderMap) -> Result<Self, AppError> {
+        match query.map(|f| f.trim().to_ascii_lowercase()).as_deref() {
+            Some("json") => return Ok(Format::Json),
+            Some("yaml" | "yml") => return Ok(Format::Yaml),
+            Some(other) => {
+                return Err(AppError::BadRequest(format!(
+                    "Unknown format {}; expected json or yaml",
+                    other
+                )))
+            }
+            None => {}
+        }
+        let accept = headers
+            .get(header::ACCEPT)
+            .and_then(|v| v.to_str().ok())
+            .unwrap_or_default();
+        Ok(if accept.contains("yaml") {
+            Format::Yaml
+        } else {
+            Format::Json
+        })
+    }
+
+    fn content_type(self) -> &'static str {
+        match self {
+            Format::Json => "application/json",
+            Format::Yaml => "application/yaml",
+        }
+    }
+}
+
+#[derive(sqlx::FromRow)]
+struct ApprovalRow {
+    server_name: String,
+    repository_url: String,
+    approval_id: Uuid,
+    status: String,
+    approved_at: Option<DateTime<Utc>>,
+    approved_by: Option<String>,
+    expires_at: Option<DateTime<Utc>>,
+    report_json: JsonValue,
+}
+
+/// A server the gateway may route to.
+#[derive(Debug, Serialize)]
+pub struct AllowedServer {
+    pub server_name: String,
+    pub repository_url: String,
+    /// The approved report this entry rests on.
+    pub approval_id: Uuid,
+    pub status: String,
+    pub approved_at: Option<DateTime<Utc>>,
+    pub approved_by: Option<String>,
+    pub expires_at: Option<DateTime<Utc>>,
+    pub risk_level: Option<String>,
+    pub permitted_content_types: Vec<String>,
+}
+
+#[derive(Debug, Serialize)]
+pub struct Allowlist {
+    schema_version: u32,
+    /// Key the response signature was made with.
+    key_id: String,
+    servers: Vec<AllowedServer>,
+}
+
+/// What the report says the server may return, from
+/// `phase1_security.permitted_content_types`; `default` when it says nothing.
+/// Unknown types are dropped rather than passed on to the gateway.
+fn permitted_content_types(report_json: &JsonValue, default: &[String]) -> Vec<String> {
+    let Some(listed) = report_json
+        .get("phase1_security")
+        .and_then(|s| s.get("permitted_content_types"))
+        .and_then(JsonValue::as_array)
+    else {
+        return default.to_vec();
+    };
+    let mut types: Vec<String> = listed
+        .iter()
+        .filter_map(JsonValue::as_str)
+        .map(|t| t.trim().to_ascii_lowercase())
+        .filter(|t| CONTENT_TYPES.contains(&t.as_str()))
+        .collect();
+    types.sort();
+    types.dedup();
+    types
+}
+
+fn entry(row: ApprovalRow, default_types: &[String]) -> AllowedServer {
+    AllowedServer {
+        risk_level: crate::recertification::risk_level(&row.report_json)
+            .map(|r| r.trim().to_ascii_uppercase()),
+        permitted_content_types: permitted_content_types(&row.report_json, default_types),
+        server_name: row.server_name,
+        repository_url: row.repository_url,
+        approval_id: row.approval_id,
+        status: row.status,
+        approved_at: row.approved_at,
+        approved_by: row.approved_by,
+        expires_at: row.expires_at,
+    }
+}
+
+/// Current approvals: for each server (or just `server_name`), its latest
+/// decided report if that is an approval that has not expired. Rejections
+/// count as decisions, so they supersede older approvals.
+async fn approvals(
+    pool: &PgPool,
+    server_name: Option<&str>,
+) -> Result<Vec<ApprovalRow>, sqlx::Error> {
+    sqlx::query_as::<_, ApprovalRow>(
+        r#"
+        WITH latest AS (
+            SELECT DISTINCT ON (server_name) *
+            FROM mcp_server_reports
+            WHERE status IN ('approved', 'approved_with_conditions', 'rejected', 'expired')
+              AND ($1::text IS NULL OR server_name = $1)
+            ORDER BY server_name, submitted_at DESC
+        )
+        SELECT server_name, repository_url, id AS approval_id, status,
+               reviewed_at AS approved_at, reviewed_by AS approved_by, expires_at, report_json
+        FROM latest
+        WHERE status IN ('approved', 'approved_with_conditions')
+          AND (expires_at IS NULL OR expires_at > NOW())
+        ORDER BY server_name
+        "#,
+    )
+    .bind(server_name)
+    .fetch_all(pool)
+    .await
+}
+
+/// The allow-list entry of `server_name`, if it is currently approved.
+pub async fn find(
+    pool: &PgPool,
+    config: &Config,
+    server_name: &str,
+) -> Result<Option<AllowedServer>, AppError> {
+    let rows = approvals(pool, Some(server_name))
+        .await
+        .map_err(AppError::db("Failed to look up the server's approval"))?;
+    Ok(rows
+        .into_iter()
+        .next()
+        .map(|row| entry(row, &config.allowlist_content_types)))
+}
+
+/// Servers whose latest decided report is an approval that has not expired.
+pub async fn load(pool: &PgPool, config: &Config) -> Result<Allowlist, AppError> {
+    let rows = approvals(pool, None)
+        .await
+        .map_err(AppError::db("Failed to load the allow-list"))?;
+
+    Ok(Allowlist {
+        schema_version: SCHEMA_VERSION,
+        key_id: config.signing.key_id().to_string(),
+        servers: rows
+            .into_iter()
+            .map(|row| entry(row, &config.allowlist_content_types))
+            .collect(),
+    })
+}
+
+fn render(allowlist: &Allowlist, format: Format) -> Vec<u8> {
+    match format {
+        Format::Json => serde_json::to_vec_pretty(allowlist).expect("allow-lists serialize"),
+        Format::Yaml => serde_norway::to_string(allowlist)
+            .expect("allow-lists serialize")
+            .into_bytes(),
+    }
+}
+
+/// Strong ETag over the rendered body, so it changes exactly when the bytes do.
+fn etag(body: &[u8]) -> HeaderValue {
+    let digest = hex::encode(Sha256::digest(body));
+    HeaderValue::from_str(&format!("\"{}\"", &digest[..32])).expect("hex is a valid header value")
+}
+
+/// The response headers shared by `200` and `304`.
+fn headers(etag: HeaderValue, signer: &Signer) -> [(&'static str, HeaderValue); 4] {
+    [
+        (header::ETAG.as_str(), etag),
+        (header::VARY.as_str(), HeaderValue::from_static("accept")),
+        (
+            header::CACHE_CONTROL.as_str(),
+            HeaderValue::from_static("no-cache"),
+        ),
+        (
+            signing::KEY_ID_HEADER,
+            HeaderValue::from_str(signer.key_id()).expect("hex is a valid header value"),
+        ),
+    ]
+}
+
+#[derive(Debug, Deserialize)]
+pub struct AllowlistQuery {
+    format: Option<String>,
+}
+
+/// `GET /api/v1/servers/allowlist`: approved servers for the MCP gateway, as
+/// JSON or YAML. The body is signed with the service key
+/// (`x-golive-signature: ed25519=<base64>`); poll with `If-None-Match`.
+pub async fn get_allowlist(
+    State(pool): State<PgPool>,
+    State(config): State<Arc<Config>>,
+    Query(query): Query<AllowlistQuery>,
+    request_headers: HeaderMap,
+) -> Result<Response, AppError> {
+    let format = Format::negotiate(query.format.as_deref(), &request_headers)?;
+    let allowlist = load(&pool, &config).await?;
+    let body = render(&allowlist, format);
+    let etag = etag(&body);
+
+    if concurrency::not_modified(&request_headers, &etag) {
+        return Ok((StatusCode::NOT_MODIFIED, headers(etag, &config.signing)).into_response());
+    }
+    tracing::info!(
+        "Served allow-list of {} server(s) as {:?}",
+        allowlist.servers.len(),
+        format
+    );
+    let signature = config.signing.signature_header(&body);
+    Ok((
+        headers(etag, &config.signing),
+        [
+            (
+                header::CONTENT_TYPE.as_str(),
+                HeaderValue::from_static(format.content_type()),
+            ),
+            (SIGNATURE_HEADER, signature),
+        ],
+        body,
+    )
+        .into_response())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    fn allowlist() -> Allowlist {
+        let row = ApprovalRow {
+            server_name: "weather-mcp".to_string(),
+            repository_url: "https://github.com/acme/weather-mcp".to_string(),
+            approval_id: Uuid::nil(),
+            status: "approved".to_string(),
+            approved_at: None,
+            approved_by: Some("rev@acme.test".to_string()),
+            expires_at: None,
+            report_json: json!({"phase1_security": {"risk_level": "High"}}),
+        };
+        Allowlist {
+            schema_version: SCHEMA_VERSION,
+            key_id: "0123456789abcdef".to_string(),
+            servers: vec![entry(row, &["text".to_string()])],
+        }
+    }
+
+    #[test]
+    fn test_permitted_content_types() {
+        let default = vec!["text".to_string(), "resource".to_string()];
+        assert_eq!(permitted_content_types(&json!({}), &default), default);
+        assert_eq!(
+            permitted_content_types(
+                &json!({"phase1_security": {"permitted_content_types": ["TEXT", "image", "video", "text"]}}),
+                &default
+            ),
+            vec!["image", "text"]
+        );
+        assert!(permitted_content_types(
+            &json!({"phase1_security": {"permitted_content_types": []}}),
+            &default
+        )
+        .is_empty());
+    }
+
+    #[test]
+    fn test_negotiate_format() {
+        let mut headers = HeaderMap::new();
+        assert_eq!(Format::negotiate(None, &headers).unwrap(), Format::Json);
+        headers.insert(header::ACCEPT, HeaderValue::from_static("application/yaml"));
+        assert_eq!(Format::negotiate(None, &headers).unwrap(), Format::Yaml);
+        assert_eq!(
+            Format::negotiate(Some("json"), &headers).unwrap(),
+            Format::Json
+        );
+        assert!(Format::negotiate(Some("xml"), &headers).is_err());
+    }
+
+    #[test]
+    fn test_render_is_stable() {
+        let json = render(&allowlist(), Format::Json);
+        let yaml = render(&allowlist(), Format::Yaml);
+        assert_eq!(etag(&json), etag(&render(&allowlist(), Format::Json)));
+        assert_ne!(etag(&json), etag(&yaml));
+
+        let parsed: JsonValue = serde_json::from_slice(&json).unwrap();
+        assert_eq!(parsed["servers"][0]["risk_level"], "HIGH");
+        assert_eq!(
+            parsed["servers"][0]["permitted_content_types"],
+            json!(["text"])
+        );
+        let yaml = String::from_utf8(yaml).unwrap();
+        assert!(yaml.contains("server_name: weather-mcp"));
+        assert!(yaml.contains("- text"));
+    }
+}
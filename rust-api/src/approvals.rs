@@ -0,0 +1,513 @@
+use axum::{
+    extract::{Path, State},
+    http::{header, HeaderMap},
+    response::{IntoResponse, Json, Response},
+};
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use sqlx::{PgExecutor, PgPool, Postgres, Transaction};
+use std::sync::Arc;
+use uuid::Uuid;
+
+use crate::auth::{AuthConfig, CurrentUser};
+use crate::concurrency;
+use crate::conditions::{self, NewCondition};
+use crate::config::Config;
+use crate::error::AppError;
+use crate::separation;
+use crate::{events, webhooks};
+use crate::{fetch_visible_report, Report};
+
+// ============================================================================
+// Multi-Stage Approvals
+// ============================================================================
+
+/// One sign-off step, e.g. security before platform.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ApprovalStage {
+    pub name: String,
+    /// Approvals needed before the next stage opens.
+    pub quorum: usize,
+    /// `APPROVAL_STAGE_<NAME>_MEMBERS`. When empty, any reviewer may vote.
+    pub members: Vec<String>,
+}
+
+impl ApprovalStage {
+    fn accepts(&self, user: &CurrentUser, auth: &AuthConfig) -> bool {
+        if self.members.is_empty() {
+            user.is_reviewer(auth)
+        } else {
+            self.members.contains(&user.email)
+        }
+    }
+}
+
+/// `APPROVAL_STAGES`, in order. Empty keeps the single-step
+/// `PATCH /status` approval.
+#[derive(Debug, Clone, Default)]
+pub struct ApprovalConfig {
+    pub stages: Vec<ApprovalStage>,
+}
+
+impl ApprovalConfig {
+    pub fn enabled(&self) -> bool {
+        !self.stages.is_empty()
+    }
+}
+
+/// Parses `security:1,platform:2` into stage names and quorums.
+pub fn parse_stage_spec(raw: &str) -> Result<Vec<(String, usize)>, String> {
+    let mut stages: Vec<(String, usize)> = Vec::new();
+    for part in raw.split(',').map(str::trim).filter(|p| !p.is_empty()) {
+        let (name, quorum) = part.split_once(':').unwrap_or((part, "1"));
+        let name = name.trim().to_ascii_lowercase();
+        if name.is_empty() || !name.chars().all(|c| c.is_ascii_alphanumeric() || c == '_') {
+            return Err(format!("invalid stage name {:?}", name));
+        }
+        let quorum: usize = quorum
+            .trim()
+            .parse()
+            .ok()
+            .filter(|q| *q > 0)
+            .ok_or_else(|| format!("stage {} needs a quorum of at least 1", name))?;
+        if stages.iter().any(|(n, _)| *n == name) {
+            return Err(format!("stage {} is listed twice", name));
+        }
+        stages.push((name, quorum));
+    }
+    Ok(stages)
+}
+
+#[derive(Debug, sqlx::FromRow)]
+struct Vote {
+    stage: String,
+    voter: String,
+    created_at: DateTime<Utc>,
+}
+
+#[derive(Debug, Serialize)]
+pub struct StageVote {
+    voter: String,
+    approved_at: DateTime<Utc>,
+}
+
+#[derive(Debug, Serialize)]
+pub struct StageStatus {
+    name: String,
+    quorum: usize,
+    approvals: Vec<StageVote>,
+    satisfied: bool,
+}
+
+#[derive(Debug, Serialize)]
+pub struct ApprovalStatus {
+    report_id: Uuid,
+    report_status: String,
+    stages: Vec<StageStatus>,
+    /// The stage currently taking votes; `None` once all are satisfied.
+    current_stage: Option<String>,
+    complete: bool,
+}
+
+/// Counts approvals per stage. Stages are sequential, so votes are only
+/// ever recorded against the first unsatisfied one.
+fn tally(stages: &[ApprovalStage], votes: &[Vote]) -> Vec<StageStatus> {
+    stages
+        .iter()
+        .map(|stage| {
+            let approvals: Vec<StageVote> = votes
+                .iter()
+                .filter(|v| v.stage == stage.name)
+                .map(|v| StageVote {
+                    voter: v.voter.clone(),
+                    approved_at: v.created_at,
+                })
+                .collect();
+            StageStatus {
+                name: stage.name.clone(),
+                quorum: stage.quorum,
+                satisfied: approvals.len() >= stage.quorum,
+                approvals,
+            }
+        })
+        .collect()
+}
+
+fn status_of(report: &Report, stages: Vec<StageStatus>) -> ApprovalStatus {
+    let current_stage = stages.iter().find(|s| !s.satisfied).map(|s| s.name.clone());
+    ApprovalStatus {
+        report_id: report.id,
+        report_status: report.status.clone(),
+        complete: current_stage.is_none(),
+        current_stage,
+        stages,
+    }
+}
+
+/// Human-readable progress, e.g. `security 1/1, platform 0/2`.
+fn progress(stages: &[StageStatus]) -> String {
+    stages
+        .iter()
+        .map(|s| format!("{} {}/{}", s.name, s.approvals.len(), s.quorum))
+        .collect::<Vec<_>>()
+        .join(", ")
+}
+
+async fn active_votes(
+    executor: impl PgExecutor<'_>,
+    report_id: Uuid,
+) -> Result<Vec<Vote>, AppError> {
+    sqlx::query_as::<_, Vote>(
+        r#"
+        SELECT stage, voter, created_at FROM approval_votes
+        WHERE report_id = $1 AND invalidated_at IS NULL AND decision = 'approve'
+        ORDER BY created_at
+        "#,
+    )
+    .bind(report_id)
+    .fetch_all(executor)
+    .await
+    .map_err(AppError::db("Failed to load approvals"))
+}
+
+/// Fails unless every configured stage has its quorum. Called before a
+/// report may become `approved` through any path.
+pub async fn require_complete(
+    tx: &mut Transaction<'_, Postgres>,
+    config: &ApprovalConfig,
+    report_id: Uuid,
+) -> Result<(), AppError> {
+    if !config.enabled() {
+        return Ok(());
+    }
+    let stages = tally(&config.stages, &active_votes(&mut **tx, report_id).await?);
+    if stages.iter().all(|s| s.satisfied) {
+        return Ok(());
+    }
+    Err(AppError::Conflict(format!(
+        "Report {} still needs approvals ({}); vote via POST /api/v1/reports/{}/approvals",
+        report_id,
+        progress(&stages),
+        report_id
+    )))
+}
+
+/// Discards the current round of votes, e.g. when a report is rejected or
+/// sent back to pending review. Votes are kept for the audit trail.
+pub async fn invalidate_votes(
+    tx: &mut Transaction<'_, Postgres>,
+    report_id: Uuid,
+) -> Result<(), AppError> {
+    sqlx::query(
+        "UPDATE approval_votes SET invalidated_at = NOW() WHERE report_id = $1 AND invalidated_at IS NULL",
+    )
+    .bind(report_id)
+    .execute(&mut **tx)
+    .await
+    .map_err(AppError::db("Failed to reset approvals"))?;
+    Ok(())
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum Decision {
+    Approve,
+    Reject,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct VoteRequest {
+    decision: Decision,
+    notes: Option<String>,
+    /// Admin override of the four-eyes rule; audited.
+    break_glass_reason: Option<String>,
+    /// Only on the final approval: makes the outcome `approved_with_conditions`.
+    conditions: Option<Vec<NewCondition>>,
+}
+
+pub async fn get_approvals(
+    State(pool): State<PgPool>,
+    State(config): State<Arc<Config>>,
+    Path(id): Path<Uuid>,
+    user: Option<CurrentUser>,
+) -> Result<Json<ApprovalStatus>, AppError> {
+    let report = fetch_visible_report(&pool, id, user.as_ref()).await?;
+    let votes = active_votes(&pool, id).await?;
+    Ok(Json(status_of(
+        &report,
+        tally(&config.approvals.stages, &votes),
+    )))
+}
+
+pub async fn vote(
+    State(pool): State<PgPool>,
+    State(config): State<Arc<Config>>,
+    Path(id): Path<Uuid>,
+    user: CurrentUser,
+    headers: HeaderMap,
+    Json(req): Json<VoteRequest>,
+) -> Result<Response, AppError> {
+    let approvals = &config.approvals;
+    if !approvals.enabled() {
+        return Err(AppError::BadRequest(
+            "Approval stages are not configured; use PATCH /api/v1/reports/{id}/status".to_string(),
+        ));
+    }
+    let expected = concurrency::if_match(&headers, config.require_if_match)?;
+    if let Some(list) = &req.conditions {
+        if matches!(req.decision, Decision::Reject) {
+            return Err(AppError::Validation(
+                "conditions can only accompany an approval".to_string(),
+            ));
+        }
+        conditions::validate("approved_with_conditions", Some(list))?;
+    }
+    if let Some(notes) = &req.notes {
+        if notes.contains('\0') {
+            return Err(AppError::Validation(
+                "notes must not contain NUL characters".to_string(),
+            ));
+        }
+    }
+
+    let mut tx = pool
+        .begin()
+        .await
+        .map_err(AppError::db("Failed to start transaction"))?;
+
+    // Row lock so concurrent votes are counted one at a time.
+    let report =
+        sqlx::query_as::<_, Report>("SELECT * FROM mcp_server_reports WHERE id = $1 FOR UPDATE")
+            .bind(id)
+            .fetch_optional(&mut *tx)
+            .await
+            .map_err(AppError::db("Failed to fetch report"))?
+            .ok_or_else(|| AppError::report_not_found(id))?;
+    if let Some(v) = expected.version() {
+        if v != report.version {
+            return Err(concurrency::stale(id, report.version, expected));
+        }
+    }
+    if !["pending_review", "in_review"].contains(&report.status.as_str()) {
+        return Err(AppError::Conflict(format!(
+            "Report {} is {}; only open reports take approval votes",
+            id, report.status
+        )));
+    }
+
+    let votes = active_votes(&mut *tx, id).await?;
+    if votes.iter().any(|v| v.voter == user.email) {
+        return Err(AppError::Conflict(format!(
+            "{} has already signed off on report {} in this round",
+            user.email, id
+        )));
+    }
+    let stages = tally(&approvals.stages, &votes);
+    let Some(current) = stages.iter().find(|s| !s.satisfied) else {
+        return Err(AppError::Conflict(format!(
+            "Report {} already has all approvals",
+            id
+        )));
+    };
+    let stage = approvals
+        .stages
+        .iter()
+        .find(|s| s.name == current.name)
+        .expect("tally preserves configured stages");
+    if !stage.accepts(&user, &config.auth) {
+        return Err(AppError::Forbidden(format!(
+            "{} may not vote in the {} stage",
+            user.email, stage.name
+        )));
+    }
+
+    let decision = match req.decision {
+        Decision::Approve => "approve",
+        Decision::Reject => "reject",
+    };
+    separation::enforce(
+        &mut tx,
+        &config,
+        &report,
+        &user,
+        req.break_glass_reason.as_deref(),
+        decision,
+    )
+    .await?;
+    sqlx::query(
+        "INSERT INTO approval_votes (report_id, stage, voter, decision, notes) VALUES ($1, $2, $3, $4, $5)",
+    )
+    .bind(id)
+    .bind(&stage.name)
+    .bind(&user.email)
+    .bind(decision)
+    .bind(&req.notes)
+    .execute(&mut *tx)
+    .await
+    .map_err(AppError::db("Failed to record approval"))?;
+
+    // A single rejection ends the round; approval needs every stage.
+    let new_status = match req.decision {
+        Decision::Reject => {
+            invalidate_votes(&mut tx, id).await?;
+            Some("rejected")
+        }
+        Decision::Approve => {
+            let stages = tally(&approvals.stages, &active_votes(&mut *tx, id).await?);
+            let complete = stages.iter().all(|s| s.satisfied);
+            match (complete, &req.conditions) {
+                (true, Some(_)) => Some("approved_with_conditions"),
+                (true, None) => Some("approved"),
+                (false, Some(_)) => {
+                    return Err(AppError::Validation(format!(
+                        "conditions can only be attached by the final approval ({} still open)",
+                        progress(&stages)
+                    )))
+                }
+                (false, None) => None,
+            }
+        }
+    };
+
+    // Every vote bumps the version so ETags held by other reviewers go stale.
+    let report = sqlx::query_as::<_, Report>(
+        r#"
+        UPDATE mcp_server_reports
+        SET status = COALESCE($2, status),
+            reviewed_by = CASE WHEN $2 IS NULL THEN reviewed_by ELSE $3 END,
+            review_notes = CASE WHEN $2 IS NULL THEN review_notes ELSE $4 END,
+            reviewed_at = CASE WHEN $2 IS NULL THEN reviewed_at ELSE NOW() END,
+            claim_expires_at = CASE WHEN $2 IS NULL THEN claim_expires_at END,
+            expires_at = CASE WHEN $2 IS NULL THEN expires_at ELSE $5 END,
+            version = version + 1
+        WHERE id = $1
+        RETURNING *
+        "#,
+    )
+    .bind(id)
+    .bind(new_status)
+    .bind(&user.email)
+    .bind(&req.notes)
+    .bind(
+        new_status
+            .filter(|s| s.starts_with("approved"))
+            .map(|_| config.validity.expires_at(&report.report_json, Utc::now())),
+    )
+    .fetch_one(&mut *tx)
+    .await
+    .map_err(AppError::db("Failed to update report"))?;
+
+    if new_status.is_some() {
+        conditions::replace(
+            &mut tx,
+            &report,
+            req.conditions.as_deref().unwrap_or_default(),
+        )
+        .await?;
+        if let Some(event) = webhooks::status_event(&report.status) {
+            events::publish(&mut tx, &config, event, &report).await?;
+        }
+    }
+    let stages = tally(&approvals.stages, &active_votes(&mut *tx, id).await?);
+    tx.commit()
+        .await
+        .map_err(AppError::db("Failed to commit approval"))?;
+
+    tracing::info!(
+        "{} voted {} on report {} in stage {} ({})",
+        user.email,
+        decision,
+        id,
+        stage.name,
+        progress(&stages)
+    );
+
+    let mut response = Json(status_of(&report, stages)).into_response();
+    response
+        .headers_mut()
+        .insert(header::ETAG, concurrency::etag(report.version));
+    Ok(response)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn stage(name: &str, quorum: usize) -> ApprovalStage {
+        ApprovalStage {
+            name: name.to_string(),
+            quorum,
+            members: Vec::new(),
+        }
+    }
+
+    fn vote(stage: &str, voter: &str) -> Vote {
+        Vote {
+            stage: stage.to_string(),
+            voter: voter.to_string(),
+            created_at: Utc::now(),
+        }
+    }
+
+    #[test]
+    fn test_parse_stage_spec() {
+        assert_eq!(
+            parse_stage_spec("Security:1, platform:2").unwrap(),
+            vec![("security".to_string(), 1), ("platform".to_string(), 2)]
+        );
+        assert_eq!(
+            parse_stage_spec("security").unwrap(),
+            vec![("security".to_string(), 1)]
+        );
+        assert!(parse_stage_spec("").unwrap().is_empty());
+        assert!(parse_stage_spec("security:0").is_err());
+        assert!(parse_stage_spec("a:1,a:2").is_err());
+        assert!(parse_stage_spec("sec-ops:1").is_err());
+    }
+
+    #[test]
+    fn test_tally_is_sequential() {
+        let stages = [stage("security", 1), stage("platform", 2)];
+        let report_stages = tally(&stages, &[]);
+        assert_eq!(progress(&report_stages), "security 0/1, platform 0/2");
+        assert!(!report_stages[0].satisfied);
+
+        let votes = [
+            vote("security", "s@x"),
+            vote("platform", "p1@x"),
+            vote("platform", "p2@x"),
+        ];
+        let done = tally(&stages, &votes);
+        assert!(done.iter().all(|s| s.satisfied));
+
+        let partial = tally(&stages, &votes[..2]);
+        assert_eq!(
+            partial
+                .iter()
+                .find(|s| !s.satisfied)
+                .map(|s| s.name.as_str()),
+            Some("platform")
+        );
+    }
+
+    #[test]
+    fn test_stage_membership() {
+        let auth = AuthConfig {
+            reviewers: vec!["rev@x".to_string()],
+            ..AuthConfig::default()
+        };
+        let user = |email: &str| CurrentUser {
+            email: email.to_string(),
+        };
+        let open = stage("security", 1);
+        assert!(open.accepts(&user("rev@x"), &auth));
+        assert!(!open.accepts(&user("dev@x"), &auth));
+
+        let restricted = ApprovalStage {
+            members: vec!["sec@x".to_string()],
+            ..stage("security", 1)
+        };
+        assert!(restricted.accepts(&user("sec@x"), &auth));
+        assert!(!restricted.accepts(&user("rev@x"), &auth));
+    }
+}
@@ -0,0 +1,334 @@
+use axum::{
+    extract::{Path, State},
+    http::{HeaderMap, StatusCode},
+    response::Response,
+};
+use sqlx::{PgPool, Postgres, Transaction};
+use std::collections::HashMap;
+use std::str::FromStr;
+use std::sync::Arc;
+use uuid::Uuid;
+
+use crate::auth::CurrentUser;
+use crate::concurrency;
+use crate::config::Config;
+use crate::error::AppError;
+use crate::{fetch_report, report_response, Report};
+
+// ============================================================================
+// Review Claiming & Assignment
+// ============================================================================
+
+/// How new submissions are assigned to a reviewer (`ASSIGNMENT_STRATEGY`).
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum AssignmentStrategy {
+    /// Leave new reports unassigned; reviewers claim them.
+    #[default]
+    None,
+    /// Cycle through `REVIEWERS` in order.
+    RoundRobin,
+    /// Pick the reviewer with the fewest open (pending or in review) reports.
+    LeastLoaded,
+}
+
+impl FromStr for AssignmentStrategy {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.to_ascii_lowercase().replace('-', "_").as_str() {
+            "none" => Ok(AssignmentStrategy::None),
+            "round_robin" => Ok(AssignmentStrategy::RoundRobin),
+            "least_loaded" => Ok(AssignmentStrategy::LeastLoaded),
+            other => Err(format!("unknown assignment strategy: {}", other)),
+        }
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct AssignmentConfig {
+    pub strategy: AssignmentStrategy,
+    /// How long a claim lasts before the report returns to the queue
+    /// (`CLAIM_LEASE_MINUTES`).
+    pub lease_minutes: i32,
+}
+
+impl Default for AssignmentConfig {
+    fn default() -> Self {
+        Self {
+            strategy: AssignmentStrategy::None,
+            lease_minutes: 120,
+        }
+    }
+}
+
+/// Round robin: the reviewer after `last` in `candidates`, wrapping around.
+fn next_round_robin(
+    reviewers: &[String],
+    candidates: &[String],
+    last: Option<&str>,
+) -> Option<String> {
+    let start = last
+        .and_then(|l| reviewers.iter().position(|r| r == l))
+        .map(|i| i + 1)
+        .unwrap_or(0);
+    (0..reviewers.len())
+        .map(|offset| &reviewers[(start + offset) % reviewers.len()])
+        .find(|r| candidates.contains(r))
+        .cloned()
+}
+
+/// Least loaded: fewest open reports, ties broken by configuration order.
+fn least_loaded(candidates: &[String], load: &HashMap<String, i64>) -> Option<String> {
+    candidates
+        .iter()
+        .min_by_key(|r| load.get(*r).copied().unwrap_or(0))
+        .cloned()
+}
+
+/// Chooses an assignee for a new submission. The submitting developer is
+/// never assigned their own report.
+pub async fn pick_reviewer(
+    tx: &mut Transaction<'_, Postgres>,
+    config: &Config,
+    developer_email: &str,
+) -> Result<Option<String>, AppError> {
+    let reviewers = &config.auth.reviewers;
+    let candidates: Vec<String> = reviewers
+        .iter()
+        .filter(|r| !r.eq_ignore_ascii_case(developer_email))
+        .cloned()
+        .collect();
+    if candidates.is_empty() {
+        return Ok(None);
+    }
+
+    match config.assignment.strategy {
+        AssignmentStrategy::None => Ok(None),
+        AssignmentStrategy::RoundRobin => {
+            let last: Option<String> = sqlx::query_scalar(
+                r#"
+                SELECT assignee FROM mcp_server_reports
+                WHERE assignee = ANY($1)
+                ORDER BY submitted_at DESC
+                LIMIT 1
+                "#,
+            )
+            .bind(reviewers)
+            .fetch_optional(&mut **tx)
+            .await
+            .map_err(AppError::db("Failed to assign reviewer"))?;
+            Ok(next_round_robin(reviewers, &candidates, last.as_deref()))
+        }
+        AssignmentStrategy::LeastLoaded => {
+            let rows: Vec<(String, i64)> = sqlx::query_as(
+                r#"
+                SELECT assignee, COUNT(*) FROM mcp_server_reports
+                WHERE assignee = ANY($1) AND status IN ('pending_review', 'in_review')
+                GROUP BY assignee
+                "#,
+            )
+            .bind(&candidates)
+            .fetch_all(&mut **tx)
+            .await
+            .map_err(AppError::db("Failed to assign reviewer"))?;
+            Ok(least_loaded(&candidates, &rows.into_iter().collect()))
+        }
+    }
+}
+
+/// Fails if someone other than `actor` holds an unexpired claim on `report`.
+pub fn check_claim(report: &Report, actor: &str) -> Result<(), AppError> {
+    if report.status != "in_review" {
+        return Ok(());
+    }
+    let Some(expires) = report.claim_expires_at else {
+        return Ok(());
+    };
+    match &report.assignee {
+        Some(assignee) if expires > chrono::Utc::now() && !assignee.eq_ignore_ascii_case(actor) => {
+            Err(AppError::Conflict(format!(
+                "Report {} is claimed by {} until {}",
+                report.id,
+                assignee,
+                expires.to_rfc3339()
+            )))
+        }
+        _ => Ok(()),
+    }
+}
+
+pub async fn claim_report(
+    State(pool): State<PgPool>,
+    State(config): State<Arc<Config>>,
+    Path(id): Path<Uuid>,
+    user: CurrentUser,
+    headers: HeaderMap,
+) -> Result<Response, AppError> {
+    user.require_reviewer(&config.auth)?;
+    let expected = concurrency::if_match(&headers, config.require_if_match)?;
+
+    let report = sqlx::query_as::<_, Report>(
+        r#"
+        UPDATE mcp_server_reports
+        SET status = 'in_review', assignee = $2, claimed_at = NOW(),
+            claim_expires_at = NOW() + make_interval(mins => $3), version = version + 1
+        WHERE id = $1
+          AND ($4::int IS NULL OR version = $4)
+          AND (status = 'pending_review'
+               OR (status = 'in_review' AND (assignee = $2 OR claim_expires_at < NOW())))
+        RETURNING *
+        "#,
+    )
+    .bind(id)
+    .bind(&user.email)
+    .bind(config.assignment.lease_minutes)
+    .bind(expected.version())
+    .fetch_optional(&pool)
+    .await
+    .map_err(AppError::db("Failed to claim report"))?;
+
+    let Some(report) = report else {
+        let current = fetch_report(&pool, id).await?;
+        if let Some(v) = expected.version() {
+            if v != current.version {
+                return Err(concurrency::stale(id, current.version, expected));
+            }
+        }
+        check_claim(&current, &user.email)?;
+        return Err(AppError::Conflict(format!(
+            "Report {} is {}; only pending reports can be claimed",
+            id, current.status
+        )));
+    };
+
+    tracing::info!(
+        "Report {} claimed by {} until {:?}",
+        report.id,
+        user.email,
+        report.claim_expires_at
+    );
+    Ok(report_response(StatusCode::OK, &report))
+}
+
+pub async fn release_report(
+    State(pool): State<PgPool>,
+    State(config): State<Arc<Config>>,
+    Path(id): Path<Uuid>,
+    user: CurrentUser,
+    headers: HeaderMap,
+) -> Result<Response, AppError> {
+    user.require_reviewer(&config.auth)?;
+    let expected = concurrency::if_match(&headers, config.require_if_match)?;
+
+    // Back to the queue for anyone to pick up, not to the releasing reviewer.
+    let report = sqlx::query_as::<_, Report>(
+        r#"
+        UPDATE mcp_server_reports
+        SET status = 'pending_review', assignee = NULL, claimed_at = NULL,
+            claim_expires_at = NULL, version = version + 1
+        WHERE id = $1
+          AND ($4::int IS NULL OR version = $4)
+          AND status = 'in_review'
+          AND (assignee = $2 OR $3)
+        RETURNING *
+        "#,
+    )
+    .bind(id)
+    .bind(&user.email)
+    .bind(user.is_admin(&config.auth))
+    .bind(expected.version())
+    .fetch_optional(&pool)
+    .await
+    .map_err(AppError::db("Failed to release report"))?;
+
+    let Some(report) = report else {
+        let current = fetch_report(&pool, id).await?;
+        if let Some(v) = expected.version() {
+            if v != current.version {
+                return Err(concurrency::stale(id, current.version, expected));
+            }
+        }
+        if current.status != "in_review" {
+            return Err(AppError::Conflict(format!("Report {} is not claimed", id)));
+        }
+        return Err(AppError::Forbidden(format!(
+            "Report {} is claimed by {}",
+            id,
+            current.assignee.as_deref().unwrap_or("another reviewer")
+        )));
+    };
+
+    tracing::info!("Report {} released by {}", report.id, user.email);
+    Ok(report_response(StatusCode::OK, &report))
+}
+
+/// Returns reports whose claim lease ran out to the review queue.
+pub async fn release_expired_claims(pool: &PgPool) -> Result<u64, sqlx::Error> {
+    let result = sqlx::query(
+        r#"
+        UPDATE mcp_server_reports
+        SET status = 'pending_review', claimed_at = NULL, claim_expires_at = NULL,
+            version = version + 1
+        WHERE status = 'in_review' AND claim_expires_at < NOW()
+        "#,
+    )
+    .execute(pool)
+    .await?;
+    Ok(result.rows_affected())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn emails(list: &[&str]) -> Vec<String> {
+        list.iter().map(|s| s.to_string()).collect()
+    }
+
+    #[test]
+    fn test_round_robin_wraps_and_skips_developer() {
+        let reviewers = emails(&["a@x", "b@x", "c@x"]);
+        let all = reviewers.clone();
+        assert_eq!(
+            next_round_robin(&reviewers, &all, None).as_deref(),
+            Some("a@x")
+        );
+        assert_eq!(
+            next_round_robin(&reviewers, &all, Some("a@x")).as_deref(),
+            Some("b@x")
+        );
+        assert_eq!(
+            next_round_robin(&reviewers, &all, Some("c@x")).as_deref(),
+            Some("a@x")
+        );
+
+        // b@x submitted the report, so it goes to c@x instead.
+        let without_b = emails(&["a@x", "c@x"]);
+        assert_eq!(
+            next_round_robin(&reviewers, &without_b, Some("a@x")).as_deref(),
+            Some("c@x")
+        );
+    }
+
+    #[test]
+    fn test_least_loaded_prefers_idle_then_order() {
+        let candidates = emails(&["a@x", "b@x", "c@x"]);
+        let load: HashMap<String, i64> = [("a@x".to_string(), 3), ("b@x".to_string(), 1)].into();
+        assert_eq!(least_loaded(&candidates, &load).as_deref(), Some("c@x"));
+
+        let load: HashMap<String, i64> = [
+            ("a@x".to_string(), 1),
+            ("b@x".to_string(), 1),
+            ("c@x".to_string(), 2),
+        ]
+        .into();
+        assert_eq!(least_loaded(&candidates, &load).as_deref(), Some("a@x"));
+    }
+
+    #[test]
+    fn test_strategy_parsing() {
+        assert_eq!("round-robin".parse(), Ok(AssignmentStrategy::RoundRobin));
+        assert_eq!("least_loaded".parse(), Ok(AssignmentStrategy::LeastLoaded));
+        assert!("random".parse::<AssignmentStrategy>().is_err());
+    }
+}
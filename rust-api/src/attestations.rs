@@ -0,0 +1,269 @@
+use axum::{
+    extract::{Path, State},
+    http::HeaderValue,
+    response::{IntoResponse, Json, Response},
+};
+use base64::{engine::general_purpose::STANDARD as BASE64, Engine};
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use serde_json::{json, Value as JsonValue};
+use sha2::{Digest, Sha256};
+use sqlx::{PgPool, Postgres, Transaction};
+use uuid::Uuid;
+
+use crate::auth::CurrentUser;
+use crate::error::AppError;
+use crate::recertification::risk_level;
+use crate::signing::{self, Signer};
+use crate::Report;
+
+// ============================================================================
+// Approval Attestations (in-toto Statement in a DSSE envelope)
+// ============================================================================
+
+pub const PAYLOAD_TYPE: &str = "application/vnd.in-toto+json";
+pub const STATEMENT_TYPE: &str = "https://in-toto.io/Statement/v1";
+pub const PREDICATE_TYPE: &str = "https://golive.dev/attestations/approval/v1";
+
+/// Statuses whose latest attestation still holds.
+const APPROVED: [&str; 2] = ["approved", "approved_with_conditions"];
+
+/// A signed DSSE envelope; `payload` is the base64 statement.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Envelope {
+    payload_type: String,
+    payload: String,
+    signatures: Vec<EnvelopeSignature>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct EnvelopeSignature {
+    keyid: String,
+    sig: String,
+}
+
+/// DSSE pre-authentication encoding: what the signature actually covers.
+pub fn pae(payload_type: &str, payload: &[u8]) -> Vec<u8> {
+    let mut encoded = format!(
+        "DSSEv1 {} {} {} ",
+        payload_type.len(),
+        payload_type,
+        payload.len()
+    )
+    .into_bytes();
+    encoded.extend_from_slice(payload);
+    encoded
+}
+
+fn sha256(bytes: &[u8]) -> String {
+    hex::encode(Sha256::digest(bytes))
+}
+
+/// The in-toto statement for an approval of `report`. The subjects are the
+/// digests of the approved content, recomputable from the report itself:
+/// `report_json` as compact JSON with sorted keys, `report_data` as UTF-8.
+fn statement(report: &Report, conditions: &[(String, DateTime<Utc>)]) -> JsonValue {
+    let report_json = serde_json::to_vec(&report.report_json).expect("JSON values serialize");
+    json!({
+        "_type": STATEMENT_TYPE,
+        "subject": [
+            { "name": "report_json", "digest": { "sha256": sha256(&report_json) } },
+            { "name": "report_data", "digest": { "sha256": sha256(report.report_data.as_bytes()) } },
+        ],
+        "predicateType": PREDICATE_TYPE,
+        "predicate": {
+            "report_id": report.id,
+            "report_version": report.version,
+            "server_name": report.server_name,
+            "repository_url": report.repository_url,
+            "developer_email": report.developer_email,
+            "verified_origin": report.verified_origin,
+            "origin_key_id": report.origin_key_id,
+            "status": report.status,
+            "risk_level": risk_level(&report.report_json),
+            "reviewed_by": report.reviewed_by,
+            "reviewed_at": report.reviewed_at,
+            "expires_at": report.expires_at,
+            "conditions": conditions
+                .iter()
+                .map(|(description, due_at)| json!({ "description": description, "due_at": due_at }))
+                .collect::<Vec<_>>(),
+        },
+    })
+}
+
+fn envelope(signer: &Signer, statement: &JsonValue) -> Envelope {
+    let payload = serde_json::to_vec(statement).expect("statements serialize");
+    Envelope {
+        payload_type: PAYLOAD_TYPE.to_string(),
+        payload: BASE64.encode(&payload),
+        signatures: vec![EnvelopeSignature {
+            keyid: signer.key_id().to_string(),
+            sig: signer.sign(&pae(PAYLOAD_TYPE, &payload)),
+        }],
+    }
+}
+
+/// Signs and stores an attestation of `report`'s approval. Runs in the
+/// approving transaction, so every committed approval has one, as long as a
+/// signing key is configured: a throwaway key would leave attestations that
+/// nobody can verify after a restart, so none are issued with it.
+pub async fn issue(
+    tx: &mut Transaction<'_, Postgres>,
+    signer: &Signer,
+    report: &Report,
+) -> Result<(), AppError> {
+    if signer.is_ephemeral() {
+        tracing::warn!(
+            "Not attesting approval of report {}: SIGNING_KEY is not set",
+            report.id
+        );
+        return Ok(());
+    }
+    let failed = || AppError::db("Failed to issue approval attestation");
+    let conditions: Vec<(String, DateTime<Utc>)> = sqlx::query_as(
+        r#"
+        SELECT description, due_at FROM report_conditions
+        WHERE report_id = $1 AND fulfilled_at IS NULL AND withdrawn_at IS NULL
+        ORDER BY due_at
+        "#,
+    )
+    .bind(report.id)
+    .fetch_all(&mut **tx)
+    .await
+    .map_err(failed())?;
+
+    let envelope = envelope(signer, &statement(report, &conditions));
+    sqlx::query(
+        "INSERT INTO report_attestations (report_id, status, key_id, envelope) VALUES ($1, $2, $3, $4)",
+    )
+    .bind(report.id)
+    .bind(&report.status)
+    .bind(signer.key_id())
+    .bind(sqlx::types::Json(&envelope))
+    .execute(&mut **tx)
+    .await
+    .map_err(failed())?;
+    tracing::info!(
+        "Issued approval attestation for report {} ({})",
+        report.id,
+        report.server_name
+    );
+    Ok(())
+}
+
+/// `GET /api/v1/reports/{id}/attestation`: the DSSE envelope of the report's
+/// latest approval, verifiable with `GET /api/v1/signing-key`. Only served
+/// while the report is still approved; a demoted, rejected or expired
+/// report's attestation no longer holds.
+pub async fn get_attestation(
+    State(pool): State<PgPool>,
+    Path(id): Path<Uuid>,
+    user: Option<CurrentUser>,
+) -> Result<Response, AppError> {
+    let report = crate::fetch_visible_report(&pool, id, user.as_ref()).await?;
+    if !APPROVED.contains(&report.status.as_str()) {
+        return Err(AppError::NotFound(format!(
+            "Report {} is {}; it has no current approval attestation",
+            id, report.status
+        )));
+    }
+    let found: Option<(String, sqlx::types::Json<Envelope>)> = sqlx::query_as(
+        r#"
+        SELECT key_id, envelope FROM report_attestations
+        WHERE report_id = $1
+        ORDER BY created_at DESC
+        LIMIT 1
+        "#,
+    )
+    .bind(id)
+    .fetch_optional(&pool)
+    .await
+    .map_err(AppError::db("Failed to get attestation"))?;
+
+    let Some((key_id, envelope)) = found else {
+        return Err(AppError::NotFound(format!(
+            "Report {} has no approval attestation",
+            id
+        )));
+    };
+    let mut response = Json(envelope.0).into_response();
+    if let Ok(key_id) = HeaderValue::from_str(&key_id) {
+        response
+            .headers_mut()
+            .insert(signing::KEY_ID_HEADER, key_id);
+    }
+    Ok(response)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use ed25519_dalek::{Signature, Verifier};
+
+    fn report() -> Report {
+        let reviewed_at = "2026-01-02T00:00:00Z".parse().unwrap();
+        Report {
+            report_data: "# Weather MCP".to_string(),
+            report_json: json!({"phase1_security": {"risk_level": "LOW"}, "b": 1, "a": 2}),
+            status: "approved_with_conditions".to_string(),
+            reviewed_at: Some(reviewed_at),
+            reviewed_by: Some("rev@acme.test".to_string()),
+            version: 3,
+            expires_at: Some("2027-01-02T00:00:00Z".parse().unwrap()),
+            status_changed_at: reviewed_at,
+            ..Report::sample()
+        }
+    }
+
+    #[test]
+    fn test_pae() {
+        assert_eq!(
+            pae("http://example.com/HelloWorld", b"hello world"),
+            b"DSSEv1 29 http://example.com/HelloWorld 11 hello world".to_vec()
+        );
+    }
+
+    #[test]
+    fn test_statement_covers_content() {
+        let due = "2026-03-01T00:00:00Z".parse().unwrap();
+        let statement = statement(&report(), &[("Enable audit logging".to_string(), due)]);
+        assert_eq!(statement["_type"], STATEMENT_TYPE);
+        // Compact JSON with sorted keys, as a verifier would recompute it.
+        let canonical = br#"{"a":2,"b":1,"phase1_security":{"risk_level":"LOW"}}"#;
+        assert_eq!(
+            statement["subject"][0]["digest"]["sha256"],
+            sha256(canonical)
+        );
+        assert_eq!(
+            statement["subject"][1]["digest"]["sha256"],
+            sha256(b"# Weather MCP")
+        );
+        let predicate = &statement["predicate"];
+        assert_eq!(predicate["reviewed_by"], "rev@acme.test");
+        assert_eq!(predicate["risk_level"], "LOW");
+        assert_eq!(predicate["expires_at"], "2027-01-02T00:00:00Z");
+        assert_eq!(
+            predicate["conditions"][0]["description"],
+            "Enable audit logging"
+        );
+    }
+
+    #[test]
+    fn test_envelope_verifies() {
+        let signer = Signer::parse("nWGxne/9WmC6hEr0kuwsxERJxWl7MmkZcDusAxyuf2A=").unwrap();
+        let envelope = envelope(&signer, &statement(&report(), &[]));
+        assert_eq!(envelope.signatures[0].keyid, signer.key_id());
+
+        let public = signer.verifying_key();
+        let payload = BASE64.decode(&envelope.payload).unwrap();
+        let sig =
+            Signature::from_slice(&BASE64.decode(&envelope.signatures[0].sig).unwrap()).unwrap();
+        assert!(public.verify(&pae(PAYLOAD_TYPE, &payload), &sig).is_ok());
+        assert!(public.verify(&payload, &sig).is_err());
+
+        let decoded: JsonValue = serde_json::from_slice(&payload).unwrap();
+        assert_eq!(decoded["predicate"]["report_id"], Uuid::nil().to_string());
+    }
+}
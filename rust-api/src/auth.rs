@@ -0,0 +1,145 @@
+use axum::extract::{FromRef, FromRequestParts, OptionalFromRequestParts};
+use axum::http::request::Parts;
+use std::sync::Arc;
+
+use crate::config::Config;
+use crate::error::AppError;
+
+// ============================================================================
+// Caller Identity
+// ============================================================================
+
+/// Who may do what. Identity itself comes from a header set by the
+/// authenticating proxy in front of the API (`IDENTITY_HEADER`).
+#[derive(Debug, Clone)]
+pub struct AuthConfig {
+    pub identity_header: String,
+    /// `REVIEWERS`: emails allowed to claim and decide on reports. When empty,
+    /// every identified caller counts as a reviewer.
+    pub reviewers: Vec<String>,
+    /// `ADMINS`: emails allowed to act on other reviewers' claims.
+    pub admins: Vec<String>,
+}
+
+impl Default for AuthConfig {
+    fn default() -> Self {
+        Self {
+            identity_header: "x-user-email".to_string(),
+            reviewers: Vec::new(),
+            admins: Vec::new(),
+        }
+    }
+}
+
+/// Splits a comma-separated list of emails, normalized to lowercase.
+pub fn parse_emails(raw: &str) -> Vec<String> {
+    raw.split(',')
+        .map(|e| e.trim().to_lowercase())
+        .filter(|e| !e.is_empty())
+        .collect()
+}
+
+/// The authenticated caller, lowercased.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct CurrentUser {
+    pub email: String,
+}
+
+impl CurrentUser {
+    pub fn is_admin(&self, auth: &AuthConfig) -> bool {
+        auth.admins.contains(&self.email)
+    }
+
+    pub fn is_reviewer(&self, auth: &AuthConfig) -> bool {
+        auth.reviewers.is_empty() || auth.reviewers.contains(&self.email) || self.is_admin(auth)
+    }
+
+    pub fn require_reviewer(&self, auth: &AuthConfig) -> Result<(), AppError> {
+        if self.is_reviewer(auth) {
+            Ok(())
+        } else {
+            Err(AppError::Forbidden(format!(
+                "{} is not a configured reviewer",
+                self.email
+            )))
+        }
+    }
+}
+
+fn identity(parts: &Parts, auth: &AuthConfig) -> Option<CurrentUser> {
+    parts
+        .headers
+        .get(auth.identity_header.as_str())
+        .and_then(|v| v.to_str().ok())
+        .map(|v| v.trim().to_lowercase())
+        .filter(|v| !v.is_empty())
+        .map(|email| CurrentUser { email })
+}
+
+impl<S> FromRequestParts<S> for CurrentUser
+where
+    Arc<Config>: FromRef<S>,
+    S: Send + Sync,
+{
+    type Rejection = AppError;
+
+    async fn from_request_parts(parts: &mut Parts, state: &S) -> Result<Self, Self::Rejection> {
+        let config = Arc::<Config>::from_ref(state);
+        identity(parts, &config.auth).ok_or_else(|| {
+            AppError::Unauthorized(format!(
+                "Missing caller identity ({} header)",
+                config.auth.identity_header
+            ))
+        })
+    }
+}
+
+impl<S> OptionalFromRequestParts<S> for CurrentUser
+where
+    Arc<Config>: FromRef<S>,
+    S: Send + Sync,
+{
+    type Rejection = AppError;
+
+    async fn from_request_parts(
+        parts: &mut Parts,
+        state: &S,
+    ) -> Result<Option<Self>, Self::Rejection> {
+        let config = Arc::<Config>::from_ref(state);
+        Ok(identity(parts, &config.auth))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn user(email: &str) -> CurrentUser {
+        CurrentUser {
+            email: email.to_string(),
+        }
+    }
+
+    #[test]
+    fn test_parse_emails() {
+        assert_eq!(
+            parse_emails(" A@x.no, ,b@x.no "),
+            vec!["a@x.no".to_string(), "b@x.no".to_string()]
+        );
+    }
+
+    #[test]
+    fn test_reviewer_roles() {
+        let open = AuthConfig::default();
+        assert!(user("anyone@x.no").is_reviewer(&open));
+
+        let auth = AuthConfig {
+            reviewers: vec!["rev@x.no".to_string()],
+            admins: vec!["admin@x.no".to_string()],
+            ..AuthConfig::default()
+        };
+        assert!(user("rev@x.no").is_reviewer(&auth));
+        assert!(user("admin@x.no").is_reviewer(&auth));
+        assert!(user("dev@x.no").require_reviewer(&auth).is_err());
+    }
+}
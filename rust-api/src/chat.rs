@@ -0,0 +1,437 @@
+use serde_json::{json, Value as JsonValue};
+use sqlx::{PgPool, Postgres, Transaction};
+use std::str::FromStr;
+use std::time::Duration;
+use uuid::Uuid;
+
+use crate::config::Config;
+use crate::error::AppError;
+use crate::jobs::{self, Job, NewJob};
+use crate::{fetch_report, issues_response, recertification, webhooks, Report};
+
+// ============================================================================
+// Chat Notifications
+// ============================================================================
+
+pub const POST_JOB: &str = "chat_message";
+
+/// Events posted to a channel that does not list its own.
+pub const DEFAULT_EVENTS: [&str; 3] = ["report.created", "report.approved", "report.rejected"];
+
+/// The chat product behind a channel; each has its own [`Connector`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ConnectorKind {
+    Teams,
+    Slack,
+}
+
+impl FromStr for ConnectorKind {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.to_ascii_lowercase().as_str() {
+            "teams" => Ok(ConnectorKind::Teams),
+            "slack" => Ok(ConnectorKind::Slack),
+            other => Err(format!("unknown chat connector: {}", other)),
+        }
+    }
+}
+
+impl ConnectorKind {
+    pub fn connector(self) -> &'static dyn Connector {
+        match self {
+            ConnectorKind::Teams => &Teams,
+            ConnectorKind::Slack => &Slack,
+        }
+    }
+}
+
+/// A channel messages are routed to, with the rules that pick its messages.
+#[derive(Debug, Clone)]
+pub struct ChatChannel {
+    pub name: String,
+    pub kind: ConnectorKind,
+    /// Incoming-webhook URL of the channel (`CHAT_CHANNEL_<NAME>_URL`).
+    pub url: String,
+    /// `CHAT_CHANNEL_<NAME>_EVENTS`; defaults to [`DEFAULT_EVENTS`].
+    pub events: Vec<String>,
+    /// Only reports at these risk levels (`CHAT_CHANNEL_<NAME>_RISK_LEVELS`);
+    /// empty for all.
+    pub risk_levels: Vec<String>,
+    /// Only reports by members of these `TEAMS` (`CHAT_CHANNEL_<NAME>_TEAMS`);
+    /// empty for all.
+    pub teams: Vec<String>,
+}
+
+impl ChatChannel {
+    /// Whether `event_type` for a report at `risk_level` by a developer on
+    /// `teams` belongs in this channel.
+    pub fn wants(&self, event_type: &str, risk_level: Option<&str>, teams: &[&str]) -> bool {
+        self.events.iter().any(|e| e == event_type)
+            && (self.risk_levels.is_empty()
+                || risk_level.is_some_and(|risk| {
+                    self.risk_levels
+                        .iter()
+                        .any(|level| level.eq_ignore_ascii_case(risk.trim()))
+                }))
+            && (self.teams.is_empty() || self.teams.iter().any(|t| teams.contains(&t.as_str())))
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct ChatConfig {
+    pub channels: Vec<ChatChannel>,
+    /// Per-post request timeout (`CHAT_TIMEOUT_SECS`).
+    pub timeout_secs: u64,
+}
+
+impl Default for ChatConfig {
+    fn default() -> Self {
+        Self {
+            channels: Vec::new(),
+            timeout_secs: 10,
+        }
+    }
+}
+
+/// Parses `security:teams,payments:slack` into channel names and connectors.
+pub fn parse_channels(raw: &str) -> Result<Vec<(String, ConnectorKind)>, String> {
+    raw.split(',')
+        .map(str::trim)
+        .filter(|c| !c.is_empty())
+        .map(|channel| {
+            let (name, kind) = channel
+                .split_once(':')
+                .ok_or_else(|| format!("{:?} is missing ':teams' or ':slack'", channel))?;
+            Ok((name.trim().to_string(), kind.trim().parse()?))
+        })
+        .collect()
+}
+
+/// What a chat message says about a report.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Summary {
+    pub headline: String,
+    pub server_name: String,
+    pub status: String,
+    pub developer_email: String,
+    pub risk_level: Option<String>,
+    pub critical_issues: usize,
+    pub warnings: usize,
+    pub reviewed_by: Option<String>,
+    pub link: Option<String>,
+}
+
+impl Summary {
+    fn facts(&self) -> Vec<(&'static str, String)> {
+        let mut facts = vec![
+            ("Server", self.server_name.clone()),
+            ("Status", self.status.replace('_', " ")),
+            (
+                "Risk level",
+                self.risk_level
+                    .clone()
+                    .unwrap_or_else(|| "not stated".to_string()),
+            ),
+            ("Critical issues", self.critical_issues.to_string()),
+            ("Warnings", self.warnings.to_string()),
+            ("Developer", self.developer_email.clone()),
+        ];
+        if let Some(by) = &self.reviewed_by {
+            facts.push(("Reviewed by", by.clone()));
+        }
+        facts
+    }
+}
+
+fn headline(event_type: &str, server_name: &str, status: &str) -> String {
+    match event_type {
+        "report.created" => format!("New go-live report for review: {}", server_name),
+        "report.approved" if status == "approved_with_conditions" => {
+            format!("{} approved with conditions", server_name)
+        }
+        "report.approved" => format!("{} approved for go-live", server_name),
+        "report.rejected" => format!("{} rejected", server_name),
+        "report.withdrawn" => format!("{} withdrawn by the developer", server_name),
+        "report.expired" => format!("Approval of {} expired", server_name),
+        "report.demoted" => format!("{} returned to review: condition overdue", server_name),
+        "report.reopened" => format!("{} returned to review", server_name),
+        other => format!("{}: {}", server_name, other),
+    }
+}
+
+/// Renders a [`Summary`] as the JSON body a chat product's incoming webhook
+/// accepts. Add a connector by implementing this and naming it in
+/// [`ConnectorKind`].
+pub trait Connector: Send + Sync {
+    fn payload(&self, summary: &Summary) -> JsonValue;
+}
+
+/// Microsoft Teams: an Adaptive Card, as accepted by Workflows and
+/// incoming-webhook connectors.
+pub struct Teams;
+
+impl Connector for Teams {
+    fn payload(&self, summary: &Summary) -> JsonValue {
+        let facts: Vec<JsonValue> = summary
+            .facts()
+            .into_iter()
+            .map(|(title, value)| json!({ "title": title, "value": value }))
+            .collect();
+        let actions: Vec<JsonValue> = summary
+            .link
+            .iter()
+            .map(|url| json!({ "type": "Action.OpenUrl", "title": "Open report", "url": url }))
+            .collect();
+        json!({
+            "type": "message",
+            "attachments": [{
+                "contentType": "application/vnd.microsoft.card.adaptive",
+                "content": {
+                    "$schema": "http://adaptivecards.io/schemas/adaptive-card.json",
+                    "type": "AdaptiveCard",
+                    "version": "1.4",
+                    "body": [
+                        {
+                            "type": "TextBlock",
+                            "text": summary.headline,
+                            "size": "Medium",
+                            "weight": "Bolder",
+                            "wrap": true,
+                        },
+                        { "type": "FactSet", "facts": facts },
+                    ],
+                    "actions": actions,
+                },
+            }],
+        })
+    }
+}
+
+/// Slack: Block Kit blocks with a plain-text fallback.
+pub struct Slack;
+
+impl Connector for Slack {
+    fn payload(&self, summary: &Summary) -> JsonValue {
+        let fields: Vec<JsonValue> = summary
+            .facts()
+            .into_iter()
+            .map(|(title, value)| json!({ "type": "mrkdwn", "text": format!("*{}*\n{}", title, value) }))
+            .collect();
+        let mut blocks = vec![
+            json!({ "type": "header", "text": { "type": "plain_text", "text": summary.headline } }),
+            json!({ "type": "section", "fields": fields }),
+        ];
+        if let Some(url) = &summary.link {
+            blocks.push(json!({
+                "type": "actions",
+                "elements": [{
+                    "type": "button",
+                    "text": { "type": "plain_text", "text": "Open report" },
+                    "url": url,
+                }],
+            }));
+        }
+        json!({ "text": summary.headline, "blocks": blocks })
+    }
+}
+
+/// Queues a post to every channel whose rules match `event_type` for
+/// `report`. Runs in the caller's transaction. The status is captured now;
+/// the issue counts are read when the job runs, after the change committed.
+pub async fn route(
+    tx: &mut Transaction<'_, Postgres>,
+    config: &Config,
+    event_type: &str,
+    report: &Report,
+) -> Result<(), AppError> {
+    let risk_level = recertification::risk_level(&report.report_json);
+    let teams = config.separation.team_names(&report.developer_email);
+    for channel in &config.chat.channels {
+        if !channel.wants(event_type, risk_level, &teams) {
+            continue;
+        }
+        let payload = json!({
+            "channel": channel.name,
+            "event_type": event_type,
+            "report_id": report.id,
+            "status": report.status,
+            "reviewed_by": report.reviewed_by,
+        });
+        jobs::enqueue(&mut **tx, &config.jobs, NewJob::new(POST_JOB, payload))
+            .await
+            .map_err(AppError::db("Failed to queue chat message"))?;
+    }
+    Ok(())
+}
+
+/// Runs a `chat_message` job: renders the report summary and posts it.
+pub async fn deliver(pool: &PgPool, config: &Config, job: &Job) -> Result<(), String> {
+    let field = |name: &str| {
+        job.payload
+            .get(name)
+            .and_then(|v| v.as_str())
+            .ok_or(format!("job payload has no {}", name))
+    };
+    let channel_name = field("channel")?;
+    let event_type = field("event_type")?;
+    let status = field("status")?;
+    let report_id: Uuid = field("report_id")?
+        .parse()
+        .map_err(|_| "job payload has an invalid report_id")?;
+    let Some(channel) = config.chat.channels.iter().find(|c| c.name == channel_name) else {
+        tracing::warn!("Dropping chat message for removed channel {}", channel_name);
+        return Ok(());
+    };
+
+    let report = match fetch_report(pool, report_id).await {
+        Ok(report) => report,
+        Err(AppError::NotFound(_)) => return Ok(()),
+        Err(e) => return Err(e.message().to_string()),
+    };
+    let issues = issues_response(pool, &report)
+        .await
+        .map_err(|e| e.message().to_string())?;
+    let summary = Summary {
+        headline: headline(event_type, &report.server_name, status),
+        server_name: report.server_name.clone(),
+        status: status.to_string(),
+        developer_email: report.developer_email.clone(),
+        risk_level: recertification::risk_level(&report.report_json).map(str::to_string),
+        critical_issues: issues.critical_issues.len(),
+        warnings: issues.warnings.len(),
+        reviewed_by: job
+            .payload
+            .get("reviewed_by")
+            .and_then(|v| v.as_str())
+            .map(str::to_string),
+        link: config.report_link(report.id),
+    };
+
+    let body = channel.kind.connector().payload(&summary);
+    let response = webhooks::client()
+        .post(&channel.url)
+        .timeout(Duration::from_secs(config.chat.timeout_secs))
+        .header(reqwest::header::CONTENT_TYPE, "application/json")
+        .body(body.to_string())
+        .send()
+        .await
+        .map_err(|e| format!("posting to {} failed: {}", channel.name, e))?;
+    if !response.status().is_success() {
+        return Err(format!(
+            "posting to {} failed: channel answered {}",
+            channel.name,
+            response.status()
+        ));
+    }
+    tracing::info!(
+        "Posted {} for report {} to chat channel {}",
+        event_type,
+        report_id,
+        channel.name
+    );
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn channel(risk_levels: &[&str], teams: &[&str]) -> ChatChannel {
+        ChatChannel {
+            name: "security".to_string(),
+            kind: ConnectorKind::Teams,
+            url: "https://chat.example/hook".to_string(),
+            events: DEFAULT_EVENTS.iter().map(|e| e.to_string()).collect(),
+            risk_levels: risk_levels.iter().map(|r| r.to_string()).collect(),
+            teams: teams.iter().map(|t| t.to_string()).collect(),
+        }
+    }
+
+    fn summary(link: Option<&str>) -> Summary {
+        Summary {
+            headline: "weather-mcp rejected".to_string(),
+            server_name: "weather-mcp".to_string(),
+            status: "rejected".to_string(),
+            developer_email: "dev@acme.test".to_string(),
+            risk_level: Some("HIGH".to_string()),
+            critical_issues: 2,
+            warnings: 1,
+            reviewed_by: Some("rev@acme.test".to_string()),
+            link: link.map(str::to_string),
+        }
+    }
+
+    #[test]
+    fn test_parse_channels() {
+        assert_eq!(
+            parse_channels("security:Teams, payments:slack").unwrap(),
+            vec![
+                ("security".to_string(), ConnectorKind::Teams),
+                ("payments".to_string(), ConnectorKind::Slack),
+            ]
+        );
+        assert!(parse_channels("security").is_err());
+        assert!(parse_channels("security:irc").is_err());
+    }
+
+    #[test]
+    fn test_routing_rules() {
+        let all = channel(&[], &[]);
+        assert!(all.wants("report.created", None, &[]));
+        assert!(!all.wants("report.withdrawn", None, &[]));
+
+        let high_risk = channel(&["CRITICAL", "HIGH"], &[]);
+        assert!(high_risk.wants("report.approved", Some("high"), &[]));
+        assert!(!high_risk.wants("report.approved", Some("LOW"), &[]));
+        assert!(!high_risk.wants("report.approved", None, &[]));
+
+        let payments = channel(&[], &["payments"]);
+        assert!(payments.wants("report.rejected", Some("LOW"), &["platform", "payments"]));
+        assert!(!payments.wants("report.rejected", Some("LOW"), &["platform"]));
+    }
+
+    #[test]
+    fn test_teams_card() {
+        let card = Teams.payload(&summary(Some("https://golive.test/reports/1")));
+        let content = &card["attachments"][0]["content"];
+        assert_eq!(content["type"], "AdaptiveCard");
+        assert_eq!(content["body"][0]["text"], "weather-mcp rejected");
+        let facts = content["body"][1]["facts"].as_array().unwrap();
+        assert!(facts.contains(&json!({"title": "Risk level", "value": "HIGH"})));
+        assert!(facts.contains(&json!({"title": "Critical issues", "value": "2"})));
+        assert_eq!(
+            content["actions"][0]["url"],
+            "https://golive.test/reports/1"
+        );
+
+        let card = Teams.payload(&summary(None));
+        assert!(card["attachments"][0]["content"]["actions"]
+            .as_array()
+            .unwrap()
+            .is_empty());
+    }
+
+    #[test]
+    fn test_slack_blocks() {
+        let message = Slack.payload(&summary(Some("https://golive.test/reports/1")));
+        assert_eq!(message["text"], "weather-mcp rejected");
+        let blocks = message["blocks"].as_array().unwrap();
+        assert_eq!(blocks.len(), 3);
+        assert!(blocks[1]["fields"]
+            .as_array()
+            .unwrap()
+            .contains(&json!({"type": "mrkdwn", "text": "*Critical issues*\n2"})));
+        assert_eq!(
+            blocks[2]["elements"][0]["url"],
+            "https://golive.test/reports/1"
+        );
+        assert_eq!(
+            Slack.payload(&summary(None))["blocks"]
+                .as_array()
+                .unwrap()
+                .len(),
+            2
+        );
+    }
+}
@@ -0,0 +1,464 @@
+use axum::{
+    extract::{Path, Query, State},
+    http::StatusCode,
+    response::Json,
+};
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use serde_json::Value as JsonValue;
+use sqlx::PgPool;
+use std::sync::Arc;
+use uuid::Uuid;
+
+use crate::auth::{AuthConfig, CurrentUser};
+use crate::config::Config;
+use crate::error::AppError;
+use crate::validation;
+use crate::{fetch_visible_report, find_issue, Report};
+
+// ============================================================================
+// Review Comments
+// ============================================================================
+
+/// What a thread is about. Serialized as `{"type": "...", "ref": "..."}`.
+#[derive(Debug, Clone, PartialEq, Eq, Default, Serialize, Deserialize)]
+#[serde(tag = "type", content = "ref", rename_all = "snake_case")]
+pub enum Anchor {
+    /// The report as a whole.
+    #[default]
+    Report,
+    /// A phase id such as `P1`, as given in a `phase_id` in the report.
+    Phase(String),
+    /// Index into `security_review.items`.
+    SecurityReviewItem(usize),
+    /// An issue from the issues endpoint, as `<list>/<index>`,
+    /// e.g. `critical_issues/0`.
+    Issue(String),
+}
+
+impl Anchor {
+    fn from_columns(kind: &str, reference: Option<String>) -> Self {
+        match (kind, reference) {
+            ("phase", Some(r)) => Anchor::Phase(r),
+            ("security_review_item", Some(r)) => r
+                .parse()
+                .map(Anchor::SecurityReviewItem)
+                .unwrap_or(Anchor::Report),
+            ("issue", Some(r)) => Anchor::Issue(r),
+            _ => Anchor::Report,
+        }
+    }
+
+    fn kind(&self) -> &'static str {
+        match self {
+            Anchor::Report => "report",
+            Anchor::Phase(_) => "phase",
+            Anchor::SecurityReviewItem(_) => "security_review_item",
+            Anchor::Issue(_) => "issue",
+        }
+    }
+
+    fn reference(&self) -> Option<String> {
+        match self {
+            Anchor::Report => None,
+            Anchor::Phase(phase) => Some(phase.clone()),
+            Anchor::SecurityReviewItem(index) => Some(index.to_string()),
+            Anchor::Issue(issue) => Some(issue.clone()),
+        }
+    }
+
+    /// Checks that the anchored section exists in the report, so threads do
+    /// not point at nothing.
+    fn validate(&self, report_json: &JsonValue) -> Result<(), AppError> {
+        let missing = |what: String| {
+            Err(AppError::Validation(format!(
+                "{} not found in report",
+                what
+            )))
+        };
+        match self {
+            Anchor::Report => Ok(()),
+            Anchor::Phase(phase) => {
+                if phase.trim().is_empty() || phase.chars().count() > 255 {
+                    return Err(AppError::Validation(
+                        "anchor.ref must be a phase id of 1-255 characters".to_string(),
+                    ));
+                }
+                if !mentions_phase(report_json, phase) {
+                    return missing(format!("phase {}", phase));
+                }
+                Ok(())
+            }
+            Anchor::SecurityReviewItem(index) => {
+                let items = report_json
+                    .pointer("/security_review/items")
+                    .and_then(|v| v.as_array())
+                    .map_or(0, |items| items.len());
+                if *index >= items {
+                    return missing(format!("security_review item {}", index));
+                }
+                Ok(())
+            }
+            Anchor::Issue(issue) => {
+                if find_issue(report_json, issue).is_none() {
+                    return missing(format!("issue {}", issue));
+                }
+                Ok(())
+            }
+        }
+    }
+}
+
+/// Whether any `phase_id` in `json` names `phase`.
+fn mentions_phase(json: &JsonValue, phase: &str) -> bool {
+    match json {
+        JsonValue::Object(map) => map.iter().any(|(key, value)| {
+            (key == "phase_id" && value.as_str() == Some(phase)) || mentions_phase(value, phase)
+        }),
+        JsonValue::Array(items) => items.iter().any(|item| mentions_phase(item, phase)),
+        _ => false,
+    }
+}
+
+#[derive(Debug, sqlx::FromRow)]
+struct CommentRow {
+    id: Uuid,
+    report_id: Uuid,
+    thread_id: Uuid,
+    anchor_type: String,
+    anchor_ref: Option<String>,
+    author: String,
+    author_role: String,
+    body: String,
+    resolved_at: Option<DateTime<Utc>>,
+    resolved_by: Option<String>,
+    created_at: DateTime<Utc>,
+}
+
+#[derive(Debug, Serialize)]
+pub struct Comment {
+    id: Uuid,
+    author: String,
+    /// `developer` or `reviewer`, as of when the comment was written.
+    author_role: String,
+    body: String,
+    created_at: DateTime<Utc>,
+}
+
+#[derive(Debug, Serialize)]
+pub struct Thread {
+    id: Uuid,
+    report_id: Uuid,
+    anchor: Anchor,
+    resolved: bool,
+    resolved_at: Option<DateTime<Utc>>,
+    resolved_by: Option<String>,
+    /// The opening comment followed by its replies, oldest first.
+    comments: Vec<Comment>,
+}
+
+impl From<&CommentRow> for Comment {
+    fn from(row: &CommentRow) -> Self {
+        Comment {
+            id: row.id,
+            author: row.author.clone(),
+            author_role: row.author_role.clone(),
+            body: row.body.clone(),
+            created_at: row.created_at,
+        }
+    }
+}
+
+/// Groups rows ordered by `created_at` into threads, keyed by their opening comment.
+fn into_threads(rows: Vec<CommentRow>) -> Vec<Thread> {
+    let mut threads: Vec<Thread> = Vec::new();
+    for row in &rows {
+        if row.id == row.thread_id {
+            threads.push(Thread {
+                id: row.id,
+                report_id: row.report_id,
+                anchor: Anchor::from_columns(&row.anchor_type, row.anchor_ref.clone()),
+                resolved: row.resolved_at.is_some(),
+                resolved_at: row.resolved_at,
+                resolved_by: row.resolved_by.clone(),
+                comments: vec![row.into()],
+            });
+        } else if let Some(thread) = threads.iter_mut().find(|t| t.id == row.thread_id) {
+            thread.comments.push(row.into());
+        }
+    }
+    threads
+}
+
+#[derive(Debug, Deserialize)]
+pub struct CreateThreadRequest {
+    body: String,
+    #[serde(default)]
+    anchor: Anchor,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct ReplyRequest {
+    body: String,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct ThreadQuery {
+    /// Only threads with this anchor type.
+    anchor_type: Option<String>,
+    resolved: Option<bool>,
+}
+
+/// The submitting developer and reviewers may take part in a report's
+/// threads; anyone else is turned away.
+fn participant_role(
+    report: &Report,
+    user: &CurrentUser,
+    auth: &AuthConfig,
+) -> Result<&'static str, AppError> {
+    if user.email.eq_ignore_ascii_case(&report.developer_email) {
+        return Ok("developer");
+    }
+    user.require_reviewer(auth)?;
+    Ok("reviewer")
+}
+
+async fn load_thread(pool: &PgPool, report_id: Uuid, thread_id: Uuid) -> Result<Thread, AppError> {
+    let rows = sqlx::query_as::<_, CommentRow>(
+        "SELECT * FROM review_comments WHERE report_id = $1 AND thread_id = $2 ORDER BY created_at, id",
+    )
+    .bind(report_id)
+    .bind(thread_id)
+    .fetch_all(pool)
+    .await
+    .map_err(AppError::db("Failed to load comment thread"))?;
+
+    into_threads(rows)
+        .pop()
+        .ok_or_else(|| AppError::NotFound(format!("Comment thread {} not found", thread_id)))
+}
+
+/// Resolves a comment id (opening comment or reply) to its thread.
+async fn thread_of(pool: &PgPool, report_id: Uuid, comment_id: Uuid) -> Result<Uuid, AppError> {
+    sqlx::query_scalar("SELECT thread_id FROM review_comments WHERE id = $1 AND report_id = $2")
+        .bind(comment_id)
+        .bind(report_id)
+        .fetch_optional(pool)
+        .await
+        .map_err(AppError::db("Failed to load comment"))?
+        .ok_or_else(|| AppError::NotFound(format!("Comment {} not found", comment_id)))
+}
+
+pub async fn list_threads(
+    State(pool): State<PgPool>,
+    Path(id): Path<Uuid>,
+    Query(query): Query<ThreadQuery>,
+    user: Option<CurrentUser>,
+) -> Result<Json<Vec<Thread>>, AppError> {
+    fetch_visible_report(&pool, id, user.as_ref()).await?;
+
+    let rows = sqlx::query_as::<_, CommentRow>(
+        r#"
+        SELECT c.* FROM review_comments c
+        JOIN review_comments t ON t.id = c.thread_id
+        WHERE c.report_id = $1
+          AND ($2::text IS NULL OR t.anchor_type = $2)
+          AND ($3::bool IS NULL OR (t.resolved_at IS NOT NULL) = $3)
+        ORDER BY c.created_at, c.id
+        "#,
+    )
+    .bind(id)
+    .bind(&query.anchor_type)
+    .bind(query.resolved)
+    .fetch_all(&pool)
+    .await
+    .map_err(AppError::db("Failed to list comments"))?;
+
+    let threads = into_threads(rows);
+    tracing::info!("Listed {} comment threads for report {}", threads.len(), id);
+    Ok(Json(threads))
+}
+
+pub async fn create_thread(
+    State(pool): State<PgPool>,
+    State(config): State<Arc<Config>>,
+    Path(id): Path<Uuid>,
+    user: CurrentUser,
+    Json(req): Json<CreateThreadRequest>,
+) -> Result<(StatusCode, Json<Thread>), AppError> {
+    validation::validate_comment(&req.body)?;
+    let report = fetch_visible_report(&pool, id, Some(&user)).await?;
+    let role = participant_role(&report, &user, &config.auth)?;
+    req.anchor.validate(&report.report_json)?;
+
+    let thread_id = Uuid::new_v4();
+    sqlx::query(
+        r#"
+        INSERT INTO review_comments (id, report_id, thread_id, anchor_type, anchor_ref, author, author_role, body)
+        VALUES ($1, $2, $1, $3, $4, $5, $6, $7)
+        "#,
+    )
+    .bind(thread_id)
+    .bind(id)
+    .bind(req.anchor.kind())
+    .bind(req.anchor.reference())
+    .bind(&user.email)
+    .bind(role)
+    .bind(&req.body)
+    .execute(&pool)
+    .await
+    .map_err(AppError::db("Failed to create comment"))?;
+
+    tracing::info!(
+        "{} {} opened comment thread {} on report {}",
+        role,
+        user.email,
+        thread_id,
+        id
+    );
+    Ok((
+        StatusCode::CREATED,
+        Json(load_thread(&pool, id, thread_id).await?),
+    ))
+}
+
+pub async fn reply(
+    State(pool): State<PgPool>,
+    State(config): State<Arc<Config>>,
+    Path((id, comment_id)): Path<(Uuid, Uuid)>,
+    user: CurrentUser,
+    Json(req): Json<ReplyRequest>,
+) -> Result<(StatusCode, Json<Thread>), AppError> {
+    validation::validate_comment(&req.body)?;
+    let report = fetch_visible_report(&pool, id, Some(&user)).await?;
+    let role = participant_role(&report, &user, &config.auth)?;
+    let thread_id = thread_of(&pool, id, comment_id).await?;
+
+    sqlx::query(
+        r#"
+        INSERT INTO review_comments (report_id, thread_id, anchor_type, anchor_ref, author, author_role, body)
+        SELECT report_id, id, anchor_type, anchor_ref, $2, $3, $4
+        FROM review_comments WHERE id = $1
+        "#,
+    )
+    .bind(thread_id)
+    .bind(&user.email)
+    .bind(role)
+    .bind(&req.body)
+    .execute(&pool)
+    .await
+    .map_err(AppError::db("Failed to add reply"))?;
+
+    tracing::info!(
+        "{} {} replied to thread {} on report {}",
+        role,
+        user.email,
+        thread_id,
+        id
+    );
+    Ok((
+        StatusCode::CREATED,
+        Json(load_thread(&pool, id, thread_id).await?),
+    ))
+}
+
+async fn set_resolved(
+    pool: PgPool,
+    config: Arc<Config>,
+    id: Uuid,
+    comment_id: Uuid,
+    user: CurrentUser,
+    resolved: bool,
+) -> Result<Json<Thread>, AppError> {
+    let report = fetch_visible_report(&pool, id, Some(&user)).await?;
+    participant_role(&report, &user, &config.auth)?;
+    let thread_id = thread_of(&pool, id, comment_id).await?;
+
+    sqlx::query(
+        r#"
+        UPDATE review_comments
+        SET resolved_at = CASE WHEN $2 THEN COALESCE(resolved_at, NOW()) END,
+            resolved_by = CASE WHEN $2 THEN COALESCE(resolved_by, $3) END
+        WHERE id = $1
+        "#,
+    )
+    .bind(thread_id)
+    .bind(resolved)
+    .bind(&user.email)
+    .execute(&pool)
+    .await
+    .map_err(AppError::db("Failed to update comment thread"))?;
+
+    tracing::info!(
+        "Thread {} on report {} {} by {}",
+        thread_id,
+        id,
+        if resolved { "resolved" } else { "reopened" },
+        user.email
+    );
+    Ok(Json(load_thread(&pool, id, thread_id).await?))
+}
+
+pub async fn resolve(
+    State(pool): State<PgPool>,
+    State(config): State<Arc<Config>>,
+    Path((id, comment_id)): Path<(Uuid, Uuid)>,
+    user: CurrentUser,
+) -> Result<Json<Thread>, AppError> {
+    set_resolved(pool, config, id, comment_id, user, true).await
+}
+
+pub async fn unresolve(
+    State(pool): State<PgPool>,
+    State(config): State<Arc<Config>>,
+    Path((id, comment_id)): Path<(Uuid, Uuid)>,
+    user: CurrentUser,
+) -> Result<Json<Thread>, AppError> {
+    set_resolved(pool, config, id, comment_id, user, false).await
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn test_anchor_wire_format() {
+        let anchor: Anchor =
+            serde_json::from_value(json!({"type": "issue", "ref": "warnings/1"})).unwrap();
+        assert_eq!(anchor, Anchor::Issue("warnings/1".to_string()));
+        assert_eq!(
+            serde_json::from_value::<Anchor>(json!({"type": "report"})).unwrap(),
+            Anchor::Report
+        );
+        assert_eq!(
+            serde_json::to_value(Anchor::SecurityReviewItem(2)).unwrap(),
+            json!({"type": "security_review_item", "ref": 2})
+        );
+
+        let stored = Anchor::from_columns(anchor.kind(), anchor.reference());
+        assert_eq!(stored, anchor);
+    }
+
+    #[test]
+    fn test_anchor_must_exist_in_report() {
+        let report = json!({
+            "security_review": {"items": [{"type": "auth", "status": "Pass", "description": "ok"}]},
+            "critical_issues": [{"severity": "CRITICAL", "description": "Leaky", "phase_id": "P1"}]
+        });
+        assert!(Anchor::SecurityReviewItem(0).validate(&report).is_ok());
+        assert!(Anchor::SecurityReviewItem(1).validate(&report).is_err());
+        assert!(Anchor::Issue("critical_issues/0".into())
+            .validate(&report)
+            .is_ok());
+        assert!(Anchor::Issue("warnings/0".into())
+            .validate(&report)
+            .is_err());
+        assert!(Anchor::Issue("report_data/0".into())
+            .validate(&report)
+            .is_err());
+        assert!(Anchor::Phase("P1".into()).validate(&report).is_ok());
+        assert!(Anchor::Phase("P2".into()).validate(&report).is_err());
+        assert!(Anchor::Phase(" ".into()).validate(&report).is_err());
+    }
+}
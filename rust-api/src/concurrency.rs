@@ -0,0 +1,131 @@
+use axum::http::{header, HeaderMap, HeaderValue};
+
+use crate::error::AppError;
+
+// ============================================================================
+// Optimistic Concurrency
+// ============================================================================
+
+/// Strong ETag for a report row version.
+pub fn etag(version: i32) -> HeaderValue {
+    HeaderValue::from_str(&format!("\"{}\"", version)).expect("digits are a valid header value")
+}
+
+/// Whether `If-None-Match` already names `etag`, so `304 Not Modified` can be
+/// sent. Weak comparison, as for conditional GETs.
+pub fn not_modified(headers: &HeaderMap, etag: &HeaderValue) -> bool {
+    let Some(value) = headers.get(header::IF_NONE_MATCH) else {
+        return false;
+    };
+    let current = etag.to_str().unwrap_or_default();
+    value.to_str().unwrap_or_default().split(',').any(|tag| {
+        let tag = tag.trim();
+        tag == "*" || tag.strip_prefix("W/").unwrap_or(tag) == current
+    })
+}
+
+/// Version the client expects to modify, taken from `If-Match`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Precondition {
+    /// `If-Match: *`, or no header while `REQUIRE_IF_MATCH=false`.
+    Any,
+    Version(i32),
+}
+
+impl Precondition {
+    /// Bind value for `($n::int IS NULL OR version = $n)` guards.
+    pub fn version(self) -> Option<i32> {
+        match self {
+            Precondition::Any => None,
+            Precondition::Version(v) => Some(v),
+        }
+    }
+}
+
+/// Parses `If-Match`. Only strong, single-version tags are meaningful for a
+/// report, so weak tags and lists are rejected rather than guessed at.
+pub fn if_match(headers: &HeaderMap, required: bool) -> Result<Precondition, AppError> {
+    let Some(value) = headers.get(header::IF_MATCH) else {
+        if required {
+            return Err(AppError::PreconditionRequired(
+                "If-Match header with the report's ETag is required".to_string(),
+            ));
+        }
+        return Ok(Precondition::Any);
+    };
+
+    let raw = value.to_str().unwrap_or_default().trim();
+    if raw == "*" {
+        return Ok(Precondition::Any);
+    }
+    raw.strip_prefix('"')
+        .and_then(|s| s.strip_suffix('"'))
+        .and_then(|s| s.parse::<i32>().ok())
+        .map(Precondition::Version)
+        .ok_or_else(|| {
+            AppError::BadRequest(format!(
+                "If-Match must be a single strong ETag such as \"3\", got {}",
+                raw
+            ))
+        })
+}
+
+/// Error for a guarded write that matched no row because the version moved.
+pub fn stale(id: impl std::fmt::Display, current: i32, expected: Precondition) -> AppError {
+    AppError::PreconditionFailed(format!(
+        "Report {} was modified by someone else (current version {}, If-Match {}); reload and try again",
+        id,
+        current,
+        expected.version().map(|v| v.to_string()).unwrap_or_else(|| "*".to_string())
+    ))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn headers(if_match: &'static str) -> HeaderMap {
+        let mut headers = HeaderMap::new();
+        headers.insert(header::IF_MATCH, HeaderValue::from_static(if_match));
+        headers
+    }
+
+    #[test]
+    fn test_etag_round_trip() {
+        let mut h = HeaderMap::new();
+        h.insert(header::IF_MATCH, etag(7));
+        assert_eq!(if_match(&h, true).unwrap(), Precondition::Version(7));
+    }
+
+    #[test]
+    fn test_missing_if_match() {
+        let err = if_match(&HeaderMap::new(), true).unwrap_err();
+        assert_eq!(err.code(), "precondition_required");
+        assert_eq!(
+            if_match(&HeaderMap::new(), false).unwrap(),
+            Precondition::Any
+        );
+    }
+
+    #[test]
+    fn test_wildcard_and_invalid_tags() {
+        assert_eq!(if_match(&headers("*"), true).unwrap(), Precondition::Any);
+        assert!(if_match(&headers("W/\"3\""), true).is_err());
+        assert!(if_match(&headers("\"3\", \"4\""), true).is_err());
+    }
+
+    #[test]
+    fn test_not_modified() {
+        let tag = HeaderValue::from_static("\"abc\"");
+        let since = |value: &'static str| {
+            let mut h = HeaderMap::new();
+            h.insert(header::IF_NONE_MATCH, HeaderValue::from_static(value));
+            not_modified(&h, &tag)
+        };
+        assert!(!not_modified(&HeaderMap::new(), &tag));
+        assert!(since("\"abc\""));
+        assert!(since("\"old\", W/\"abc\""));
+        assert!(since("*"));
+        assert!(!since("\"old\""));
+    }
+}
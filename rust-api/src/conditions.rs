@@ -0,0 +1,436 @@
+use axum::{
+    extract::{Path, State},
+    response::Json,
+};
+use chrono::{DateTime, Duration, Utc};
+use serde::{Deserialize, Serialize};
+use sqlx::{PgPool, Postgres, Transaction};
+use std::str::FromStr;
+use std::sync::Arc;
+use uuid::Uuid;
+
+use crate::auth::CurrentUser;
+use crate::config::Config;
+use crate::error::AppError;
+use crate::{approvals, events, separation};
+use crate::{fetch_visible_report, Report};
+
+// ============================================================================
+// Conditional Approvals
+// ============================================================================
+
+const MAX_CONDITIONS: usize = 50;
+const MAX_DESCRIPTION_CHARS: usize = 2000;
+const MAX_EVIDENCE_CHARS: usize = 10_000;
+
+/// What happens when a condition's deadline passes unfulfilled
+/// (`CONDITION_OVERDUE_ACTION`).
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum OverdueAction {
+    /// Log a warning and flag the condition as overdue.
+    #[default]
+    Alert,
+    /// Also send the report back to `pending_review`.
+    Demote,
+}
+
+impl FromStr for OverdueAction {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.to_ascii_lowercase().as_str() {
+            "alert" => Ok(OverdueAction::Alert),
+            "demote" => Ok(OverdueAction::Demote),
+            other => Err(format!("unknown overdue action: {}", other)),
+        }
+    }
+}
+
+/// A condition attached to an `approved_with_conditions` decision.
+#[derive(Debug, Deserialize)]
+pub struct NewCondition {
+    description: String,
+    /// Defaults to the submitting developer.
+    owner: Option<String>,
+    /// Either an absolute deadline...
+    due_at: Option<DateTime<Utc>>,
+    /// ...or a number of days from now.
+    due_in_days: Option<i64>,
+}
+
+impl NewCondition {
+    fn due(&self, now: DateTime<Utc>) -> Result<DateTime<Utc>, AppError> {
+        match (self.due_at, self.due_in_days) {
+            (Some(at), None) if at > now => Ok(at),
+            (None, Some(days)) if (1..=3650).contains(&days) => Ok(now + Duration::days(days)),
+            (Some(_), None) => Err(AppError::Validation(
+                "conditions[].due_at must be in the future".to_string(),
+            )),
+            (None, Some(_)) => Err(AppError::Validation(
+                "conditions[].due_in_days must be between 1 and 3650".to_string(),
+            )),
+            _ => Err(AppError::Validation(
+                "each condition needs exactly one of due_at or due_in_days".to_string(),
+            )),
+        }
+    }
+}
+
+/// Checks the conditions that must accompany `approved_with_conditions`
+/// and must not accompany any other outcome.
+pub fn validate(status: &str, conditions: Option<&[NewCondition]>) -> Result<(), AppError> {
+    let conditions = conditions.unwrap_or_default();
+    if status != "approved_with_conditions" {
+        if !conditions.is_empty() {
+            return Err(AppError::Validation(
+                "conditions are only allowed with status approved_with_conditions".to_string(),
+            ));
+        }
+        return Ok(());
+    }
+    if conditions.is_empty() || conditions.len() > MAX_CONDITIONS {
+        return Err(AppError::Validation(format!(
+            "approved_with_conditions needs 1-{} conditions",
+            MAX_CONDITIONS
+        )));
+    }
+    let now = Utc::now();
+    for condition in conditions {
+        let chars = condition.description.trim().chars().count();
+        if chars == 0 || chars > MAX_DESCRIPTION_CHARS || condition.description.contains('\0') {
+            return Err(AppError::Validation(format!(
+                "conditions[].description must be 1-{} characters",
+                MAX_DESCRIPTION_CHARS
+            )));
+        }
+        if let Some(owner) = &condition.owner {
+            if !owner.contains('@') || owner.chars().count() > 255 {
+                return Err(AppError::Validation(
+                    "conditions[].owner must be an email address".to_string(),
+                ));
+            }
+        }
+        condition.due(now)?;
+    }
+    Ok(())
+}
+
+/// Records a new decision's conditions, inside the decision's transaction.
+/// Conditions still open from an earlier decision are withdrawn.
+pub async fn replace(
+    tx: &mut Transaction<'_, Postgres>,
+    report: &Report,
+    conditions: &[NewCondition],
+) -> Result<(), AppError> {
+    sqlx::query(
+        r#"
+        UPDATE report_conditions SET withdrawn_at = NOW()
+        WHERE report_id = $1 AND fulfilled_at IS NULL AND withdrawn_at IS NULL
+        "#,
+    )
+    .bind(report.id)
+    .execute(&mut **tx)
+    .await
+    .map_err(AppError::db("Failed to withdraw previous conditions"))?;
+
+    let now = Utc::now();
+    for condition in conditions {
+        let owner = condition
+            .owner
+            .as_deref()
+            .unwrap_or(&report.developer_email)
+            .trim()
+            .to_lowercase();
+        sqlx::query(
+            "INSERT INTO report_conditions (report_id, description, owner, due_at) VALUES ($1, $2, $3, $4)",
+        )
+        .bind(report.id)
+        .bind(condition.description.trim())
+        .bind(owner)
+        .bind(condition.due(now)?)
+        .execute(&mut **tx)
+        .await
+        .map_err(AppError::db("Failed to store approval condition"))?;
+    }
+    Ok(())
+}
+
+#[derive(Debug, Serialize, sqlx::FromRow)]
+pub struct Condition {
+    id: Uuid,
+    report_id: Uuid,
+    description: String,
+    owner: String,
+    due_at: DateTime<Utc>,
+    fulfilled_at: Option<DateTime<Utc>>,
+    fulfilled_by: Option<String>,
+    evidence: Option<String>,
+    /// When the deadline passed unfulfilled and the overdue action ran.
+    overdue_at: Option<DateTime<Utc>>,
+    /// When a later decision on the report superseded this condition.
+    withdrawn_at: Option<DateTime<Utc>>,
+    created_at: DateTime<Utc>,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct FulfilRequest {
+    /// A link or description showing the condition is met.
+    evidence: String,
+}
+
+pub async fn list_conditions(
+    State(pool): State<PgPool>,
+    Path(id): Path<Uuid>,
+    user: Option<CurrentUser>,
+) -> Result<Json<Vec<Condition>>, AppError> {
+    fetch_visible_report(&pool, id, user.as_ref()).await?;
+    let conditions = sqlx::query_as::<_, Condition>(
+        "SELECT * FROM report_conditions WHERE report_id = $1 ORDER BY due_at, created_at",
+    )
+    .bind(id)
+    .fetch_all(&pool)
+    .await
+    .map_err(AppError::db("Failed to list conditions"))?;
+    Ok(Json(conditions))
+}
+
+/// Marks a condition fulfilled. When a reviewer independent of the submitter
+/// fulfils the last open condition, an `approved_with_conditions` report
+/// becomes `approved`. Evidence for the last condition from anyone else is
+/// recorded, and the report waits for a reviewer to approve it.
+pub async fn fulfil(
+    State(pool): State<PgPool>,
+    State(config): State<Arc<Config>>,
+    Path((id, condition_id)): Path<(Uuid, Uuid)>,
+    user: CurrentUser,
+    Json(req): Json<FulfilRequest>,
+) -> Result<Json<Condition>, AppError> {
+    let chars = req.evidence.trim().chars().count();
+    if chars == 0 || chars > MAX_EVIDENCE_CHARS || req.evidence.contains('\0') {
+        return Err(AppError::Validation(format!(
+            "evidence must be 1-{} characters",
+            MAX_EVIDENCE_CHARS
+        )));
+    }
+    let mut tx = pool
+        .begin()
+        .await
+        .map_err(AppError::db("Failed to start transaction"))?;
+    // Serializes fulfilments on the report, so the last one sees all others.
+    let report =
+        sqlx::query_as::<_, Report>("SELECT * FROM mcp_server_reports WHERE id = $1 FOR UPDATE")
+            .bind(id)
+            .fetch_optional(&mut *tx)
+            .await
+            .map_err(AppError::db("Failed to get report"))?
+            .ok_or_else(|| AppError::report_not_found(id))?;
+
+    let condition = sqlx::query_as::<_, Condition>(
+        "SELECT * FROM report_conditions WHERE id = $1 AND report_id = $2 FOR UPDATE",
+    )
+    .bind(condition_id)
+    .bind(id)
+    .fetch_optional(&mut *tx)
+    .await
+    .map_err(AppError::db("Failed to load condition"))?
+    .ok_or_else(|| AppError::NotFound(format!("Condition {} not found", condition_id)))?;
+
+    let may_fulfil = user.email == condition.owner
+        || user.email.eq_ignore_ascii_case(&report.developer_email)
+        || user.is_reviewer(&config.auth);
+    if !may_fulfil {
+        return Err(AppError::Forbidden(format!(
+            "Only {} or a reviewer may fulfil this condition",
+            condition.owner
+        )));
+    }
+    if condition.fulfilled_at.is_some() || condition.withdrawn_at.is_some() {
+        return Err(AppError::Conflict(format!(
+            "Condition {} is no longer open",
+            condition_id
+        )));
+    }
+
+    let condition = sqlx::query_as::<_, Condition>(
+        r#"
+        UPDATE report_conditions
+        SET fulfilled_at = NOW(), fulfilled_by = $2, evidence = $3
+        WHERE id = $1
+        RETURNING *
+        "#,
+    )
+    .bind(condition_id)
+    .bind(&user.email)
+    .bind(req.evidence.trim())
+    .fetch_one(&mut *tx)
+    .await
+    .map_err(AppError::db("Failed to fulfil condition"))?;
+
+    // Accepting the evidence is a decision on the report, so the four-eyes
+    // rule applies to whoever promotes it.
+    let accepting = user.is_reviewer(&config.auth);
+    let open: i64 = sqlx::query_scalar(
+        r#"
+        SELECT COUNT(*) FROM report_conditions
+        WHERE report_id = $1 AND fulfilled_at IS NULL AND withdrawn_at IS NULL
+        "#,
+    )
+    .bind(id)
+    .fetch_one(&mut *tx)
+    .await
+    .map_err(AppError::db("Failed to count open conditions"))?;
+    if open > 0 || !accepting {
+        tx.commit()
+            .await
+            .map_err(AppError::db("Failed to commit condition"))?;
+        tracing::info!(
+            "Condition {} on report {} fulfilled by {}",
+            condition_id,
+            id,
+            user.email
+        );
+        if open == 0 {
+            tracing::info!(
+                "All conditions on report {} met; awaiting a reviewer's approval",
+                id
+            );
+        }
+        return Ok(Json(condition));
+    }
+    separation::enforce(
+        &mut tx,
+        &config,
+        &report,
+        &user,
+        None,
+        "accept the evidence for",
+    )
+    .await?;
+
+    let promoted = sqlx::query_as::<_, Report>(
+        r#"
+        UPDATE mcp_server_reports
+        SET status = 'approved', version = version + 1
+        WHERE id = $1 AND status = 'approved_with_conditions'
+        RETURNING *
+        "#,
+    )
+    .bind(id)
+    .fetch_optional(&mut *tx)
+    .await
+    .map_err(AppError::db("Failed to update report"))?;
+    if let Some(report) = &promoted {
+        events::publish(&mut tx, &config, "report.approved", report).await?;
+    }
+
+    tx.commit()
+        .await
+        .map_err(AppError::db("Failed to commit condition"))?;
+
+    tracing::info!(
+        "Condition {} on report {} fulfilled by {}",
+        condition_id,
+        id,
+        user.email
+    );
+    if promoted.is_some() {
+        tracing::info!("All conditions met; report {} is now approved", id);
+    }
+    Ok(Json(condition))
+}
+
+/// Applies the overdue action to conditions whose deadline has passed.
+/// Each condition is handled once.
+pub async fn sweep_overdue(pool: &PgPool, config: &Config) -> Result<u64, AppError> {
+    let failed = || AppError::db("Failed to process overdue conditions");
+    let mut tx = pool.begin().await.map_err(failed())?;
+    let overdue: Vec<(Uuid, Uuid, String, String)> = sqlx::query_as(
+        r#"
+        UPDATE report_conditions
+        SET overdue_at = NOW()
+        WHERE fulfilled_at IS NULL AND withdrawn_at IS NULL AND overdue_at IS NULL
+          AND due_at < NOW()
+        RETURNING id, report_id, owner, description
+        "#,
+    )
+    .fetch_all(&mut *tx)
+    .await
+    .map_err(failed())?;
+
+    for (condition_id, report_id, owner, description) in &overdue {
+        tracing::warn!(
+            "Condition {} on report {} is overdue (owner {}): {}",
+            condition_id,
+            report_id,
+            owner,
+            description
+        );
+        if config.condition_overdue_action == OverdueAction::Demote {
+            let demoted = sqlx::query_as::<_, Report>(
+                r#"
+                UPDATE mcp_server_reports
+                SET status = 'pending_review', expires_at = NULL, version = version + 1,
+                    review_notes = COALESCE(review_notes || E'\n\n', '')
+                        || 'Returned to review: condition overdue: ' || $2
+                WHERE id = $1 AND status = 'approved_with_conditions'
+                RETURNING *
+                "#,
+            )
+            .bind(report_id)
+            .bind(description)
+            .fetch_optional(&mut *tx)
+            .await
+            .map_err(failed())?;
+            if let Some(report) = demoted {
+                // Like a manual reset, this starts a new approval round.
+                approvals::invalidate_votes(&mut tx, report.id).await?;
+                events::publish(&mut tx, config, "report.demoted", &report).await?;
+                tracing::warn!("Report {} demoted to pending_review", report_id);
+            }
+        }
+    }
+    tx.commit().await.map_err(failed())?;
+    Ok(overdue.len() as u64)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    fn conditions(value: serde_json::Value) -> Vec<NewCondition> {
+        serde_json::from_value(value).unwrap()
+    }
+
+    #[test]
+    fn test_conditions_only_with_conditional_status() {
+        let one = conditions(json!([{"description": "Add rate limiting", "due_in_days": 30}]));
+        assert!(validate("approved_with_conditions", Some(&one)).is_ok());
+        assert!(validate("approved", Some(&one)).is_err());
+        assert!(validate("approved", None).is_ok());
+        assert!(validate("approved_with_conditions", None).is_err());
+        assert!(validate("approved_with_conditions", Some(&[])).is_err());
+    }
+
+    #[test]
+    fn test_condition_deadlines() {
+        let now = Utc::now();
+        let past = conditions(json!([{"description": "x", "due_at": "2020-01-01T00:00:00Z"}]));
+        assert!(validate("approved_with_conditions", Some(&past)).is_err());
+
+        let both = conditions(json!([
+            {"description": "x", "due_at": "2999-01-01T00:00:00Z", "due_in_days": 3}
+        ]));
+        assert!(validate("approved_with_conditions", Some(&both)).is_err());
+
+        let days = conditions(json!([{"description": "x", "due_in_days": 30}]));
+        let due = days[0].due(now).unwrap();
+        assert_eq!((due - now).num_days(), 30);
+    }
+
+    #[test]
+    fn test_overdue_action_parsing() {
+        assert_eq!("Demote".parse(), Ok(OverdueAction::Demote));
+        assert!("ignore".parse::<OverdueAction>().is_err());
+    }
+}
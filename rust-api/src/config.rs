@@ -0,0 +1,397 @@
+use std::str::FromStr;
+use uuid::Uuid;
+
+use crate::admission::AdmissionConfig;
+use crate::allowlist::CONTENT_TYPES;
+use crate::approvals::{parse_stage_spec, ApprovalConfig, ApprovalStage};
+use crate::assignment::{AssignmentConfig, AssignmentStrategy};
+use crate::auth::{parse_emails, AuthConfig};
+use crate::chat::{parse_channels, ChatChannel, ChatConfig, DEFAULT_EVENTS};
+use crate::conditions::OverdueAction;
+use crate::error::ErrorFormat;
+use crate::jobs::{next_run, JobsConfig};
+use crate::notifications::EmailConfig;
+use crate::origin::SignatureMode;
+use crate::recertification::{parse_validity, ValidityConfig};
+use crate::scanning::ScanMode;
+use crate::separation::{parse_teams, SeparationConfig};
+use crate::signing::Signer;
+use crate::sla::{parse_hours, SlaConfig};
+use crate::webhooks::{WebhookConfig, EVENT_TYPES};
+
+// ============================================================================
+// Configuration
+// ============================================================================
+
+/// Runtime configuration, read once from the environment at startup.
+#[derive(Debug, Clone)]
+pub struct Config {
+    pub limits: IngestLimits,
+    /// Secret/PII scanning of submissions (`SECRET_SCAN_MODE`: off, redact, block).
+    pub scan_mode: ScanMode,
+    /// Default error body format (`ERROR_FORMAT`: json, problem).
+    pub error_format: ErrorFormat,
+    /// How long a stored `Idempotency-Key` response is replayed (`IDEMPOTENCY_TTL_HOURS`).
+    pub idempotency_ttl_hours: i32,
+    /// Reject report mutations without `If-Match` (`REQUIRE_IF_MATCH`).
+    pub require_if_match: bool,
+    pub auth: AuthConfig,
+    pub assignment: AssignmentConfig,
+    pub approvals: ApprovalConfig,
+    pub separation: SeparationConfig,
+    /// What to do when an approval condition passes its deadline
+    /// (`CONDITION_OVERDUE_ACTION`: alert, demote).
+    pub condition_overdue_action: OverdueAction,
+    /// Longest validity a risk-acceptance waiver may be granted (`WAIVER_MAX_DAYS`).
+    pub waiver_max_days: i64,
+    pub validity: ValidityConfig,
+    pub sla: SlaConfig,
+    pub jobs: JobsConfig,
+    pub webhooks: WebhookConfig,
+    pub email: EmailConfig,
+    pub chat: ChatConfig,
+    /// Base URL of the review UI, for links in notifications (`PUBLIC_URL`).
+    pub public_url: Option<String>,
+    /// How long the report event stream can be resumed from (`EVENT_RETENTION_DAYS`).
+    pub event_retention_days: i64,
+    /// Key signed responses are made with (`SIGNING_KEY`, `SIGNING_KEY_FILE`).
+    pub signing: Signer,
+    /// Content types allowed for servers whose report lists none
+    /// (`ALLOWLIST_CONTENT_TYPES`).
+    pub allowlist_content_types: Vec<String>,
+    pub admission: AdmissionConfig,
+    /// Whether submissions must be signed by a registered skill key
+    /// (`SKILL_SIGNATURE_MODE`: off, mark, require).
+    pub signature_mode: SignatureMode,
+    /// Browser origins allowed to call `POST /mcp` (`MCP_ALLOWED_ORIGINS`).
+    pub mcp_allowed_origins: Vec<String>,
+}
+
+impl Config {
+    pub fn from_env() -> Self {
+        Self {
+            limits: IngestLimits::from_env(),
+            scan_mode: env_or("SECRET_SCAN_MODE", ScanMode::default()),
+            error_format: env_or("ERROR_FORMAT", ErrorFormat::default()),
+            idempotency_ttl_hours: env_or("IDEMPOTENCY_TTL_HOURS", 24),
+            require_if_match: env_or("REQUIRE_IF_MATCH", true),
+            auth: AuthConfig {
+                identity_header: env_or("IDENTITY_HEADER", AuthConfig::default().identity_header)
+                    .to_lowercase(),
+                reviewers: parse_emails(&env_or("REVIEWERS", String::new())),
+                admins: parse_emails(&env_or("ADMINS", String::new())),
+            },
+            assignment: AssignmentConfig {
+                strategy: env_or("ASSIGNMENT_STRATEGY", AssignmentStrategy::default()),
+                lease_minutes: env_or(
+                    "CLAIM_LEASE_MINUTES",
+                    AssignmentConfig::default().lease_minutes,
+                ),
+            },
+            approvals: ApprovalConfig {
+                stages: approval_stages_from_env(),
+            },
+            separation: SeparationConfig {
+                teams: parse_teams(&env_or("TEAMS", String::new()))
+                    .unwrap_or_else(|e| panic!("TEAMS has an invalid value: {}", e)),
+                same_team: env_or("FOUR_EYES_SAME_TEAM", false),
+            },
+            condition_overdue_action: env_or("CONDITION_OVERDUE_ACTION", OverdueAction::default()),
+            waiver_max_days: env_or("WAIVER_MAX_DAYS", 365),
+            validity: validity_from_env(),
+            sla: sla_from_env(),
+            jobs: jobs_from_env(),
+            webhooks: WebhookConfig {
+                timeout_secs: env_or(
+                    "WEBHOOK_TIMEOUT_SECS",
+                    WebhookConfig::default().timeout_secs,
+                ),
+                max_attempts: env_or(
+                    "WEBHOOK_MAX_ATTEMPTS",
+                    WebhookConfig::default().max_attempts,
+                ),
+            },
+            email: email_from_env(),
+            chat: chat_from_env(),
+            public_url: env_opt("PUBLIC_URL"),
+            event_retention_days: env_or("EVENT_RETENTION_DAYS", 7),
+            signing: signer_from_env(),
+            allowlist_content_types: allowlist_content_types_from_env(),
+            admission: AdmissionConfig {
+                mode: env_or("ADMISSION_MODE", AdmissionConfig::default().mode),
+                server_key: env_or(
+                    "ADMISSION_SERVER_KEY",
+                    AdmissionConfig::default().server_key,
+                ),
+                log_retention_days: env_or(
+                    "ADMISSION_LOG_RETENTION_DAYS",
+                    AdmissionConfig::default().log_retention_days,
+                ),
+            },
+            signature_mode: env_or("SKILL_SIGNATURE_MODE", SignatureMode::default()),
+            mcp_allowed_origins: env_or("MCP_ALLOWED_ORIGINS", String::new())
+                .split(',')
+                .map(|o| o.trim().trim_end_matches('/').to_string())
+                .filter(|o| !o.is_empty())
+                .collect(),
+        }
+    }
+
+    /// Link to a report's page in the review UI, when `PUBLIC_URL` is set.
+    pub fn report_link(&self, id: Uuid) -> Option<String> {
+        self.public_url
+            .as_ref()
+            .map(|base| format!("{}/reports/{}", base.trim_end_matches('/'), id))
+    }
+}
+
+/// `APPROVAL_STAGES=security:1,platform:2`, with each stage's members in
+/// `APPROVAL_STAGE_<NAME>_MEMBERS`.
+fn approval_stages_from_env() -> Vec<ApprovalStage> {
+    let spec: String = env_or("APPROVAL_STAGES", String::new());
+    parse_stage_spec(&spec)
+        .unwrap_or_else(|e| panic!("APPROVAL_STAGES has an invalid value: {}", e))
+        .into_iter()
+        .map(|(name, quorum)| {
+            let members_var = format!("APPROVAL_STAGE_{}_MEMBERS", name.to_ascii_uppercase());
+            ApprovalStage {
+                members: parse_emails(&env_or(&members_var, String::new())),
+                name,
+                quorum,
+            }
+        })
+        .collect()
+}
+
+/// `APPROVAL_VALIDITY_DAYS=HIGH:90,LOW:365` overrides the per-risk defaults
+/// it names; other risk levels keep theirs.
+fn validity_from_env() -> ValidityConfig {
+    let mut validity = ValidityConfig::default();
+    let overrides = parse_validity(&env_or("APPROVAL_VALIDITY_DAYS", String::new()))
+        .unwrap_or_else(|e| panic!("APPROVAL_VALIDITY_DAYS has an invalid value: {}", e));
+    for (level, days) in overrides {
+        match validity.days_by_risk.iter_mut().find(|(l, _)| *l == level) {
+            Some(entry) => entry.1 = days,
+            None => validity.days_by_risk.push((level, days)),
+        }
+    }
+    validity.default_days = env_or("APPROVAL_VALIDITY_DEFAULT_DAYS", validity.default_days);
+    validity.notice_days = env_or("RECERTIFICATION_NOTICE_DAYS", validity.notice_days);
+    validity
+}
+
+/// Job runner settings. Each built-in schedule can be changed with
+/// `JOB_SCHEDULE_<NAME>` (a six-field cron expression) or disabled with `off`.
+fn jobs_from_env() -> JobsConfig {
+    let defaults = JobsConfig::default();
+    let schedules = defaults
+        .schedules
+        .into_iter()
+        .map(|mut spec| {
+            let var = format!("JOB_SCHEDULE_{}", spec.name.to_ascii_uppercase());
+            if let Ok(raw) = std::env::var(&var) {
+                let raw = raw.trim();
+                spec.cron = (!raw.eq_ignore_ascii_case("off")).then(|| raw.to_string());
+            }
+            if let Some(cron) = &spec.cron {
+                next_run(cron, chrono::Utc::now())
+                    .unwrap_or_else(|e| panic!("{} has an invalid value: {}", var, e));
+            }
+            spec
+        })
+        .collect();
+    JobsConfig {
+        worker: env_or("JOB_WORKER", defaults.worker),
+        concurrency: env_or("JOB_CONCURRENCY", defaults.concurrency),
+        max_attempts: env_or("JOB_MAX_ATTEMPTS", defaults.max_attempts),
+        backoff_secs: env_or("JOB_BACKOFF_SECS", defaults.backoff_secs),
+        timeout_secs: env_or("JOB_TIMEOUT_SECS", defaults.timeout_secs),
+        retention_days: env_or("JOB_RETENTION_DAYS", defaults.retention_days),
+        schedules,
+    }
+}
+
+/// SMTP delivery of notifications; email is off unless `SMTP_URL` is set.
+fn email_from_env() -> EmailConfig {
+    let defaults = EmailConfig::default();
+    let email = EmailConfig {
+        smtp_url: env_opt("SMTP_URL"),
+        from: env_or("EMAIL_FROM", defaults.from),
+        timeout_secs: env_or("SMTP_TIMEOUT_SECS", defaults.timeout_secs),
+        condition_reminder_days: env_or(
+            "CONDITION_REMINDER_DAYS",
+            defaults.condition_reminder_days,
+        ),
+    };
+    email
+        .validate()
+        .unwrap_or_else(|e| panic!("Invalid email configuration: {}", e));
+    email
+}
+
+/// `REVIEW_SLA_HOURS=HIGH:24` overrides the per-risk defaults it names,
+/// like `APPROVAL_VALIDITY_DAYS`.
+fn sla_from_env() -> SlaConfig {
+    let mut sla = SlaConfig::default();
+    let overrides = parse_hours(&env_or("REVIEW_SLA_HOURS", String::new()))
+        .unwrap_or_else(|e| panic!("REVIEW_SLA_HOURS has an invalid value: {}", e));
+    for (level, hours) in overrides {
+        match sla.hours_by_risk.iter_mut().find(|(l, _)| *l == level) {
+            Some(entry) => entry.1 = hours,
+            None => sla.hours_by_risk.push((level, hours)),
+        }
+    }
+    sla.default_hours = env_or("REVIEW_SLA_DEFAULT_HOURS", sla.default_hours);
+    sla.remind_percent = env_or("REVIEW_SLA_REMIND_PERCENT", sla.remind_percent);
+    if !(1..=100).contains(&sla.remind_percent) {
+        panic!("REVIEW_SLA_REMIND_PERCENT must be between 1 and 100");
+    }
+    sla.escalate_after_hours = env_or("REVIEW_SLA_ESCALATE_AFTER_HOURS", sla.escalate_after_hours);
+    sla.leads = parse_teams(&env_or("TEAM_LEADS", String::new()))
+        .unwrap_or_else(|e| panic!("TEAM_LEADS has an invalid value: {}", e));
+    sla
+}
+
+/// The signing key from `SIGNING_KEY` (PEM or base64 seed) or the PEM file at
+/// `SIGNING_KEY_FILE`; a throwaway key when neither is set.
+fn signer_from_env() -> Signer {
+    let raw = match (env_opt("SIGNING_KEY"), env_opt("SIGNING_KEY_FILE")) {
+        (Some(key), _) => key,
+        (None, Some(path)) => std::fs::read_to_string(&path)
+            .unwrap_or_else(|e| panic!("SIGNING_KEY_FILE {} cannot be read: {}", path, e)),
+        (None, None) => {
+            let signer = Signer::generate();
+            tracing::warn!(
+                "SIGNING_KEY is not set; signing with throwaway key {} that changes on restart",
+                signer.key_id()
+            );
+            return signer;
+        }
+    };
+    Signer::parse(&raw).unwrap_or_else(|e| panic!("SIGNING_KEY has an invalid value: {}", e))
+}
+
+/// `ALLOWLIST_CONTENT_TYPES=text,resource`; the PRD prohibits images, audio
+/// and resource links unless a report permits them.
+fn allowlist_content_types_from_env() -> Vec<String> {
+    let types: Vec<String> = env_or("ALLOWLIST_CONTENT_TYPES", "text,resource".to_string())
+        .split(',')
+        .map(|t| t.trim().to_ascii_lowercase())
+        .filter(|t| !t.is_empty())
+        .collect();
+    if let Some(unknown) = types.iter().find(|t| !CONTENT_TYPES.contains(&t.as_str())) {
+        panic!(
+            "ALLOWLIST_CONTENT_TYPES names unknown content type {}; expected {}",
+            unknown,
+            CONTENT_TYPES.join(", ")
+        );
+    }
+    types
+}
+
+/// `CHAT_CHANNELS=security:teams,payments:slack`, with each channel's
+/// webhook URL and routing rules in `CHAT_CHANNEL_<NAME>_*`.
+fn chat_from_env() -> ChatConfig {
+    let list = |name: &str| -> Vec<String> {
+        env_or(name, String::new())
+            .split(',')
+            .map(str::trim)
+            .filter(|v| !v.is_empty())
+            .map(String::from)
+            .collect()
+    };
+    let spec: String = env_or("CHAT_CHANNELS", String::new());
+    let channels = parse_channels(&spec)
+        .unwrap_or_else(|e| panic!("CHAT_CHANNELS has an invalid value: {}", e))
+        .into_iter()
+        .map(|(name, kind)| {
+            let prefix = format!("CHAT_CHANNEL_{}", name.to_ascii_uppercase());
+            let url = env_opt(&format!("{}_URL", prefix))
+                .unwrap_or_else(|| panic!("{}_URL must be set", prefix));
+            let mut events = list(&format!("{}_EVENTS", prefix));
+            if events.is_empty() {
+                events = DEFAULT_EVENTS.iter().map(|e| e.to_string()).collect();
+            }
+            if let Some(unknown) = events.iter().find(|e| !EVENT_TYPES.contains(&e.as_str())) {
+                panic!("{}_EVENTS names unknown event {}", prefix, unknown);
+            }
+            ChatChannel {
+                url,
+                events,
+                risk_levels: list(&format!("{}_RISK_LEVELS", prefix)),
+                teams: list(&format!("{}_TEAMS", prefix)),
+                name,
+                kind,
+            }
+        })
+        .collect();
+    ChatConfig {
+        channels,
+        timeout_secs: env_or("CHAT_TIMEOUT_SECS", ChatConfig::default().timeout_secs),
+    }
+}
+
+/// Bounds applied to incoming report submissions.
+///
+/// The text columns from migration 001 (`VARCHAR(255)` / `VARCHAR(500)`) are
+/// fixed by the schema and therefore not configurable here.
+#[derive(Debug, Clone)]
+pub struct IngestLimits {
+    /// Maximum size of any request body, enforced before deserialization.
+    pub max_body_bytes: usize,
+    /// Maximum size of the markdown `report_data` field.
+    pub max_report_data_bytes: usize,
+    /// Maximum serialized size of `report_json` and of `raw_json` (each).
+    pub max_json_bytes: usize,
+    /// Maximum nesting depth of objects/arrays in `report_json` and `raw_json`.
+    pub max_json_depth: usize,
+    /// Maximum number of elements in any single JSON array.
+    pub max_json_array_len: usize,
+}
+
+impl Default for IngestLimits {
+    fn default() -> Self {
+        Self {
+            max_body_bytes: 8 * 1024 * 1024,
+            max_report_data_bytes: 1024 * 1024,
+            max_json_bytes: 2 * 1024 * 1024,
+            max_json_depth: 32,
+            max_json_array_len: 1000,
+        }
+    }
+}
+
+impl IngestLimits {
+    fn from_env() -> Self {
+        let defaults = Self::default();
+        Self {
+            max_body_bytes: env_or("MAX_BODY_BYTES", defaults.max_body_bytes),
+            max_report_data_bytes: env_or("MAX_REPORT_DATA_BYTES", defaults.max_report_data_bytes),
+            max_json_bytes: env_or("MAX_REPORT_JSON_BYTES", defaults.max_json_bytes),
+            max_json_depth: env_or("MAX_JSON_DEPTH", defaults.max_json_depth),
+            max_json_array_len: env_or("MAX_JSON_ARRAY_LEN", defaults.max_json_array_len),
+        }
+    }
+}
+
+/// Reads an optional setting; unset and blank are the same.
+fn env_opt(name: &str) -> Option<String> {
+    std::env::var(name)
+        .ok()
+        .map(|v| v.trim().to_string())
+        .filter(|v| !v.is_empty())
+}
+
+/// Reads `name` from the environment, falling back to `default` when unset.
+///
+/// Panics on a value that does not parse, so misconfiguration fails at startup
+/// rather than on the first request.
+fn env_or<T: FromStr>(name: &str, default: T) -> T {
+    match std::env::var(name) {
+        Ok(raw) => raw
+            .trim()
+            .parse()
+            .unwrap_or_else(|_| panic!("{} has an invalid value: {:?}", name, raw)),
+        Err(_) => default,
+    }
+}
@@ -0,0 +1,243 @@
+use axum::{
+    extract::{Path, State},
+    http::{HeaderMap, StatusCode},
+    response::Response,
+};
+use sqlx::{PgPool, Postgres, Transaction};
+use std::sync::Arc;
+use uuid::Uuid;
+
+use crate::auth::CurrentUser;
+use crate::config::Config;
+use crate::error::AppError;
+use crate::origin::{self, SignatureMode, SignedJson};
+use crate::scanning::{self, ScanMode};
+use crate::{approvals, assignment, concurrency, events, idempotency, issues, validation};
+use crate::{report_response, CreateReportRequest, Report};
+
+// ============================================================================
+// Drafts and Withdrawal
+// ============================================================================
+
+/// Fails unless `user` is the developer who submitted `report`.
+pub fn require_developer(
+    user: &CurrentUser,
+    report: &Report,
+    action: &str,
+) -> Result<(), AppError> {
+    if user.email.eq_ignore_ascii_case(&report.developer_email) {
+        Ok(())
+    } else {
+        Err(AppError::Forbidden(format!(
+            "Only the submitting developer ({}) may {} report {}",
+            report.developer_email, action, report.id
+        )))
+    }
+}
+
+/// Locks the caller's own report for a developer action, checking its
+/// version and that it is in one of `statuses`.
+async fn lock_own(
+    tx: &mut Transaction<'_, Postgres>,
+    id: Uuid,
+    user: &CurrentUser,
+    headers: &HeaderMap,
+    required: bool,
+    statuses: &[&str],
+    action: &str,
+) -> Result<Report, AppError> {
+    let expected = concurrency::if_match(headers, required)?;
+    let report =
+        sqlx::query_as::<_, Report>("SELECT * FROM mcp_server_reports WHERE id = $1 FOR UPDATE")
+            .bind(id)
+            .fetch_optional(&mut **tx)
+            .await
+            .map_err(AppError::db("Failed to fetch report"))?
+            .ok_or_else(|| AppError::report_not_found(id))?;
+    require_developer(user, &report, action)?;
+    if let Some(v) = expected.version() {
+        if v != report.version {
+            return Err(concurrency::stale(id, report.version, expected));
+        }
+    }
+    if !statuses.contains(&report.status.as_str()) {
+        return Err(AppError::Conflict(format!(
+            "Report {} is {}; cannot {} it unless it is {}",
+            id,
+            report.status,
+            action,
+            statuses.join(" or ")
+        )));
+    }
+    Ok(report)
+}
+
+/// Replaces the content of a draft. Drafts are validated and scanned like
+/// submissions, but are not reviewed, assigned or tracked until submitted.
+pub async fn update_draft(
+    State(pool): State<PgPool>,
+    State(config): State<Arc<Config>>,
+    Path(id): Path<Uuid>,
+    user: CurrentUser,
+    headers: HeaderMap,
+    SignedJson(mut req, body): SignedJson<CreateReportRequest>,
+) -> Result<Response, AppError> {
+    validation::validate_create_report(&req, &config.limits)?;
+    // The origin follows the content: an unsigned edit clears it.
+    let origin = origin::verify(&pool, &config, &headers, &body, &req.server_name).await?;
+
+    let mut tx = pool
+        .begin()
+        .await
+        .map_err(AppError::db("Failed to start transaction"))?;
+    let draft = lock_own(
+        &mut tx,
+        id,
+        &user,
+        &headers,
+        config.require_if_match,
+        &["draft"],
+        "update",
+    )
+    .await?;
+    if !req
+        .developer_email
+        .eq_ignore_ascii_case(&draft.developer_email)
+    {
+        return Err(AppError::Validation(
+            "developer_email of a draft cannot be changed".to_string(),
+        ));
+    }
+
+    let content_hash = idempotency::content_hash(&req);
+    let findings = scanning::scan_report(&mut req, config.scan_mode);
+    if !findings.is_empty() && config.scan_mode == ScanMode::Block {
+        return Err(AppError::Validation(scanning::describe(&findings)));
+    }
+
+    let report = sqlx::query_as::<_, Report>(
+        r#"
+        UPDATE mcp_server_reports
+        SET server_name = $2, repository_url = $3, report_data = $4, report_json = $5,
+            raw_json = $6, scan_findings = $7, content_hash = $8, verified_origin = $9,
+            origin_key_id = $10, version = version + 1
+        WHERE id = $1
+        RETURNING *
+        "#,
+    )
+    .bind(id)
+    .bind(&req.server_name)
+    .bind(&req.repository_url)
+    .bind(&req.report_data)
+    .bind(&req.report_json)
+    .bind(&req.raw_json)
+    .bind(sqlx::types::Json(&findings))
+    .bind(&content_hash)
+    .bind(origin.verified)
+    .bind(&origin.key_id)
+    .fetch_one(&mut *tx)
+    .await
+    .map_err(AppError::db("Failed to update draft"))?;
+    tx.commit()
+        .await
+        .map_err(AppError::db("Failed to commit draft"))?;
+
+    tracing::info!("Draft {} updated by {}", report.id, user.email);
+    Ok(report_response(StatusCode::OK, &report))
+}
+
+/// Sends a draft to review, as if it had just been submitted.
+pub async fn submit_draft(
+    State(pool): State<PgPool>,
+    State(config): State<Arc<Config>>,
+    Path(id): Path<Uuid>,
+    user: CurrentUser,
+    headers: HeaderMap,
+) -> Result<Response, AppError> {
+    let mut tx = pool
+        .begin()
+        .await
+        .map_err(AppError::db("Failed to start transaction"))?;
+    let draft = lock_own(&mut tx, id, &user, &headers, false, &["draft"], "submit").await?;
+    if config.signature_mode == SignatureMode::Require && !draft.verified_origin {
+        return Err(AppError::InvalidSignature(format!(
+            "Draft {} was last saved without a skill signature; save it signed before submitting",
+            id
+        )));
+    }
+    let assignee = assignment::pick_reviewer(&mut tx, &config, &draft.developer_email).await?;
+
+    let report = sqlx::query_as::<_, Report>(
+        r#"
+        UPDATE mcp_server_reports
+        SET status = 'pending_review', submitted_at = NOW(), assignee = $2, version = version + 1
+        WHERE id = $1
+        RETURNING *
+        "#,
+    )
+    .bind(id)
+    .bind(&assignee)
+    .fetch_one(&mut *tx)
+    .await
+    .map_err(AppError::db("Failed to submit draft"))?;
+    issues::sync_report(&mut tx, &report).await?;
+    events::publish(&mut tx, &config, "report.created", &report).await?;
+    tx.commit()
+        .await
+        .map_err(AppError::db("Failed to commit submission"))?;
+
+    tracing::info!(
+        "Draft {} for {} submitted for review by {}",
+        report.id,
+        report.server_name,
+        user.email
+    );
+    Ok(report_response(StatusCode::OK, &report))
+}
+
+/// Takes a submission back out of the review queue. Votes cast on it are
+/// invalidated; the report itself is kept for the record.
+pub async fn withdraw_report(
+    State(pool): State<PgPool>,
+    State(config): State<Arc<Config>>,
+    Path(id): Path<Uuid>,
+    user: CurrentUser,
+    headers: HeaderMap,
+) -> Result<Response, AppError> {
+    let mut tx = pool
+        .begin()
+        .await
+        .map_err(AppError::db("Failed to start transaction"))?;
+    lock_own(
+        &mut tx,
+        id,
+        &user,
+        &headers,
+        false,
+        &["pending_review", "in_review"],
+        "withdraw",
+    )
+    .await?;
+
+    let report = sqlx::query_as::<_, Report>(
+        r#"
+        UPDATE mcp_server_reports
+        SET status = 'withdrawn', claimed_at = NULL, claim_expires_at = NULL,
+            version = version + 1
+        WHERE id = $1
+        RETURNING *
+        "#,
+    )
+    .bind(id)
+    .fetch_one(&mut *tx)
+    .await
+    .map_err(AppError::db("Failed to withdraw report"))?;
+    approvals::invalidate_votes(&mut tx, id).await?;
+    events::publish(&mut tx, &config, "report.withdrawn", &report).await?;
+    tx.commit()
+        .await
+        .map_err(AppError::db("Failed to commit withdrawal"))?;
+
+    tracing::info!("Report {} withdrawn by {}", report.id, user.email);
+    Ok(report_response(StatusCode::OK, &report))
+}
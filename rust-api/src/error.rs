@@ -0,0 +1,390 @@
+use axum::{
+    body::{to_bytes, Body},
+    extract::{Request, State},
+    http::{header, HeaderValue, StatusCode},
+    middleware::Next,
+    response::{IntoResponse, Response},
+};
+use serde::Serialize;
+use std::str::FromStr;
+use std::sync::Arc;
+
+use crate::config::Config;
+use crate::validation::ValidationError;
+
+// ============================================================================
+// Application Errors
+// ============================================================================
+
+/// Error type returned by every handler.
+///
+/// Each variant maps to one HTTP status and one stable, machine-readable
+/// `code`. Clients should branch on `code`, never on the message text.
+#[derive(Debug)]
+pub enum AppError {
+    BadRequest(String),
+    Unauthorized(String),
+    Forbidden(String),
+    NotFound(String),
+    Conflict(String),
+    /// `If-Match` did not match the current version.
+    PreconditionFailed(String),
+    /// A mutation was sent without `If-Match`.
+    PreconditionRequired(String),
+    PayloadTooLarge(String),
+    Validation(String),
+    /// An `Idempotency-Key` was reused with a different request body.
+    IdempotencyKeyReused(String),
+    /// A submission signature is missing where required, malformed, made
+    /// with an unknown key, or does not match the body.
+    InvalidSignature(String),
+    /// The message is shown to the client; the underlying cause is logged
+    /// where the error is created and never leaves the service.
+    Internal(String),
+}
+
+impl AppError {
+    pub fn status(&self) -> StatusCode {
+        match self {
+            AppError::BadRequest(_) => StatusCode::BAD_REQUEST,
+            AppError::Unauthorized(_) | AppError::InvalidSignature(_) => StatusCode::UNAUTHORIZED,
+            AppError::Forbidden(_) => StatusCode::FORBIDDEN,
+            AppError::NotFound(_) => StatusCode::NOT_FOUND,
+            AppError::Conflict(_) => StatusCode::CONFLICT,
+            AppError::PreconditionFailed(_) => StatusCode::PRECONDITION_FAILED,
+            AppError::PreconditionRequired(_) => StatusCode::PRECONDITION_REQUIRED,
+            AppError::PayloadTooLarge(_) => StatusCode::PAYLOAD_TOO_LARGE,
+            AppError::Validation(_) | AppError::IdempotencyKeyReused(_) => {
+                StatusCode::UNPROCESSABLE_ENTITY
+            }
+            AppError::Internal(_) => StatusCode::INTERNAL_SERVER_ERROR,
+        }
+    }
+
+    pub fn code(&self) -> &'static str {
+        match self {
+            AppError::BadRequest(_) => "bad_request",
+            AppError::Unauthorized(_) => "unauthorized",
+            AppError::Forbidden(_) => "forbidden",
+            AppError::NotFound(_) => "not_found",
+            AppError::Conflict(_) => "conflict",
+            AppError::PreconditionFailed(_) => "precondition_failed",
+            AppError::PreconditionRequired(_) => "precondition_required",
+            AppError::PayloadTooLarge(_) => "payload_too_large",
+            AppError::Validation(_) => "validation_failed",
+            AppError::IdempotencyKeyReused(_) => "idempotency_key_reused",
+            AppError::InvalidSignature(_) => "invalid_signature",
+            AppError::Internal(_) => "internal_error",
+        }
+    }
+
+    pub fn message(&self) -> &str {
+        match self {
+            AppError::BadRequest(msg)
+            | AppError::Unauthorized(msg)
+            | AppError::Forbidden(msg)
+            | AppError::NotFound(msg)
+            | AppError::Conflict(msg)
+            | AppError::PreconditionFailed(msg)
+            | AppError::PreconditionRequired(msg)
+            | AppError::PayloadTooLarge(msg)
+            | AppError::Validation(msg)
+            | AppError::IdempotencyKeyReused(msg)
+            | AppError::InvalidSignature(msg)
+            | AppError::Internal(msg) => msg,
+        }
+    }
+
+    pub fn report_not_found(id: impl std::fmt::Display) -> Self {
+        AppError::NotFound(format!("Report not found: {}", id))
+    }
+
+    /// Maps a database error for use with `map_err`, logging the cause.
+    ///
+    /// Constraint violations are the client's fault and keep their own codes;
+    /// everything else becomes an `Internal` error carrying `context`.
+    pub fn db(context: &'static str) -> impl FnOnce(sqlx::Error) -> AppError {
+        move |e| {
+            if let sqlx::Error::Database(db) = &e {
+                match db.code().as_deref() {
+                    // unique_violation
+                    Some("23505") => {
+                        tracing::warn!("{}: {}", context, db);
+                        return AppError::Conflict(format!("{}: duplicate entry", context));
+                    }
+                    // string_data_right_truncation, check_violation
+                    Some("22001") | Some("23514") => {
+                        tracing::warn!("{}: {}", context, db);
+                        return AppError::Validation(format!("{}: {}", context, db.message()));
+                    }
+                    _ => {}
+                }
+            }
+            tracing::error!("{}: {}", context, e);
+            AppError::Internal(context.to_string())
+        }
+    }
+}
+
+impl std::fmt::Display for AppError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{} ({})", self.message(), self.code())
+    }
+}
+
+impl From<ValidationError> for AppError {
+    fn from(e: ValidationError) -> Self {
+        match e {
+            ValidationError::TooLarge(msg) => AppError::PayloadTooLarge(msg),
+            ValidationError::Invalid(msg) => AppError::Validation(msg),
+        }
+    }
+}
+
+/// Carried in response extensions so [`render_errors`] can re-render the body
+/// once the request id and the client's preferred format are known.
+#[derive(Debug, Clone)]
+struct ErrorDetails {
+    code: &'static str,
+    message: String,
+}
+
+impl IntoResponse for AppError {
+    fn into_response(self) -> Response {
+        let details = ErrorDetails {
+            code: self.code(),
+            message: self.message().to_string(),
+        };
+        let mut response = render(self.status(), &details, None, ErrorFormat::Json, "");
+        response.extensions_mut().insert(details);
+        response
+    }
+}
+
+// ============================================================================
+// Rendering
+// ============================================================================
+
+/// Error body format (`ERROR_FORMAT`). Clients can always ask for RFC 7807
+/// explicitly with `Accept: application/problem+json`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum ErrorFormat {
+    /// `{"error": ..., "code": ..., "request_id": ...}`
+    #[default]
+    Json,
+    /// RFC 7807 `application/problem+json`.
+    Problem,
+}
+
+impl FromStr for ErrorFormat {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.to_ascii_lowercase().as_str() {
+            "json" => Ok(ErrorFormat::Json),
+            "problem" | "problem+json" => Ok(ErrorFormat::Problem),
+            other => Err(format!("unknown error format: {}", other)),
+        }
+    }
+}
+
+#[derive(Debug, Serialize)]
+struct ErrorResponse<'a> {
+    error: &'a str,
+    code: &'a str,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    request_id: Option<&'a str>,
+}
+
+#[derive(Debug, Serialize)]
+struct ProblemDetails<'a> {
+    #[serde(rename = "type")]
+    problem_type: String,
+    title: &'a str,
+    status: u16,
+    detail: &'a str,
+    instance: &'a str,
+    code: &'a str,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    request_id: Option<&'a str>,
+}
+
+fn render(
+    status: StatusCode,
+    details: &ErrorDetails,
+    request_id: Option<&str>,
+    format: ErrorFormat,
+    path: &str,
+) -> Response {
+    let (content_type, body) = match format {
+        ErrorFormat::Json => (
+            "application/json",
+            serde_json::to_vec(&ErrorResponse {
+                error: &details.message,
+                code: details.code,
+                request_id,
+            }),
+        ),
+        ErrorFormat::Problem => (
+            "application/problem+json",
+            serde_json::to_vec(&ProblemDetails {
+                problem_type: format!("urn:golive:problem:{}", details.code),
+                title: status.canonical_reason().unwrap_or("Error"),
+                status: status.as_u16(),
+                detail: &details.message,
+                instance: path,
+                code: details.code,
+                request_id,
+            }),
+        ),
+    };
+
+    let mut response = Response::new(Body::from(body.unwrap_or_default()));
+    *response.status_mut() = status;
+    response
+        .headers_mut()
+        .insert(header::CONTENT_TYPE, HeaderValue::from_static(content_type));
+    response
+}
+
+/// Stable code for error responses produced outside our handlers, such as
+/// axum's extractor rejections.
+fn code_for_status(status: StatusCode) -> &'static str {
+    match status {
+        StatusCode::BAD_REQUEST => "bad_request",
+        StatusCode::UNAUTHORIZED => "unauthorized",
+        StatusCode::FORBIDDEN => "forbidden",
+        StatusCode::NOT_FOUND => "not_found",
+        StatusCode::METHOD_NOT_ALLOWED => "method_not_allowed",
+        StatusCode::CONFLICT => "conflict",
+        StatusCode::PRECONDITION_FAILED => "precondition_failed",
+        StatusCode::PRECONDITION_REQUIRED => "precondition_required",
+        StatusCode::PAYLOAD_TOO_LARGE => "payload_too_large",
+        StatusCode::UNSUPPORTED_MEDIA_TYPE => "unsupported_media_type",
+        StatusCode::UNPROCESSABLE_ENTITY => "validation_failed",
+        s if s.is_server_error() => "internal_error",
+        _ => "bad_request",
+    }
+}
+
+/// Middleware giving every error response the same shape: adds the request id
+/// (set by `SetRequestIdLayer`), honours `Accept: application/problem+json`,
+/// and converts axum's plain-text rejections into structured errors.
+pub async fn render_errors(
+    State(config): State<Arc<Config>>,
+    req: Request,
+    next: Next,
+) -> Response {
+    let request_id = req
+        .headers()
+        .get("x-request-id")
+        .and_then(|v| v.to_str().ok())
+        .map(String::from);
+    let wants_problem = req
+        .headers()
+        .get(header::ACCEPT)
+        .and_then(|v| v.to_str().ok())
+        .is_some_and(|v| v.contains("application/problem+json"));
+    let format = if wants_problem {
+        ErrorFormat::Problem
+    } else {
+        config.error_format
+    };
+    let path = req.uri().path().to_string();
+
+    let response = next.run(req).await;
+    let status = response.status();
+    if !(status.is_client_error() || status.is_server_error()) {
+        return response;
+    }
+
+    let (parts, body) = response.into_parts();
+    let details = match parts.extensions.get::<ErrorDetails>() {
+        Some(details) => details.clone(),
+        None => {
+            let is_text = parts
+                .headers
+                .get(header::CONTENT_TYPE)
+                .and_then(|v| v.to_str().ok())
+                .is_none_or(|v| v.starts_with("text/plain"));
+            if !is_text {
+                return Response::from_parts(parts, body);
+            }
+            let text = to_bytes(body, 64 * 1024).await.unwrap_or_default();
+            let message = String::from_utf8_lossy(&text).trim().to_string();
+            ErrorDetails {
+                code: code_for_status(status),
+                message: if message.is_empty() {
+                    status.canonical_reason().unwrap_or("Error").to_string()
+                } else {
+                    message
+                },
+            }
+        }
+    };
+
+    let mut rendered = render(status, &details, request_id.as_deref(), format, &path);
+    for (name, value) in parts.headers.iter() {
+        if name != header::CONTENT_TYPE && name != header::CONTENT_LENGTH {
+            rendered.headers_mut().insert(name, value.clone());
+        }
+    }
+    rendered
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    async fn body_json(response: Response) -> serde_json::Value {
+        let bytes = to_bytes(response.into_body(), usize::MAX).await.unwrap();
+        serde_json::from_slice(&bytes).unwrap()
+    }
+
+    #[test]
+    fn test_codes_and_statuses() {
+        let err = AppError::report_not_found("abc");
+        assert_eq!(err.status(), StatusCode::NOT_FOUND);
+        assert_eq!(err.code(), "not_found");
+        assert_eq!(err.message(), "Report not found: abc");
+
+        let err: AppError = ValidationError::TooLarge("too big".to_string()).into();
+        assert_eq!(err.status(), StatusCode::PAYLOAD_TOO_LARGE);
+        assert_eq!(err.code(), "payload_too_large");
+    }
+
+    #[tokio::test]
+    async fn test_into_response_json_body() {
+        let response = AppError::Conflict("already claimed".to_string()).into_response();
+        assert_eq!(response.status(), StatusCode::CONFLICT);
+        let body = body_json(response).await;
+        assert_eq!(body["error"], "already claimed");
+        assert_eq!(body["code"], "conflict");
+        assert!(body.get("request_id").is_none());
+    }
+
+    #[tokio::test]
+    async fn test_problem_details_rendering() {
+        let details = ErrorDetails {
+            code: "not_found",
+            message: "Report not found: abc".to_string(),
+        };
+        let response = render(
+            StatusCode::NOT_FOUND,
+            &details,
+            Some("req-1"),
+            ErrorFormat::Problem,
+            "/api/v1/reports/abc",
+        );
+        assert_eq!(
+            response.headers()[header::CONTENT_TYPE],
+            "application/problem+json"
+        );
+        let body = body_json(response).await;
+        assert_eq!(body["type"], "urn:golive:problem:not_found");
+        assert_eq!(body["title"], "Not Found");
+        assert_eq!(body["status"], 404);
+        assert_eq!(body["instance"], "/api/v1/reports/abc");
+        assert_eq!(body["request_id"], "req-1");
+    }
+}
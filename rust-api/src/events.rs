@@ -0,0 +1,28 @@
+use sqlx::{Postgres, Transaction};
+
+use crate::config::Config;
+use crate::error::AppError;
+use crate::Report;
+use crate::{attestations, chat, notifications, webhooks};
+
+// ============================================================================
+// Report Events
+// ============================================================================
+
+/// Announces `event_type` for `report` on every outgoing channel: webhook
+/// subscribers, email and chat. Approvals are also attested. Runs in the
+/// caller's transaction, so nothing is sent unless the change it describes
+/// commits.
+pub async fn publish(
+    tx: &mut Transaction<'_, Postgres>,
+    config: &Config,
+    event_type: &str,
+    report: &Report,
+) -> Result<(), AppError> {
+    if event_type == "report.approved" {
+        attestations::issue(tx, &config.signing, report).await?;
+    }
+    webhooks::emit(tx, config, event_type, report).await?;
+    notifications::notify(tx, config, event_type, report).await?;
+    chat::route(tx, config, event_type, report).await
+}
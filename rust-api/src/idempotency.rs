@@ -0,0 +1,207 @@
+use axum::{
+    http::{HeaderMap, HeaderValue, StatusCode},
+    response::{IntoResponse, Json, Response},
+};
+use serde_json::{json, Value as JsonValue};
+use sha2::{Digest, Sha256};
+use sqlx::{PgPool, Postgres, Transaction};
+use uuid::Uuid;
+
+use crate::error::AppError;
+use crate::CreateReportRequest;
+
+// ============================================================================
+// Idempotent Submission
+// ============================================================================
+
+pub const IDEMPOTENCY_KEY_HEADER: &str = "idempotency-key";
+pub const REPLAYED_HEADER: &str = "idempotent-replayed";
+pub const DUPLICATE_HEADER: &str = "x-duplicate-of";
+
+const MAX_KEY_LEN: usize = 255;
+
+/// SHA-256 over the submitted content, used both as the request fingerprint
+/// for `Idempotency-Key` and to spot exact re-submissions without a key.
+///
+/// `serde_json` keeps object keys sorted, so the hash does not depend on the
+/// key order the client happened to serialize.
+pub fn content_hash(req: &CreateReportRequest) -> String {
+    let canonical = json!({
+        "server_name": req.server_name,
+        "repository_url": req.repository_url,
+        "developer_email": req.developer_email,
+        "report_data": req.report_data,
+        "report_json": req.report_json,
+        "raw_json": req.raw_json,
+    });
+    let bytes = serde_json::to_vec(&canonical).unwrap_or_default();
+    hex::encode(Sha256::digest(&bytes))
+}
+
+/// Reads the optional `Idempotency-Key` header.
+pub fn key_from_headers(headers: &HeaderMap) -> Result<Option<String>, AppError> {
+    let Some(value) = headers.get(IDEMPOTENCY_KEY_HEADER) else {
+        return Ok(None);
+    };
+    let key = value
+        .to_str()
+        .map_err(|_| AppError::BadRequest("Idempotency-Key must be visible ASCII".to_string()))?
+        .trim();
+    if key.is_empty() || key.len() > MAX_KEY_LEN {
+        return Err(AppError::BadRequest(format!(
+            "Idempotency-Key must be 1-{} characters",
+            MAX_KEY_LEN
+        )));
+    }
+    Ok(Some(key.to_string()))
+}
+
+/// Outcome of claiming a key at the start of a request.
+pub enum Claim {
+    /// First use of the key; the caller must [`complete`] it in the same transaction.
+    New,
+    /// The key was already used for an identical request; send this instead.
+    Replay(Response),
+}
+
+/// Claims `key` inside `tx`.
+///
+/// The insert takes a row lock on the key, so a concurrent retry with the
+/// same key waits for the first request to commit (or roll back) and then
+/// sees its stored response rather than creating a second report.
+pub async fn claim(
+    tx: &mut Transaction<'_, Postgres>,
+    key: &str,
+    request_hash: &str,
+    ttl_hours: i32,
+) -> Result<Claim, AppError> {
+    sqlx::query(
+        "DELETE FROM idempotency_keys WHERE key = $1 AND created_at < NOW() - make_interval(hours => $2)",
+    )
+    .bind(key)
+    .bind(ttl_hours)
+    .execute(&mut **tx)
+    .await
+    .map_err(AppError::db("Failed to expire idempotency key"))?;
+
+    let inserted = sqlx::query(
+        "INSERT INTO idempotency_keys (key, request_hash) VALUES ($1, $2) ON CONFLICT (key) DO NOTHING",
+    )
+    .bind(key)
+    .bind(request_hash)
+    .execute(&mut **tx)
+    .await
+    .map_err(AppError::db("Failed to store idempotency key"))?;
+    if inserted.rows_affected() == 1 {
+        return Ok(Claim::New);
+    }
+
+    let (stored_hash, status, body): (String, Option<i16>, Option<JsonValue>) = sqlx::query_as(
+        "SELECT request_hash, response_status, response_body FROM idempotency_keys WHERE key = $1",
+    )
+    .bind(key)
+    .fetch_one(&mut **tx)
+    .await
+    .map_err(AppError::db("Failed to read idempotency key"))?;
+
+    if stored_hash != request_hash {
+        return Err(AppError::IdempotencyKeyReused(
+            "Idempotency-Key was already used with a different request body".to_string(),
+        ));
+    }
+
+    // Rows are only ever committed together with their response, so a
+    // missing one means the row was written by something other than `complete`.
+    let (Some(status), Some(body)) = (status, body) else {
+        return Err(AppError::Conflict(
+            "A request with this Idempotency-Key has not completed".to_string(),
+        ));
+    };
+    let status = StatusCode::from_u16(status as u16).unwrap_or(StatusCode::OK);
+    let mut response = (status, Json(body)).into_response();
+    response
+        .headers_mut()
+        .insert(REPLAYED_HEADER, HeaderValue::from_static("true"));
+    Ok(Claim::Replay(response))
+}
+
+/// Drops keys past `ttl_hours`. [`claim`] only expires the key it is given,
+/// so keys that are never reused would otherwise pile up.
+pub async fn purge_expired(pool: &PgPool, ttl_hours: i32) -> Result<u64, sqlx::Error> {
+    let purged = sqlx::query(
+        "DELETE FROM idempotency_keys WHERE created_at < NOW() - make_interval(hours => $1)",
+    )
+    .bind(ttl_hours)
+    .execute(pool)
+    .await?;
+    Ok(purged.rows_affected())
+}
+
+/// Stores the response for a claimed key so retries can replay it.
+pub async fn complete(
+    tx: &mut Transaction<'_, Postgres>,
+    key: &str,
+    status: StatusCode,
+    body: &JsonValue,
+    report_id: Uuid,
+) -> Result<(), AppError> {
+    sqlx::query(
+        r#"
+        UPDATE idempotency_keys
+        SET response_status = $2, response_body = $3, report_id = $4, completed_at = NOW()
+        WHERE key = $1
+        "#,
+    )
+    .bind(key)
+    .bind(status.as_u16() as i16)
+    .bind(body)
+    .bind(report_id)
+    .execute(&mut **tx)
+    .await
+    .map_err(AppError::db("Failed to store idempotent response"))?;
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_content_hash_ignores_key_order() {
+        let a: JsonValue = serde_json::from_str(r#"{"a": 1, "b": {"c": 2, "d": 3}}"#).unwrap();
+        let b: JsonValue = serde_json::from_str(r#"{"b": {"d": 3, "c": 2}, "a": 1}"#).unwrap();
+        assert_eq!(
+            content_hash(&CreateReportRequest::sample(a)),
+            content_hash(&CreateReportRequest::sample(b))
+        );
+    }
+
+    #[test]
+    fn test_content_hash_changes_with_content() {
+        let mut other = CreateReportRequest::sample(json!({}));
+        other.report_data = "# Changed".to_string();
+        assert_ne!(
+            content_hash(&CreateReportRequest::sample(json!({}))),
+            content_hash(&other)
+        );
+        assert_eq!(content_hash(&other).len(), 64);
+    }
+
+    #[test]
+    fn test_key_from_headers() {
+        let mut headers = HeaderMap::new();
+        assert!(key_from_headers(&headers).unwrap().is_none());
+
+        headers.insert(
+            IDEMPOTENCY_KEY_HEADER,
+            HeaderValue::from_static(" abc-123 "),
+        );
+        assert_eq!(
+            key_from_headers(&headers).unwrap().as_deref(),
+            Some("abc-123")
+        );
+
+        headers.insert(IDEMPOTENCY_KEY_HEADER, HeaderValue::from_static(""));
+        assert!(key_from_headers(&headers).is_err());
+    }
+}
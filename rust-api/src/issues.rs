@@ -0,0 +1,412 @@
+use axum::{
+    extract::{Path, Query, State},
+    response::Json,
+};
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+use sqlx::{PgExecutor, PgPool, Postgres, QueryBuilder, Transaction};
+use std::collections::HashMap;
+use uuid::Uuid;
+
+use crate::error::AppError;
+use crate::waivers::description_hash;
+use crate::{extract_issues, Issue, Report, ISSUE_FIELDS};
+
+// ============================================================================
+// Tracked Issues
+// ============================================================================
+
+const STATUSES: [&str; 4] = ["open", "fixed", "waived", "reopened"];
+
+/// Stable identity of an issue across a server's submissions: its phase
+/// plus the normalized description. Severity and wording of impact or
+/// recommendation may change without creating a new issue.
+pub fn fingerprint(phase_id: Option<&str>, description: &str) -> String {
+    let key = format!(
+        "{}\n{}",
+        phase_id.unwrap_or(""),
+        description_hash(description)
+    );
+    hex::encode(Sha256::digest(key.as_bytes()))
+}
+
+/// Records the issues of a newly submitted report, inside its transaction.
+///
+/// Issues seen before on the same server keep their id; a `fixed` issue that
+/// shows up again becomes `reopened`. Open issues missing from this report
+/// are marked `fixed` by it.
+pub async fn sync_report(
+    tx: &mut Transaction<'_, Postgres>,
+    report: &Report,
+) -> Result<(), AppError> {
+    let failed = || AppError::db("Failed to record report issues");
+
+    for list in ISSUE_FIELDS {
+        for (position, issue) in extract_issues(&report.report_json, list)
+            .into_iter()
+            .enumerate()
+        {
+            let issue_id: Uuid = sqlx::query_scalar(
+                r#"
+                INSERT INTO issues
+                    (server_name, fingerprint, description_hash, category, severity, phase_id,
+                     description, impact, recommendation, status,
+                     first_seen_report_id, last_seen_report_id, first_seen_at, last_seen_at)
+                VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9, 'open', $10, $10, $11, $11)
+                ON CONFLICT (server_name, fingerprint) DO UPDATE SET
+                    category = EXCLUDED.category,
+                    severity = EXCLUDED.severity,
+                    description = EXCLUDED.description,
+                    impact = EXCLUDED.impact,
+                    recommendation = EXCLUDED.recommendation,
+                    last_seen_report_id = EXCLUDED.last_seen_report_id,
+                    last_seen_at = EXCLUDED.last_seen_at,
+                    status = CASE WHEN issues.status = 'fixed' THEN 'reopened' ELSE issues.status END,
+                    reopened_at = CASE WHEN issues.status = 'fixed' THEN NOW() ELSE issues.reopened_at END,
+                    status_changed_at = CASE WHEN issues.status = 'fixed' THEN NOW() ELSE issues.status_changed_at END,
+                    fixed_at = NULL,
+                    fixed_in_report_id = NULL
+                RETURNING id
+                "#,
+            )
+            .bind(&report.server_name)
+            .bind(fingerprint(issue.phase_id.as_deref(), &issue.description))
+            .bind(description_hash(&issue.description))
+            .bind(list)
+            .bind(&issue.severity)
+            .bind(&issue.phase_id)
+            .bind(&issue.description)
+            .bind(&issue.impact)
+            .bind(&issue.recommendation)
+            .bind(report.id)
+            .bind(report.submitted_at)
+            .fetch_one(&mut **tx)
+            .await
+            .map_err(failed())?;
+
+            sqlx::query(
+                r#"
+                INSERT INTO report_issues
+                    (report_id, issue_id, list, position, severity, description, impact, recommendation, phase_id)
+                VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9)
+                "#,
+            )
+            .bind(report.id)
+            .bind(issue_id)
+            .bind(list)
+            .bind(position as i32)
+            .bind(&issue.severity)
+            .bind(&issue.description)
+            .bind(&issue.impact)
+            .bind(&issue.recommendation)
+            .bind(&issue.phase_id)
+            .execute(&mut **tx)
+            .await
+            .map_err(failed())?;
+        }
+    }
+
+    let fixed = sqlx::query(
+        r#"
+        UPDATE issues
+        SET status = 'fixed', fixed_at = NOW(), fixed_in_report_id = $2, status_changed_at = NOW()
+        WHERE server_name = $1 AND status <> 'fixed' AND last_seen_report_id IS DISTINCT FROM $2
+        "#,
+    )
+    .bind(&report.server_name)
+    .bind(report.id)
+    .execute(&mut **tx)
+    .await
+    .map_err(failed())?;
+    if fixed.rows_affected() > 0 {
+        tracing::info!(
+            "Report {} fixed {} issue(s) on {}",
+            report.id,
+            fixed.rows_affected(),
+            report.server_name
+        );
+    }
+
+    refresh_waived(&mut **tx, Some(&report.server_name)).await?;
+    Ok(())
+}
+
+/// Moves unfixed issues between `waived` and `open`/`reopened` as waivers
+/// are granted, revoked or expire. Coverage is judged against the report the
+/// issue was last seen in, the same way the issues endpoint does.
+pub async fn refresh_waived(
+    executor: impl PgExecutor<'_>,
+    server_name: Option<&str>,
+) -> Result<u64, AppError> {
+    let result = sqlx::query(
+        r#"
+        WITH coverage AS (
+            SELECT i.id,
+                   CASE
+                       WHEN EXISTS (
+                           SELECT 1 FROM issue_waivers w
+                           WHERE w.revoked_at IS NULL AND w.expires_at > NOW()
+                             AND w.description_hash = i.description_hash
+                             AND w.phase_id IS NOT DISTINCT FROM i.phase_id
+                             AND (w.report_id = i.last_seen_report_id
+                                  OR (w.scope = 'server' AND w.server_name = i.server_name
+                                      AND w.effective_from <= COALESCE(r.submitted_at, i.last_seen_at)))
+                       ) THEN 'waived'
+                       WHEN i.reopened_at IS NOT NULL THEN 'reopened'
+                       ELSE 'open'
+                   END AS status
+            FROM issues i
+            LEFT JOIN mcp_server_reports r ON r.id = i.last_seen_report_id
+            WHERE i.status <> 'fixed' AND ($1::text IS NULL OR i.server_name = $1)
+        )
+        UPDATE issues i
+        SET status = c.status, status_changed_at = NOW()
+        FROM coverage c
+        WHERE c.id = i.id AND i.status <> c.status
+        "#,
+    )
+    .bind(server_name)
+    .execute(executor)
+    .await
+    .map_err(AppError::db("Failed to refresh waived issues"))?;
+    Ok(result.rows_affected())
+}
+
+/// Materializes issues for reports submitted before issue tracking existed.
+/// Runs once, when the `issues` table is still empty.
+pub async fn backfill(pool: &PgPool) -> Result<usize, AppError> {
+    let failed = || AppError::db("Failed to backfill issues");
+    let empty: bool = sqlx::query_scalar("SELECT NOT EXISTS (SELECT 1 FROM report_issues)")
+        .fetch_one(pool)
+        .await
+        .map_err(failed())?;
+    if !empty {
+        return Ok(0);
+    }
+
+    let reports =
+        sqlx::query_as::<_, Report>("SELECT * FROM mcp_server_reports ORDER BY submitted_at, id")
+            .fetch_all(pool)
+            .await
+            .map_err(failed())?;
+    for report in &reports {
+        let mut tx = pool.begin().await.map_err(failed())?;
+        sync_report(&mut tx, report).await?;
+        tx.commit().await.map_err(failed())?;
+    }
+    Ok(reports.len())
+}
+
+#[derive(Debug, sqlx::FromRow)]
+struct OccurrenceRow {
+    list: String,
+    issue_id: Uuid,
+    status: String,
+    severity: String,
+    description: String,
+    impact: Option<String>,
+    recommendation: Option<String>,
+    phase_id: Option<String>,
+}
+
+/// A report's issues as recorded at submission, grouped by list.
+pub async fn for_report(
+    pool: &PgPool,
+    report_id: Uuid,
+) -> Result<HashMap<String, Vec<Issue>>, AppError> {
+    let rows = sqlx::query_as::<_, OccurrenceRow>(
+        r#"
+        SELECT ri.list, ri.issue_id, i.status, ri.severity, ri.description,
+               ri.impact, ri.recommendation, ri.phase_id
+        FROM report_issues ri
+        JOIN issues i ON i.id = ri.issue_id
+        WHERE ri.report_id = $1
+        ORDER BY ri.list, ri.position
+        "#,
+    )
+    .bind(report_id)
+    .fetch_all(pool)
+    .await
+    .map_err(AppError::db("Failed to load report issues"))?;
+
+    let mut lists: HashMap<String, Vec<Issue>> = HashMap::new();
+    for row in rows {
+        lists.entry(row.list).or_default().push(Issue {
+            severity: row.severity,
+            description: row.description,
+            impact: row.impact,
+            recommendation: row.recommendation,
+            phase_id: row.phase_id,
+            issue_id: Some(row.issue_id),
+            status: Some(row.status),
+        });
+    }
+    Ok(lists)
+}
+
+#[derive(Debug, Serialize, sqlx::FromRow)]
+pub struct TrackedIssue {
+    id: Uuid,
+    server_name: String,
+    fingerprint: String,
+    /// `critical_issues`, `warnings` or `recommendations` in the latest report.
+    category: String,
+    severity: String,
+    phase_id: Option<String>,
+    description: String,
+    impact: Option<String>,
+    recommendation: Option<String>,
+    /// `open`, `fixed`, `waived` or `reopened`.
+    status: String,
+    /// `None` once the report has been deleted.
+    first_seen_report_id: Option<Uuid>,
+    last_seen_report_id: Option<Uuid>,
+    first_seen_at: DateTime<Utc>,
+    last_seen_at: DateTime<Utc>,
+    fixed_at: Option<DateTime<Utc>>,
+    fixed_in_report_id: Option<Uuid>,
+    reopened_at: Option<DateTime<Utc>>,
+    status_changed_at: DateTime<Utc>,
+}
+
+#[derive(Debug, Serialize, sqlx::FromRow)]
+pub struct Occurrence {
+    report_id: Uuid,
+    submitted_at: DateTime<Utc>,
+    report_status: String,
+    list: String,
+    severity: String,
+}
+
+#[derive(Debug, Serialize)]
+pub struct IssueHistory {
+    #[serde(flatten)]
+    issue: TrackedIssue,
+    occurrences: Vec<Occurrence>,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct IssueQuery {
+    /// Comma-separated, e.g. `open,reopened`.
+    status: Option<String>,
+    /// Case-insensitive, e.g. `critical`.
+    severity: Option<String>,
+    server_name: Option<String>,
+    category: Option<String>,
+    phase_id: Option<String>,
+}
+
+fn parse_statuses(raw: &str) -> Result<Vec<String>, AppError> {
+    raw.split(',')
+        .map(|s| s.trim().to_lowercase())
+        .filter(|s| !s.is_empty())
+        .map(|s| {
+            if STATUSES.contains(&s.as_str()) {
+                Ok(s)
+            } else {
+                Err(AppError::BadRequest(format!(
+                    "Unknown issue status {}; expected one of {}",
+                    s,
+                    STATUSES.join(", ")
+                )))
+            }
+        })
+        .collect()
+}
+
+/// Cross-report view, e.g. `?status=open,reopened&severity=critical` for all
+/// unresolved critical issues on every server.
+pub async fn list_issues(
+    State(pool): State<PgPool>,
+    Query(query): Query<IssueQuery>,
+) -> Result<Json<Vec<TrackedIssue>>, AppError> {
+    let mut sql = QueryBuilder::new("SELECT * FROM issues WHERE TRUE");
+    if let Some(raw) = &query.status {
+        sql.push(" AND status = ANY(")
+            .push_bind(parse_statuses(raw)?)
+            .push(")");
+    }
+    if let Some(severity) = &query.severity {
+        sql.push(" AND UPPER(severity) = UPPER(")
+            .push_bind(severity)
+            .push(")");
+    }
+    if let Some(server_name) = &query.server_name {
+        sql.push(" AND server_name = ").push_bind(server_name);
+    }
+    if let Some(category) = &query.category {
+        sql.push(" AND category = ").push_bind(category);
+    }
+    if let Some(phase_id) = &query.phase_id {
+        sql.push(" AND phase_id = ").push_bind(phase_id);
+    }
+    sql.push(" ORDER BY last_seen_at DESC, server_name");
+
+    let issues = sql
+        .build_query_as::<TrackedIssue>()
+        .fetch_all(&pool)
+        .await
+        .map_err(AppError::db("Failed to list issues"))?;
+
+    tracing::info!("Listed {} tracked issues", issues.len());
+    Ok(Json(issues))
+}
+
+pub async fn get_issue(
+    State(pool): State<PgPool>,
+    Path(issue_id): Path<Uuid>,
+) -> Result<Json<IssueHistory>, AppError> {
+    let issue = sqlx::query_as::<_, TrackedIssue>("SELECT * FROM issues WHERE id = $1")
+        .bind(issue_id)
+        .fetch_optional(&pool)
+        .await
+        .map_err(AppError::db("Failed to fetch issue"))?
+        .ok_or_else(|| AppError::NotFound(format!("Issue {} not found", issue_id)))?;
+
+    let occurrences = sqlx::query_as::<_, Occurrence>(
+        r#"
+        SELECT r.id AS report_id, r.submitted_at, r.status AS report_status, ri.list, ri.severity
+        FROM report_issues ri
+        JOIN mcp_server_reports r ON r.id = ri.report_id
+        WHERE ri.issue_id = $1
+        ORDER BY r.submitted_at DESC
+        "#,
+    )
+    .bind(issue_id)
+    .fetch_all(&pool)
+    .await
+    .map_err(AppError::db("Failed to fetch issue history"))?;
+
+    Ok(Json(IssueHistory { issue, occurrences }))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_fingerprint_is_stable_across_rewording_whitespace() {
+        assert_eq!(
+            fingerprint(Some("P1"), "Missing  input validation"),
+            fingerprint(Some("P1"), "missing input\nvalidation")
+        );
+        assert_ne!(
+            fingerprint(Some("P1"), "Missing input validation"),
+            fingerprint(Some("P2"), "Missing input validation")
+        );
+        assert_ne!(
+            fingerprint(None, "Missing input validation"),
+            fingerprint(Some(""), "Other")
+        );
+    }
+
+    #[test]
+    fn test_parse_statuses() {
+        assert_eq!(
+            parse_statuses("open, Reopened").unwrap(),
+            vec!["open".to_string(), "reopened".to_string()]
+        );
+        assert!(parse_statuses("closed").is_err());
+    }
+}
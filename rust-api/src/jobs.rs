@@ -0,0 +1,640 @@
+use axum::{
+    extract::{Path, Query, State},
+    response::Json,
+};
+use chrono::{DateTime, Duration, Utc};
+use serde::{Deserialize, Serialize};
+use serde_json::Value as JsonValue;
+use sqlx::{PgExecutor, PgPool, QueryBuilder};
+use std::str::FromStr;
+use std::sync::Arc;
+use uuid::Uuid;
+
+use crate::auth::CurrentUser;
+use crate::config::Config;
+use crate::error::AppError;
+use crate::{chat, maintenance, notifications, webhooks};
+
+// ============================================================================
+// Background Jobs
+// ============================================================================
+
+const STATUSES: [&str; 4] = ["queued", "running", "succeeded", "dead"];
+const MAX_BACKOFF_SECS: i64 = 3600;
+const MAX_LIST: i64 = 500;
+
+/// Where jobs are run (`JOB_WORKER`). `golive-api worker` always runs them.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum WorkerMode {
+    /// Alongside the HTTP server, in the same process.
+    #[default]
+    Embedded,
+    /// Not by the HTTP server; a separate `golive-api worker` process runs them.
+    Off,
+}
+
+impl FromStr for WorkerMode {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.to_ascii_lowercase().as_str() {
+            "embedded" => Ok(WorkerMode::Embedded),
+            "off" => Ok(WorkerMode::Off),
+            other => Err(format!("unknown worker mode: {}", other)),
+        }
+    }
+}
+
+/// A recurring job, enqueued whenever its cron expression fires.
+#[derive(Debug, Clone)]
+pub struct ScheduleSpec {
+    pub name: String,
+    pub kind: String,
+    /// Six-field cron expression with seconds, e.g. `0 * * * * *`.
+    /// `None` when disabled with `JOB_SCHEDULE_<NAME>=off`.
+    pub cron: Option<String>,
+}
+
+#[derive(Debug, Clone)]
+pub struct JobsConfig {
+    pub worker: WorkerMode,
+    /// Jobs run at the same time per process (`JOB_CONCURRENCY`).
+    pub concurrency: usize,
+    /// Attempts before a job is dead-lettered (`JOB_MAX_ATTEMPTS`).
+    pub max_attempts: i32,
+    /// First retry delay, doubled per attempt (`JOB_BACKOFF_SECS`).
+    pub backoff_secs: i64,
+    /// A running job is abandoned and retried after this (`JOB_TIMEOUT_SECS`).
+    pub timeout_secs: i64,
+    /// Finished jobs are purged after this (`JOB_RETENTION_DAYS`).
+    pub retention_days: i64,
+    pub schedules: Vec<ScheduleSpec>,
+}
+
+impl Default for JobsConfig {
+    fn default() -> Self {
+        Self {
+            worker: WorkerMode::default(),
+            concurrency: 2,
+            max_attempts: 5,
+            backoff_secs: 30,
+            timeout_secs: 600,
+            retention_days: 7,
+            schedules: vec![ScheduleSpec {
+                name: "maintenance".to_string(),
+                kind: "maintenance".to_string(),
+                cron: Some("0 * * * * *".to_string()),
+            }],
+        }
+    }
+}
+
+impl JobsConfig {
+    /// Delay before the retry that follows attempt number `attempts`.
+    pub fn backoff(&self, attempts: i32) -> Duration {
+        let exponent = attempts.clamp(1, 20) as u32 - 1;
+        let secs = self
+            .backoff_secs
+            .saturating_mul(2_i64.saturating_pow(exponent));
+        Duration::seconds(secs.min(MAX_BACKOFF_SECS))
+    }
+}
+
+/// Parses a cron expression, returning its next firing after `after`.
+pub fn next_run(cron: &str, after: DateTime<Utc>) -> Result<DateTime<Utc>, String> {
+    let schedule =
+        cron::Schedule::from_str(cron).map_err(|e| format!("invalid cron {:?}: {}", cron, e))?;
+    schedule
+        .after(&after)
+        .next()
+        .ok_or_else(|| format!("cron {:?} never fires again", cron))
+}
+
+#[derive(Debug, Clone, Serialize, sqlx::FromRow)]
+pub struct Job {
+    pub id: Uuid,
+    pub kind: String,
+    pub payload: JsonValue,
+    pub status: String,
+    pub attempts: i32,
+    pub max_attempts: i32,
+    pub run_at: DateTime<Utc>,
+    pub dedupe_key: Option<String>,
+    pub locked_by: Option<String>,
+    pub locked_at: Option<DateTime<Utc>>,
+    pub last_error: Option<String>,
+    pub created_at: DateTime<Utc>,
+    pub finished_at: Option<DateTime<Utc>>,
+}
+
+/// A job to enqueue. Only `kind` and `payload` are required.
+#[derive(Debug)]
+pub struct NewJob<'a> {
+    pub kind: &'a str,
+    pub payload: JsonValue,
+    pub run_at: Option<DateTime<Utc>>,
+    pub max_attempts: Option<i32>,
+    /// At most one queued or running job per key.
+    pub dedupe_key: Option<&'a str>,
+}
+
+impl<'a> NewJob<'a> {
+    pub fn new(kind: &'a str, payload: JsonValue) -> Self {
+        Self {
+            kind,
+            payload,
+            run_at: None,
+            max_attempts: None,
+            dedupe_key: None,
+        }
+    }
+}
+
+/// Enqueues a job, inside the caller's transaction when given one. Returns
+/// `None` when a job with the same `dedupe_key` is already pending.
+pub async fn enqueue(
+    executor: impl PgExecutor<'_>,
+    config: &JobsConfig,
+    job: NewJob<'_>,
+) -> Result<Option<Uuid>, sqlx::Error> {
+    sqlx::query_scalar(
+        r#"
+        INSERT INTO jobs (kind, payload, run_at, max_attempts, dedupe_key)
+        VALUES ($1, $2, COALESCE($3, NOW()), $4, $5)
+        ON CONFLICT (dedupe_key) WHERE status IN ('queued', 'running') DO NOTHING
+        RETURNING id
+        "#,
+    )
+    .bind(job.kind)
+    .bind(&job.payload)
+    .bind(job.run_at)
+    .bind(job.max_attempts.unwrap_or(config.max_attempts))
+    .bind(job.dedupe_key)
+    .fetch_optional(executor)
+    .await
+}
+
+/// Runs one job. Errors are recorded on the job and retried with backoff.
+async fn perform(pool: &PgPool, config: &Config, job: &Job) -> Result<(), String> {
+    match job.kind.as_str() {
+        "maintenance" => maintenance::run_once(pool, config).await,
+        webhooks::DELIVERY_JOB => webhooks::deliver(pool, config, job).await,
+        notifications::SEND_JOB => notifications::deliver(pool, config, job).await,
+        chat::POST_JOB => chat::deliver(pool, config, job).await,
+        other => Err(format!("no handler for job kind {:?}", other)),
+    }
+}
+
+/// Upserts the configured schedules. A changed cron expression takes effect
+/// from now; schedules no longer configured are disabled.
+pub async fn register_schedules(pool: &PgPool, config: &JobsConfig) -> Result<(), sqlx::Error> {
+    let mut tx = pool.begin().await?;
+    sqlx::query("UPDATE job_schedules SET enabled = FALSE")
+        .execute(&mut *tx)
+        .await?;
+    for spec in &config.schedules {
+        let Some(cron) = &spec.cron else {
+            continue;
+        };
+        let next = next_run(cron, Utc::now()).expect("schedules are validated at startup");
+        sqlx::query(
+            r#"
+            INSERT INTO job_schedules (name, kind, cron, next_run_at, enabled)
+            VALUES ($1, $2, $3, $4, TRUE)
+            ON CONFLICT (name) DO UPDATE SET
+                kind = EXCLUDED.kind,
+                enabled = TRUE,
+                next_run_at = CASE WHEN job_schedules.cron = EXCLUDED.cron
+                                   THEN job_schedules.next_run_at
+                                   ELSE EXCLUDED.next_run_at END,
+                cron = EXCLUDED.cron
+            "#,
+        )
+        .bind(&spec.name)
+        .bind(&spec.kind)
+        .bind(cron)
+        .bind(next)
+        .execute(&mut *tx)
+        .await?;
+    }
+    tx.commit().await
+}
+
+/// Enqueues the schedules that are due. Each firing is claimed by moving
+/// `next_run_at` forward, so only one replica enqueues it.
+async fn fire_schedules(pool: &PgPool, config: &JobsConfig) -> Result<(), sqlx::Error> {
+    let due: Vec<(String, String, String, DateTime<Utc>)> = sqlx::query_as(
+        "SELECT name, kind, cron, next_run_at FROM job_schedules WHERE enabled AND next_run_at <= NOW()",
+    )
+    .fetch_all(pool)
+    .await?;
+
+    for (name, kind, cron, scheduled_for) in due {
+        let next = match next_run(&cron, Utc::now()) {
+            Ok(next) => next,
+            Err(e) => {
+                tracing::error!("Schedule {} disabled: {}", name, e);
+                sqlx::query("UPDATE job_schedules SET enabled = FALSE WHERE name = $1")
+                    .bind(&name)
+                    .execute(pool)
+                    .await?;
+                continue;
+            }
+        };
+        let mut tx = pool.begin().await?;
+        let claimed = sqlx::query(
+            r#"
+            UPDATE job_schedules SET next_run_at = $3, last_run_at = NOW()
+            WHERE name = $1 AND next_run_at = $2
+            "#,
+        )
+        .bind(&name)
+        .bind(scheduled_for)
+        .bind(next)
+        .execute(&mut *tx)
+        .await?;
+        if claimed.rows_affected() == 0 {
+            continue;
+        }
+        let mut job = NewJob::new(&kind, serde_json::json!({ "schedule": name }));
+        job.dedupe_key = Some(&name);
+        if enqueue(&mut *tx, config, job).await?.is_none() {
+            tracing::debug!(
+                "Schedule {} skipped; its previous run is still pending",
+                name
+            );
+        }
+        tx.commit().await?;
+    }
+    Ok(())
+}
+
+/// Returns jobs whose worker died or hung to the queue.
+async fn reclaim_abandoned(pool: &PgPool, config: &JobsConfig) -> Result<u64, sqlx::Error> {
+    let result = sqlx::query(
+        r#"
+        UPDATE jobs
+        SET status = CASE WHEN attempts >= max_attempts THEN 'dead' ELSE 'queued' END,
+            finished_at = CASE WHEN attempts >= max_attempts THEN NOW() END,
+            last_error = 'abandoned by worker ' || COALESCE(locked_by, '?'),
+            locked_by = NULL, locked_at = NULL, run_at = NOW()
+        WHERE status = 'running' AND locked_at < NOW() - make_interval(secs => $1)
+        "#,
+    )
+    .bind(config.timeout_secs as f64)
+    .execute(pool)
+    .await?;
+    Ok(result.rows_affected())
+}
+
+/// Deletes succeeded and dead jobs past the retention period.
+pub async fn purge_finished(pool: &PgPool, retention_days: i64) -> Result<u64, sqlx::Error> {
+    let result = sqlx::query(
+        r#"
+        DELETE FROM jobs
+        WHERE status IN ('succeeded', 'dead')
+          AND finished_at < NOW() - make_interval(days => $1)
+        "#,
+    )
+    .bind(retention_days as i32)
+    .execute(pool)
+    .await?;
+    Ok(result.rows_affected())
+}
+
+/// Takes the next due job, skipping rows other workers hold.
+async fn claim_next(pool: &PgPool, worker: &str) -> Result<Option<Job>, sqlx::Error> {
+    sqlx::query_as::<_, Job>(
+        r#"
+        UPDATE jobs
+        SET status = 'running', attempts = attempts + 1, locked_by = $1, locked_at = NOW()
+        WHERE id = (
+            SELECT id FROM jobs
+            WHERE status = 'queued' AND run_at <= NOW()
+            ORDER BY run_at, created_at
+            FOR UPDATE SKIP LOCKED
+            LIMIT 1
+        )
+        RETURNING *
+        "#,
+    )
+    .bind(worker)
+    .fetch_optional(pool)
+    .await
+}
+
+/// Records the outcome of this worker's run of `job`. A run that outlived
+/// `JOB_TIMEOUT_SECS` may have been reclaimed and handed to another worker
+/// meanwhile; its outcome is then dropped rather than overwriting that run.
+async fn finish(
+    pool: &PgPool,
+    config: &JobsConfig,
+    job: &Job,
+    outcome: Result<(), String>,
+) -> Result<(), sqlx::Error> {
+    let updated = match outcome {
+        Ok(()) => {
+            sqlx::query(
+                r#"
+                UPDATE jobs SET status = 'succeeded', finished_at = NOW(), locked_by = NULL, locked_at = NULL
+                WHERE id = $1 AND status = 'running' AND locked_by = $2 AND attempts = $3
+                "#,
+            )
+            .bind(job.id)
+            .bind(&job.locked_by)
+            .bind(job.attempts)
+            .execute(pool)
+            .await?
+        }
+        Err(error) if job.attempts >= job.max_attempts => {
+            tracing::error!(
+                "Job {} ({}) dead after {} attempt(s): {}",
+                job.id,
+                job.kind,
+                job.attempts,
+                error
+            );
+            sqlx::query(
+                r#"
+                UPDATE jobs SET status = 'dead', finished_at = NOW(), last_error = $2,
+                    locked_by = NULL, locked_at = NULL
+                WHERE id = $1 AND status = 'running' AND locked_by = $3 AND attempts = $4
+                "#,
+            )
+            .bind(job.id)
+            .bind(&error)
+            .bind(&job.locked_by)
+            .bind(job.attempts)
+            .execute(pool)
+            .await?
+        }
+        Err(error) => {
+            let retry_at = Utc::now() + config.backoff(job.attempts);
+            tracing::warn!(
+                "Job {} ({}) failed attempt {}/{}, retrying at {}: {}",
+                job.id,
+                job.kind,
+                job.attempts,
+                job.max_attempts,
+                retry_at,
+                error
+            );
+            sqlx::query(
+                r#"
+                UPDATE jobs SET status = 'queued', run_at = $2, last_error = $3,
+                    locked_by = NULL, locked_at = NULL
+                WHERE id = $1 AND status = 'running' AND locked_by = $4 AND attempts = $5
+                "#,
+            )
+            .bind(job.id)
+            .bind(retry_at)
+            .bind(&error)
+            .bind(&job.locked_by)
+            .bind(job.attempts)
+            .execute(pool)
+            .await?
+        }
+    };
+    if updated.rows_affected() == 0 {
+        tracing::warn!(
+            "Dropped outcome of job {} attempt {}: it was reclaimed from {}",
+            job.id,
+            job.attempts,
+            job.locked_by.as_deref().unwrap_or("?")
+        );
+    }
+    Ok(())
+}
+
+const POLL_INTERVAL: std::time::Duration = std::time::Duration::from_secs(1);
+const SCHEDULER_INTERVAL: std::time::Duration = std::time::Duration::from_secs(5);
+
+async fn worker_loop(pool: PgPool, config: Arc<Config>, worker: String) {
+    let timeout = std::time::Duration::from_secs(config.jobs.timeout_secs.max(1) as u64);
+    loop {
+        let job = match claim_next(&pool, &worker).await {
+            Ok(Some(job)) => job,
+            Ok(None) => {
+                tokio::time::sleep(POLL_INTERVAL).await;
+                continue;
+            }
+            Err(e) => {
+                tracing::error!("Worker {} failed to claim a job: {}", worker, e);
+                tokio::time::sleep(POLL_INTERVAL).await;
+                continue;
+            }
+        };
+        tracing::debug!("Worker {} running job {} ({})", worker, job.id, job.kind);
+        let outcome = match tokio::time::timeout(timeout, perform(&pool, &config, &job)).await {
+            Ok(outcome) => outcome,
+            Err(_) => Err(format!("timed out after {}s", timeout.as_secs())),
+        };
+        if let Err(e) = finish(&pool, &config.jobs, &job, outcome).await {
+            tracing::error!("Failed to record outcome of job {}: {}", job.id, e);
+        }
+    }
+}
+
+async fn scheduler_loop(pool: PgPool, config: Arc<Config>) {
+    let mut ticker = tokio::time::interval(SCHEDULER_INTERVAL);
+    loop {
+        ticker.tick().await;
+        if let Err(e) = fire_schedules(&pool, &config.jobs).await {
+            tracing::error!("Failed to fire job schedules: {}", e);
+        }
+        match reclaim_abandoned(&pool, &config.jobs).await {
+            Ok(0) => {}
+            Ok(n) => tracing::warn!("Reclaimed {} abandoned job(s)", n),
+            Err(e) => tracing::error!("Failed to reclaim abandoned jobs: {}", e),
+        }
+    }
+}
+
+/// Starts the scheduler and `JOB_CONCURRENCY` workers. Claims use
+/// `FOR UPDATE SKIP LOCKED`, so any number of processes can run them.
+pub fn spawn(pool: PgPool, config: Arc<Config>) {
+    let host = std::env::var("HOSTNAME").unwrap_or_else(|_| "local".to_string());
+    let process = format!("{}:{}", host, std::process::id());
+    for n in 0..config.jobs.concurrency.max(1) {
+        tokio::spawn(worker_loop(
+            pool.clone(),
+            config.clone(),
+            format!("{}/{}", process, n),
+        ));
+    }
+    tokio::spawn(scheduler_loop(pool, config));
+}
+
+// ----------------------------------------------------------------------------
+// Admin endpoints
+// ----------------------------------------------------------------------------
+
+fn require_admin(user: &CurrentUser, config: &Config) -> Result<(), AppError> {
+    if user.is_admin(&config.auth) {
+        Ok(())
+    } else {
+        Err(AppError::Forbidden(
+            "Background jobs are restricted to admins".to_string(),
+        ))
+    }
+}
+
+#[derive(Debug, Deserialize)]
+pub struct JobQuery {
+    status: Option<String>,
+    kind: Option<String>,
+    limit: Option<i64>,
+}
+
+/// Jobs, newest first.
+pub async fn list_jobs(
+    State(pool): State<PgPool>,
+    State(config): State<Arc<Config>>,
+    user: CurrentUser,
+    Query(query): Query<JobQuery>,
+) -> Result<Json<Vec<Job>>, AppError> {
+    require_admin(&user, &config)?;
+    let mut sql = QueryBuilder::new("SELECT * FROM jobs WHERE TRUE");
+    if let Some(status) = &query.status {
+        if !STATUSES.contains(&status.as_str()) {
+            return Err(AppError::BadRequest(format!(
+                "Unknown job status {}; expected one of {}",
+                status,
+                STATUSES.join(", ")
+            )));
+        }
+        sql.push(" AND status = ").push_bind(status);
+    }
+    if let Some(kind) = &query.kind {
+        sql.push(" AND kind = ").push_bind(kind);
+    }
+    sql.push(" ORDER BY created_at DESC LIMIT ")
+        .push_bind(query.limit.unwrap_or(100).clamp(1, MAX_LIST));
+
+    let jobs = sql
+        .build_query_as::<Job>()
+        .fetch_all(&pool)
+        .await
+        .map_err(AppError::db("Failed to list jobs"))?;
+    Ok(Json(jobs))
+}
+
+pub async fn get_job(
+    State(pool): State<PgPool>,
+    State(config): State<Arc<Config>>,
+    user: CurrentUser,
+    Path(job_id): Path<Uuid>,
+) -> Result<Json<Job>, AppError> {
+    require_admin(&user, &config)?;
+    sqlx::query_as::<_, Job>("SELECT * FROM jobs WHERE id = $1")
+        .bind(job_id)
+        .fetch_optional(&pool)
+        .await
+        .map_err(AppError::db("Failed to get job"))?
+        .map(Json)
+        .ok_or_else(|| AppError::NotFound(format!("Job {} not found", job_id)))
+}
+
+/// Requeues a dead job with a fresh set of attempts, or runs a queued job
+/// now instead of waiting out its backoff.
+pub async fn retry_job(
+    State(pool): State<PgPool>,
+    State(config): State<Arc<Config>>,
+    user: CurrentUser,
+    Path(job_id): Path<Uuid>,
+) -> Result<Json<Job>, AppError> {
+    require_admin(&user, &config)?;
+    let mut tx = pool
+        .begin()
+        .await
+        .map_err(AppError::db("Failed to retry job"))?;
+    let job = sqlx::query_as::<_, Job>(
+        r#"
+        UPDATE jobs
+        SET status = 'queued', run_at = NOW(), finished_at = NULL,
+            attempts = CASE WHEN status = 'dead' THEN 0 ELSE attempts END
+        WHERE id = $1 AND status IN ('dead', 'queued')
+        RETURNING *
+        "#,
+    )
+    .bind(job_id)
+    .fetch_optional(&mut *tx)
+    .await
+    .map_err(AppError::db("Failed to retry job"))?;
+
+    let Some(job) = job else {
+        let status: Option<String> = sqlx::query_scalar("SELECT status FROM jobs WHERE id = $1")
+            .bind(job_id)
+            .fetch_optional(&pool)
+            .await
+            .map_err(AppError::db("Failed to retry job"))?;
+        return Err(match status {
+            Some(status) => AppError::Conflict(format!(
+                "Job {} is {}; only dead or queued jobs can be retried",
+                job_id, status
+            )),
+            None => AppError::NotFound(format!("Job {} not found", job_id)),
+        });
+    };
+    if job.kind == webhooks::DELIVERY_JOB {
+        webhooks::reopen(&mut tx, &job).await?;
+    }
+    tx.commit()
+        .await
+        .map_err(AppError::db("Failed to retry job"))?;
+    tracing::info!("Job {} ({}) requeued by {}", job.id, job.kind, user.email);
+    Ok(Json(job))
+}
+
+#[derive(Debug, Serialize, sqlx::FromRow)]
+pub struct JobSchedule {
+    name: String,
+    kind: String,
+    cron: String,
+    enabled: bool,
+    next_run_at: DateTime<Utc>,
+    last_run_at: Option<DateTime<Utc>>,
+}
+
+pub async fn list_schedules(
+    State(pool): State<PgPool>,
+    State(config): State<Arc<Config>>,
+    user: CurrentUser,
+) -> Result<Json<Vec<JobSchedule>>, AppError> {
+    require_admin(&user, &config)?;
+    let schedules = sqlx::query_as::<_, JobSchedule>("SELECT * FROM job_schedules ORDER BY name")
+        .fetch_all(&pool)
+        .await
+        .map_err(AppError::db("Failed to list job schedules"))?;
+    Ok(Json(schedules))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_backoff_doubles_up_to_cap() {
+        let config = JobsConfig::default();
+        assert_eq!(config.backoff(1), Duration::seconds(30));
+        assert_eq!(config.backoff(2), Duration::seconds(60));
+        assert_eq!(config.backoff(4), Duration::seconds(240));
+        assert_eq!(config.backoff(30), Duration::seconds(MAX_BACKOFF_SECS));
+    }
+
+    #[test]
+    fn test_next_run() {
+        let at = DateTime::parse_from_rfc3339("2026-01-01T10:00:30Z")
+            .unwrap()
+            .with_timezone(&Utc);
+        let next = next_run("0 * * * * *", at).unwrap();
+        assert_eq!(next.to_rfc3339(), "2026-01-01T10:01:00+00:00");
+        assert!(next_run("every minute", at).is_err());
+    }
+
+    #[test]
+    fn test_worker_mode_parse() {
+        assert_eq!("OFF".parse::<WorkerMode>(), Ok(WorkerMode::Off));
+        assert!("sometimes".parse::<WorkerMode>().is_err());
+    }
+}
@@ -1,19 +1,91 @@
+mod admission;
+mod allowlist;
+mod approvals;
+mod attestations;
+mod assignment;
+mod auth;
+mod chat;
+mod comments;
+mod concurrency;
+mod conditions;
+mod config;
+mod drafts;
+mod error;
+mod events;
+mod idempotency;
+mod issues;
+mod jobs;
+mod maintenance;
+mod mcp;
+mod notifications;
+mod origin;
+mod recertification;
+mod scanning;
+mod separation;
+mod signing;
+mod sla;
+mod stream;
+mod validation;
+mod waivers;
+mod webhooks;
+
 use axum::{
-    extract::{Path, Query, State},
-    http::StatusCode,
-    response::Json,
-    routing::{delete, get, patch, post},
+    extract::{DefaultBodyLimit, FromRef, Path, Query, State},
+    http::{header, HeaderMap, HeaderValue, StatusCode},
+    middleware,
+    response::{IntoResponse, Json, Response},
+    routing::{delete, get, patch, post, put},
     Router,
 };
 use chrono::{DateTime, Utc};
 use serde::{Deserialize, Serialize};
 use serde_json::Value as JsonValue;
-use sqlx::{postgres::PgPoolOptions, PgPool};
+use sqlx::{postgres::PgPoolOptions, PgPool, QueryBuilder};
 use std::collections::HashMap;
-use tower_http::{cors::CorsLayer, trace::TraceLayer};
+use std::sync::Arc;
+use tower_http::{
+    cors::CorsLayer,
+    request_id::{MakeRequestUuid, PropagateRequestIdLayer, SetRequestIdLayer},
+    trace::TraceLayer,
+};
 use tracing_subscriber::{layer::SubscriberExt, util::SubscriberInitExt};
 use uuid::Uuid;
 
+use crate::auth::CurrentUser;
+use crate::config::Config;
+use crate::error::AppError;
+use crate::origin::SignedJson;
+use crate::scanning::{ScanFinding, ScanMode};
+
+// ============================================================================
+// State
+// ============================================================================
+
+#[derive(Clone)]
+struct AppState {
+    pool: PgPool,
+    config: Arc<Config>,
+    events: stream::ReportEvents,
+}
+
+impl FromRef<AppState> for PgPool {
+    fn from_ref(state: &AppState) -> Self {
+        state.pool.clone()
+    }
+}
+
+impl FromRef<AppState> for Arc<Config> {
+    fn from_ref(state: &AppState) -> Self {
+        state.config.clone()
+    }
+}
+
+impl FromRef<AppState> for stream::ReportEvents {
+    fn from_ref(state: &AppState) -> Self {
+        state.events.clone()
+    }
+}
+
 // ============================================================================
 // Models
 // ============================================================================
@@ -32,6 +104,61 @@ struct Report {
     reviewed_at: Option<DateTime<Utc>>,
     reviewed_by: Option<String>,
     review_notes: Option<String>,
+    scan_findings: sqlx::types::Json<Vec<ScanFinding>>,
+    content_hash: Option<String>,
+    version: i32,
+    assignee: Option<String>,
+    claimed_at: Option<DateTime<Utc>>,
+    claim_expires_at: Option<DateTime<Utc>>,
+    /// When an approval lapses and the server needs re-certification.
+    expires_at: Option<DateTime<Utc>>,
+    status_changed_at: DateTime<Utc>,
+    /// When the current review started; its SLA runs from here.
+    review_started_at: Option<DateTime<Utc>>,
+    /// Submitted with a valid signature from a registered skill key.
+    verified_origin: bool,
+    origin_key_id: Option<String>,
+    /// The authenticated caller who created the report, if any.
+    submitted_by: Option<String>,
+    #[sqlx(skip)]
+    #[serde(flatten, default, skip_serializing_if = "Option::is_none")]
+    sla: Option<sla::SlaStatus>,
+}
+
+/// Test fixture: a pending report submitted on 2026-01-01. Tests override
+/// the fields they care about with struct update syntax.
+#[cfg(test)]
+impl Report {
+    fn sample() -> Self {
+        let submitted_at = "2026-01-01T00:00:00Z".parse().unwrap();
+        Report {
+            id: Uuid::nil(),
+            server_name: "weather-mcp".to_string(),
+            repository_url: "https://github.com/acme/weather-mcp".to_string(),
+            developer_email: "dev@acme.test".to_string(),
+            report_data: String::new(),
+            report_json: serde_json::json!({}),
+            raw_json: None,
+            status: "pending_review".to_string(),
+            submitted_at,
+            reviewed_at: None,
+            reviewed_by: None,
+            review_notes: None,
+            scan_findings: sqlx::types::Json(Vec::new()),
+            content_hash: None,
+            version: 1,
+            assignee: None,
+            claimed_at: None,
+            claim_expires_at: None,
+            expires_at: None,
+            status_changed_at: submitted_at,
+            review_started_at: None,
+            verified_origin: false,
+            origin_key_id: None,
+            submitted_by: None,
+            sla: None,
+        }
+    }
 }
 
 #[derive(Debug, Deserialize)]
@@ -44,21 +171,49 @@ struct CreateReportRequest {
     raw_json: Option<JsonValue>,
 }
 
+/// Test fixture: a submission carrying `report_json`.
+#[cfg(test)]
+impl CreateReportRequest {
+    fn sample(report_json: JsonValue) -> Self {
+        CreateReportRequest {
+            server_name: "test-server".to_string(),
+            repository_url: "https://github.com/test/test".to_string(),
+            developer_email: "test@example.com".to_string(),
+            report_data: "# Test".to_string(),
+            report_json,
+            raw_json: None,
+        }
+    }
+}
+
+#[derive(Debug, Deserialize)]
+struct CreateQuery {
+    /// Save as a draft, editable by the developer until submitted.
+    #[serde(default)]
+    draft: bool,
+}
+
 #[derive(Debug, Deserialize)]
 struct UpdateStatusRequest {
     status: String,
-    reviewed_by: String,
+    /// The reviewer is the caller; if given, this must name them.
+    reviewed_by: Option<String>,
     review_notes: Option<String>,
+    /// Admin override of the four-eyes rule; audited.
+    break_glass_reason: Option<String>,
+    /// Required with `approved_with_conditions`.
+    conditions: Option<Vec<conditions::NewCondition>>,
 }
 
 #[derive(Debug, Deserialize)]
 struct ListQuery {
     status: Option<String>,
-}
-
-#[derive(Debug, Serialize)]
-struct ErrorResponse {
-    error: String,
+    /// An email, or `me` for the caller.
+    assignee: Option<String>,
+    /// Only reviews past (`true`) or within (`false`) their SLA.
+    sla_breached: Option<bool>,
+    /// Only reports signed (`true`) or not signed (`false`) by a skill key.
+    verified_origin: Option<bool>,
 }
 
 // Phase 3: Analytics Models
@@ -73,6 +228,12 @@ struct Issue {
     recommendation: Option<String>,
     #[serde(skip_serializing_if = "Option::is_none")]
     phase_id: Option<String>,
+    /// Tracked issue this occurrence belongs to, see `issues`.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    issue_id: Option<Uuid>,
+    /// Current lifecycle status of the tracked issue.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    status: Option<String>,
 }
 
 #[derive(Debug, Serialize)]
@@ -82,6 +243,8 @@ struct IssuesResponse {
     critical_issues: Vec<Issue>,
     warnings: Vec<Issue>,
     recommendations: Vec<Issue>,
+    /// Issues covered by an active waiver, left out of the lists above.
+    waived: Vec<waivers::WaivedIssue>,
 }
 
 #[derive(Debug, Serialize)]
@@ -91,6 +254,8 @@ struct AnalyticsSummary {
     by_status: std::collections::HashMap<String, i64>,
     by_risk_level: std::collections::HashMap<String, i64>,
     recent_submissions_24h: i64,
+    /// Reviews awaiting a decision past their SLA.
+    sla_breached: i64,
 }
 
 // ============================================================================
@@ -103,165 +268,418 @@ async fn health_check() -> &'static str {
 
 async fn create_report(
     State(pool): State<PgPool>,
-    Json(req): Json<CreateReportRequest>,
-) -> Result<(StatusCode, Json<Report>), (StatusCode, Json<ErrorResponse>)> {
-    let report = sqlx::query_as::<_, Report>(
-        r#"
-        INSERT INTO mcp_server_reports (server_name, repository_url, developer_email, report_data, report_json, raw_json)
-        VALUES ($1, $2, $3, $4, $5, $6)
-        RETURNING *
-        "#,
-    )
-    .bind(&req.server_name)
-    .bind(&req.repository_url)
-    .bind(&req.developer_email)
-    .bind(&req.report_data)
-    .bind(&req.report_json)
-    .bind(&req.raw_json)
-    .fetch_one(&pool)
-    .await
-    .map_err(|e| {
-        tracing::error!("Failed to create report: {}", e);
-        (
-            StatusCode::INTERNAL_SERVER_ERROR,
-            Json(ErrorResponse {
-                error: "Failed to create report".to_string(),
-            }),
-        )
+    State(config): State<Arc<Config>>,
+    Query(query): Query<CreateQuery>,
+    user: Option<CurrentUser>,
+    headers: HeaderMap,
+    SignedJson(mut req, body): SignedJson<CreateReportRequest>,
+) -> Result<Response, AppError> {
+    validation::validate_create_report(&req, &config.limits).inspect_err(|e| {
+        tracing::warn!("Rejected report for {}: {}", req.server_name, e.message());
     })?;
+    let origin = origin::verify(&pool, &config, &headers, &body, &req.server_name).await?;
+    if query.draft && user.is_none() {
+        return Err(AppError::Unauthorized(
+            "Drafts require a caller identity".to_string(),
+        ));
+    }
+    // The four-eyes rule keys on developer_email, so a caller may only submit
+    // under their own address.
+    if let Some(user) = &user {
+        if !user.email.eq_ignore_ascii_case(&req.developer_email) {
+            return Err(AppError::Forbidden(
+                "Reports can only be submitted by the developer named in developer_email"
+                    .to_string(),
+            ));
+        }
+    }
+    let idempotency_key = idempotency::key_from_headers(&headers)?;
+    let content_hash = idempotency::content_hash(&req);
 
-    tracing::info!(
-        "Created report: {} for {} (with structured JSON)",
-        report.id,
-        report.server_name
-    );
-    Ok((StatusCode::CREATED, Json(report)))
+    let mut tx = pool
+        .begin()
+        .await
+        .map_err(AppError::db("Failed to create report"))?;
+
+    if let Some(key) = &idempotency_key {
+        let claim =
+            idempotency::claim(&mut tx, key, &content_hash, config.idempotency_ttl_hours).await?;
+        if let idempotency::Claim::Replay(response) = claim {
+            tracing::info!("Replayed response for Idempotency-Key {}", key);
+            return Ok(response);
+        }
+    }
+
+    // A retry without a key: hand back the report that is still awaiting review.
+    let duplicate = if query.draft {
+        None
+    } else {
+        sqlx::query_as::<_, Report>(
+            r#"
+            SELECT * FROM mcp_server_reports
+            WHERE content_hash = $1 AND status IN ('pending_review', 'in_review')
+            ORDER BY submitted_at DESC
+            LIMIT 1
+            "#,
+        )
+        .bind(&content_hash)
+        .fetch_optional(&mut *tx)
+        .await
+        .map_err(AppError::db("Failed to create report"))?
+    };
+
+    let (status, report) = match duplicate {
+        Some(existing) => {
+            tracing::info!(
+                "Submission for {} duplicates pending report {}",
+                existing.server_name,
+                existing.id
+            );
+            (StatusCode::OK, existing)
+        }
+        None => {
+            let findings = scanning::scan_report(&mut req, config.scan_mode);
+            if !findings.is_empty() {
+                tracing::warn!(
+                    "Secret scan flagged {} item(s) in report for {}",
+                    findings.len(),
+                    req.server_name
+                );
+                if config.scan_mode == ScanMode::Block {
+                    return Err(AppError::Validation(scanning::describe(&findings)));
+                }
+            }
+
+            // Drafts are assigned and tracked when they are submitted.
+            let assignee = if query.draft {
+                None
+            } else {
+                assignment::pick_reviewer(&mut tx, &config, &req.developer_email).await?
+            };
+            let status = if query.draft { "draft" } else { "pending_review" };
+
+            let report = sqlx::query_as::<_, Report>(
+                r#"
+                INSERT INTO mcp_server_reports (server_name, repository_url, developer_email, report_data, report_json, raw_json, scan_findings, content_hash, assignee, status, verified_origin, origin_key_id, submitted_by)
+                VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9, $10, $11, $12, $13)
+                RETURNING *
+                "#,
+            )
+            .bind(&req.server_name)
+            .bind(&req.repository_url)
+            .bind(&req.developer_email)
+            .bind(&req.report_data)
+            .bind(&req.report_json)
+            .bind(&req.raw_json)
+            .bind(sqlx::types::Json(&findings))
+            .bind(&content_hash)
+            .bind(&assignee)
+            .bind(status)
+            .bind(origin.verified)
+            .bind(&origin.key_id)
+            .bind(user.as_ref().map(|u| &u.email))
+            .fetch_one(&mut *tx)
+            .await
+            .map_err(AppError::db("Failed to create report"))?;
+            if !query.draft {
+                issues::sync_report(&mut tx, &report).await?;
+                events::publish(&mut tx, &config, "report.created", &report).await?;
+            }
+
+            tracing::info!(
+                "Created {} report: {} for {} (with structured JSON)",
+                report.status,
+                report.id,
+                report.server_name
+            );
+            (StatusCode::CREATED, report)
+        }
+    };
+
+    if let Some(key) = &idempotency_key {
+        let body = serde_json::to_value(&report)
+            .map_err(|e| AppError::Internal(format!("Failed to serialize report: {}", e)))?;
+        idempotency::complete(&mut tx, key, status, &body, report.id).await?;
+    }
+    tx.commit()
+        .await
+        .map_err(AppError::db("Failed to create report"))?;
+
+    let mut response = report_response(status, &report);
+    if status == StatusCode::OK {
+        if let Ok(value) = HeaderValue::from_str(&report.id.to_string()) {
+            response
+                .headers_mut()
+                .insert(idempotency::DUPLICATE_HEADER, value);
+        }
+    }
+    Ok(response)
 }
 
 async fn list_reports(
     State(pool): State<PgPool>,
+    State(config): State<Arc<Config>>,
     Query(query): Query<ListQuery>,
-) -> Result<Json<Vec<Report>>, (StatusCode, Json<ErrorResponse>)> {
-    let reports = if let Some(status) = query.status {
-        sqlx::query_as::<_, Report>(
-            "SELECT * FROM mcp_server_reports WHERE status = $1 ORDER BY submitted_at DESC",
-        )
-        .bind(status)
+    user: Option<CurrentUser>,
+) -> Result<Json<Vec<Report>>, AppError> {
+    let assignee = match query.assignee.as_deref() {
+        Some("me") => Some(
+            user.as_ref()
+                .ok_or_else(|| {
+                    AppError::Unauthorized("assignee=me requires a caller identity".to_string())
+                })?
+                .email
+                .clone(),
+        ),
+        Some(email) => Some(email.trim().to_lowercase()),
+        None => None,
+    };
+
+    let mut sql = QueryBuilder::new("SELECT * FROM mcp_server_reports WHERE TRUE");
+    if let Some(status) = &query.status {
+        sql.push(" AND status = ").push_bind(status);
+    }
+    if let Some(assignee) = &assignee {
+        sql.push(" AND LOWER(assignee) = ").push_bind(assignee);
+    }
+    if query.sla_breached.is_some() {
+        sql.push(" AND status IN ('pending_review', 'in_review')");
+    }
+    if let Some(verified) = query.verified_origin {
+        sql.push(" AND verified_origin = ").push_bind(verified);
+    }
+    // Drafts are only listed for their developer.
+    sql.push(" AND (status <> 'draft' OR LOWER(developer_email) = ")
+        .push_bind(user.map(|u| u.email))
+        .push(")");
+    sql.push(" ORDER BY submitted_at DESC");
+
+    let mut reports = sql
+        .build_query_as::<Report>()
         .fetch_all(&pool)
         .await
-    } else {
-        sqlx::query_as::<_, Report>("SELECT * FROM mcp_server_reports ORDER BY submitted_at DESC")
-            .fetch_all(&pool)
-            .await
+        .map_err(AppError::db("Failed to list reports"))?;
+    sla::annotate(&config.sla, &mut reports);
+    if let Some(breached) = query.sla_breached {
+        reports.retain(|r| r.sla.as_ref().is_some_and(|s| s.sla_breached == breached));
     }
-    .map_err(|e| {
-        tracing::error!("Failed to list reports: {}", e);
-        (
-            StatusCode::INTERNAL_SERVER_ERROR,
-            Json(ErrorResponse {
-                error: "Failed to list reports".to_string(),
-            }),
-        )
-    })?;
 
     tracing::info!("Listed {} reports", reports.len());
     Ok(Json(reports))
 }
 
+/// Loads a report or fails with `NotFound`; database failures stay 500s.
+async fn fetch_report(pool: &PgPool, id: Uuid) -> Result<Report, AppError> {
+    sqlx::query_as::<_, Report>("SELECT * FROM mcp_server_reports WHERE id = $1")
+        .bind(id)
+        .fetch_optional(pool)
+        .await
+        .map_err(AppError::db("Failed to get report"))?
+        .ok_or_else(|| AppError::report_not_found(id))
+}
+
+/// Loads a report the caller may read. As in the report list, a draft only
+/// exists for its developer; anyone else gets `NotFound`.
+async fn fetch_visible_report(
+    pool: &PgPool,
+    id: Uuid,
+    user: Option<&CurrentUser>,
+) -> Result<Report, AppError> {
+    let report = fetch_report(pool, id).await?;
+    if report.status == "draft"
+        && !user.is_some_and(|u| u.email.eq_ignore_ascii_case(&report.developer_email))
+    {
+        return Err(AppError::report_not_found(id));
+    }
+    Ok(report)
+}
+
+/// JSON response for a single report, with its version as the `ETag`.
+fn report_response(status: StatusCode, report: &Report) -> Response {
+    let mut response = (status, Json(report)).into_response();
+    response
+        .headers_mut()
+        .insert(header::ETAG, concurrency::etag(report.version));
+    response
+}
+
+/// Turns a guarded write that matched no row into 404 or 412.
+async fn write_conflict(
+    pool: &PgPool,
+    id: Uuid,
+    expected: concurrency::Precondition,
+) -> AppError {
+    match fetch_report(pool, id).await {
+        Ok(current) => concurrency::stale(id, current.version, expected),
+        Err(e) => e,
+    }
+}
+
 async fn get_report(
     State(pool): State<PgPool>,
+    State(config): State<Arc<Config>>,
     Path(id): Path<Uuid>,
-) -> Result<Json<Report>, (StatusCode, Json<ErrorResponse>)> {
-    let report = sqlx::query_as::<_, Report>("SELECT * FROM mcp_server_reports WHERE id = $1")
-        .bind(id)
-        .fetch_one(&pool)
-        .await
-        .map_err(|e| {
-            tracing::error!("Failed to get report {}: {}", id, e);
-            (
-                StatusCode::NOT_FOUND,
-                Json(ErrorResponse {
-                    error: format!("Report not found: {}", id),
-                }),
-            )
-        })?;
+    user: Option<CurrentUser>,
+) -> Result<Response, AppError> {
+    let mut report = fetch_visible_report(&pool, id, user.as_ref()).await?;
+    sla::annotate(&config.sla, std::slice::from_mut(&mut report));
 
     tracing::info!("Retrieved report: {}", report.id);
-    Ok(Json(report))
+    Ok(report_response(StatusCode::OK, &report))
 }
 
 async fn update_status(
     State(pool): State<PgPool>,
+    State(config): State<Arc<Config>>,
     Path(id): Path<Uuid>,
+    user: CurrentUser,
+    headers: HeaderMap,
     Json(req): Json<UpdateStatusRequest>,
-) -> Result<Json<Report>, (StatusCode, Json<ErrorResponse>)> {
+) -> Result<Response, AppError> {
+    user.require_reviewer(&config.auth)?;
+    let expected = concurrency::if_match(&headers, config.require_if_match)?;
+
     // Validate status
-    if !["approved", "rejected", "pending_review"].contains(&req.status.as_str()) {
-        return Err((
-            StatusCode::BAD_REQUEST,
-            Json(ErrorResponse {
-                error: "Status must be 'approved', 'rejected', or 'pending_review'".to_string(),
-            }),
+    if !["approved", "approved_with_conditions", "rejected", "pending_review"]
+        .contains(&req.status.as_str())
+    {
+        return Err(AppError::BadRequest(
+            "Status must be 'approved', 'approved_with_conditions', 'rejected', or 'pending_review'"
+                .to_string(),
         ));
     }
+    let approving = req.status.starts_with("approved");
+
+    validation::validate_update_status(&req)?;
+    conditions::validate(&req.status, req.conditions.as_deref())?;
+
+    if let Some(named) = &req.reviewed_by {
+        if !named.trim().eq_ignore_ascii_case(&user.email) {
+            return Err(AppError::Validation(format!(
+                "reviewed_by must be the caller ({}); decisions cannot be recorded for someone else",
+                user.email
+            )));
+        }
+    }
+    let mut tx = pool
+        .begin()
+        .await
+        .map_err(AppError::db("Failed to start transaction"))?;
+    // Locked, so the checks below still hold when the update runs.
+    let current =
+        sqlx::query_as::<_, Report>("SELECT * FROM mcp_server_reports WHERE id = $1 FOR UPDATE")
+            .bind(id)
+            .fetch_optional(&mut *tx)
+            .await
+            .map_err(AppError::db("Failed to get report"))?
+            .ok_or_else(|| AppError::report_not_found(id))?;
+    if let Some(v) = expected.version() {
+        if v != current.version {
+            return Err(concurrency::stale(id, current.version, expected));
+        }
+    }
+    if ["draft", "withdrawn"].contains(&current.status.as_str()) {
+        return Err(AppError::Conflict(format!(
+            "Report {} is {}; it is not up for review",
+            id, current.status
+        )));
+    }
+    if current.status == "expired" {
+        return Err(AppError::Conflict(format!(
+            "Report {} has expired; submit a fresh report to re-certify {}",
+            id, current.server_name
+        )));
+    }
+    assignment::check_claim(&current, &user.email)?;
+
+    if req.status != "pending_review" {
+        let action = if approving { "approve" } else { "reject" };
+        separation::enforce(
+            &mut tx,
+            &config,
+            &current,
+            &user,
+            req.break_glass_reason.as_deref(),
+            action,
+        )
+        .await?;
+    }
+    if approving {
+        approvals::require_complete(&mut tx, &config.approvals, id).await?;
+    }
 
     let report = sqlx::query_as::<_, Report>(
         r#"
         UPDATE mcp_server_reports
-        SET status = $1, reviewed_by = $2, review_notes = $3, reviewed_at = NOW()
+        SET status = $1, reviewed_by = $2, review_notes = $3, reviewed_at = NOW(),
+            claim_expires_at = NULL, expires_at = $5, version = version + 1
         WHERE id = $4
         RETURNING *
         "#,
     )
     .bind(&req.status)
-    .bind(&req.reviewed_by)
+    .bind(&user.email)
     .bind(&req.review_notes)
     .bind(id)
-    .fetch_one(&pool)
+    .bind(approving.then(|| config.validity.expires_at(&current.report_json, Utc::now())))
+    .fetch_one(&mut *tx)
     .await
-    .map_err(|e| {
-        tracing::error!("Failed to update status for {}: {}", id, e);
-        (
-            StatusCode::NOT_FOUND,
-            Json(ErrorResponse {
-                error: format!("Report not found: {}", id),
-            }),
-        )
-    })?;
+    .map_err(AppError::db("Failed to update status"))?;
+    if !approving {
+        approvals::invalidate_votes(&mut tx, id).await?;
+    }
+    conditions::replace(&mut tx, &report, req.conditions.as_deref().unwrap_or_default()).await?;
+    if let Some(event) = webhooks::status_event(&report.status) {
+        events::publish(&mut tx, &config, event, &report).await?;
+    }
+    tx.commit()
+        .await
+        .map_err(AppError::db("Failed to commit status update"))?;
 
     tracing::info!(
         "Updated report {} status to {} by {}",
         report.id,
         report.status,
-        report.reviewed_by.as_ref().unwrap_or(&"N/A".to_string())
+        report.reviewed_by.as_deref().unwrap_or("N/A")
     );
-    Ok(Json(report))
+    Ok(report_response(StatusCode::OK, &report))
 }
 
 async fn delete_report(
     State(pool): State<PgPool>,
+    State(config): State<Arc<Config>>,
     Path(id): Path<Uuid>,
-) -> Result<StatusCode, (StatusCode, Json<ErrorResponse>)> {
-    let result = sqlx::query!("DELETE FROM mcp_server_reports WHERE id = $1", id)
-        .execute(&pool)
-        .await
-        .map_err(|e| {
-            tracing::error!("Failed to delete report {}: {}", id, e);
-            (
-                StatusCode::INTERNAL_SERVER_ERROR,
-                Json(ErrorResponse {
-                    error: "Failed to delete report".to_string(),
-                }),
-            )
-        })?;
+    user: CurrentUser,
+    headers: HeaderMap,
+) -> Result<StatusCode, AppError> {
+    let expected = concurrency::if_match(&headers, config.require_if_match)?;
+
+    // A developer may discard their own draft; anything else is admin-only.
+    let report = fetch_report(&pool, id).await?;
+    let own_draft =
+        report.status == "draft" && user.email.eq_ignore_ascii_case(&report.developer_email);
+    if !own_draft && !user.is_admin(&config.auth) {
+        if report.status == "draft" {
+            return Err(AppError::report_not_found(id));
+        }
+        return Err(AppError::Forbidden(
+            "Only admins may delete submitted reports".to_string(),
+        ));
+    }
+
+    // Guard on the version checked above so a draft submitted meanwhile
+    // is not deleted as a draft.
+    let result = sqlx::query!(
+        "DELETE FROM mcp_server_reports WHERE id = $1 AND version = $2",
+        id,
+        expected.version().unwrap_or(report.version)
+    )
+    .execute(&pool)
+    .await
+    .map_err(AppError::db("Failed to delete report"))?;
 
     if result.rows_affected() == 0 {
-        return Err((
-            StatusCode::NOT_FOUND,
-            Json(ErrorResponse {
-                error: format!("Report not found: {}", id),
-            }),
-        ));
+        return Err(write_conflict(&pool, id, expected).await);
     }
 
     tracing::info!("Deleted report {}", id);
@@ -272,6 +690,19 @@ async fn delete_report(
 // Phase 3: Analytics Handlers
 // ============================================================================
 
+/// Issue lists in `report_json`, in the order the issues endpoint returns them.
+const ISSUE_FIELDS: [&str; 3] = ["critical_issues", "warnings", "recommendations"];
+
+/// Looks up a single issue by `<list>/<index>`, e.g. `critical_issues/0`.
+fn find_issue(json: &JsonValue, reference: &str) -> Option<Issue> {
+    let (field, index) = reference.split_once('/')?;
+    if !ISSUE_FIELDS.contains(&field) {
+        return None;
+    }
+    let index: usize = index.parse().ok()?;
+    extract_issues(json, field).into_iter().nth(index)
+}
+
 /// Helper function to extract issues from JSON arrays
 fn extract_issues(json: &JsonValue, field: &str) -> Vec<Issue> {
     json.get(field)
@@ -285,6 +716,8 @@ fn extract_issues(json: &JsonValue, field: &str) -> Vec<Issue> {
                         impact: item.get("impact").and_then(|v| v.as_str()).map(String::from),
                         recommendation: item.get("recommendation").and_then(|v| v.as_str()).map(String::from),
                         phase_id: item.get("phase_id").and_then(|v| v.as_str()).map(String::from),
+                        issue_id: None,
+                        status: None,
                     })
                 })
                 .collect()
@@ -294,7 +727,7 @@ fn extract_issues(json: &JsonValue, field: &str) -> Vec<Issue> {
 
 async fn get_risk_distribution(
     State(pool): State<PgPool>,
-) -> Result<Json<HashMap<String, i64>>, (StatusCode, Json<ErrorResponse>)> {
+) -> Result<Json<HashMap<String, i64>>, AppError> {
     let results = sqlx::query!(
         r#"
         SELECT
@@ -307,15 +740,7 @@ async fn get_risk_distribution(
     )
     .fetch_all(&pool)
     .await
-    .map_err(|e| {
-        tracing::error!("Failed to get risk distribution: {}", e);
-        (
-            StatusCode::INTERNAL_SERVER_ERROR,
-            Json(ErrorResponse {
-                error: "Failed to query risk distribution".to_string(),
-            }),
-        )
-    })?;
+    .map_err(AppError::db("Failed to query risk distribution"))?;
 
     let mut distribution = HashMap::new();
     for row in results {
@@ -331,49 +756,53 @@ async fn get_risk_distribution(
 async fn get_report_issues(
     State(pool): State<PgPool>,
     Path(id): Path<Uuid>,
-) -> Result<Json<IssuesResponse>, (StatusCode, Json<ErrorResponse>)> {
-    let report = sqlx::query_as::<_, Report>(
-        "SELECT * FROM mcp_server_reports WHERE id = $1"
-    )
-    .bind(id)
-    .fetch_one(&pool)
-    .await
-    .map_err(|_| {
-        (
-            StatusCode::NOT_FOUND,
-            Json(ErrorResponse {
-                error: format!("Report not found: {}", id),
-            }),
-        )
-    })?;
-
-    let json_data = &report.report_json;
-
-    // Extract issues from JSON
-    let critical = extract_issues(&json_data, "critical_issues");
-    let warnings = extract_issues(&json_data, "warnings");
-    let recommendations = extract_issues(&json_data, "recommendations");
+    user: Option<CurrentUser>,
+) -> Result<Json<IssuesResponse>, AppError> {
+    let report = fetch_visible_report(&pool, id, user.as_ref()).await?;
+    let response = issues_response(&pool, &report).await?;
 
     tracing::info!("Retrieved {} critical issues, {} warnings, {} recommendations for report {}",
-        critical.len(), warnings.len(), recommendations.len(), id);
+        response.critical_issues.len(), response.warnings.len(), response.recommendations.len(), id);
 
-    Ok(Json(IssuesResponse {
+    Ok(Json(response))
+}
+
+/// The issues recorded at submission, setting aside those with an active waiver.
+async fn issues_response(pool: &PgPool, report: &Report) -> Result<IssuesResponse, AppError> {
+    let mut recorded = issues::for_report(pool, report.id).await?;
+    let active_waivers = waivers::active_for_report(pool, report).await?;
+    let mut waived = Vec::new();
+    let mut extract = |field: &str| {
+        let found = recorded.remove(field).unwrap_or_default();
+        let (open, covered) = waivers::apply(&active_waivers, found);
+        waived.extend(covered);
+        open
+    };
+    let critical = extract("critical_issues");
+    let warnings = extract("warnings");
+    let recommendations = extract("recommendations");
+
+    Ok(IssuesResponse {
         report_id: report.id,
-        server_name: report.server_name,
+        server_name: report.server_name.clone(),
         critical_issues: critical,
         warnings,
         recommendations,
-    }))
+        waived,
+    })
 }
 
 async fn get_analytics_summary(
     State(pool): State<PgPool>,
-) -> Result<Json<AnalyticsSummary>, (StatusCode, Json<ErrorResponse>)> {
+    State(config): State<Arc<Config>>,
+) -> Result<Json<AnalyticsSummary>, AppError> {
+    let query_failed = || AppError::db("Failed to query analytics summary");
+
     // Total reports
     let total: i64 = sqlx::query_scalar("SELECT COUNT(*) FROM mcp_server_reports")
         .fetch_one(&pool)
         .await
-        .unwrap_or(0);
+        .map_err(query_failed())?;
 
     // With structured data (all reports now have JSON)
     let with_json = total;
@@ -384,7 +813,7 @@ async fn get_analytics_summary(
     )
     .fetch_all(&pool)
     .await
-    .unwrap_or_default();
+    .map_err(query_failed())?;
 
     let mut by_status = HashMap::new();
     for row in status_results {
@@ -403,7 +832,7 @@ async fn get_analytics_summary(
     )
     .fetch_all(&pool)
     .await
-    .unwrap_or_default();
+    .map_err(query_failed())?;
 
     let mut by_risk_level = HashMap::new();
     for row in risk_results {
@@ -418,7 +847,9 @@ async fn get_analytics_summary(
     )
     .fetch_one(&pool)
     .await
-    .unwrap_or(0);
+    .map_err(query_failed())?;
+
+    let sla_breached = sla::count_breached(&pool, &config.sla).await?;
 
     tracing::info!("Retrieved analytics summary: {} total reports, {} with JSON", total, with_json);
 
@@ -428,6 +859,7 @@ async fn get_analytics_summary(
         by_status,
         by_risk_level,
         recent_submissions_24h: recent,
+        sla_breached,
     }))
 }
 
@@ -466,21 +898,132 @@ async fn main() {
 
     tracing::info!("Migrations complete");
 
+    match issues::backfill(&pool).await {
+        Ok(0) => {}
+        Ok(n) => tracing::info!("Recorded issues for {} existing reports", n),
+        Err(e) => panic!("Failed to backfill issues: {}", e.message()),
+    }
+
+    let config = Config::from_env();
+    match recertification::backfill(&pool, &config.validity).await {
+        Ok(0) => {}
+        Ok(n) => tracing::info!("Set expiry on {} existing approvals", n),
+        Err(e) => panic!("Failed to backfill approval expiry: {}", e.message()),
+    }
+    let config = Arc::new(config);
+    jobs::register_schedules(&pool, &config.jobs)
+        .await
+        .expect("Failed to register job schedules");
+
+    // `golive-api worker` runs background jobs only, without the HTTP server.
+    if std::env::args().nth(1).as_deref() == Some("worker") {
+        tracing::info!("Running as job worker ({} concurrent)", config.jobs.concurrency);
+        jobs::spawn(pool, config);
+        tokio::signal::ctrl_c()
+            .await
+            .expect("Failed to listen for shutdown signal");
+        return;
+    }
+    if config.jobs.worker == jobs::WorkerMode::Embedded {
+        jobs::spawn(pool.clone(), config.clone());
+    }
+    let events = stream::ReportEvents::default();
+    events.spawn_listener(pool.clone());
+    let state = AppState {
+        pool,
+        config,
+        events,
+    };
+
     // Build router
     let app = Router::new()
         .route("/healthz", get(health_check))
+        .route("/mcp", post(mcp::post_mcp))
         .route("/api/v1/reports", post(create_report))
         .route("/api/v1/reports", get(list_reports))
         .route("/api/v1/reports/{id}", get(get_report))
+        .route("/api/v1/reports/{id}", put(drafts::update_draft))
         .route("/api/v1/reports/{id}", delete(delete_report))
+        .route("/api/v1/events", get(stream::stream_events))
+        .route("/api/v1/reports/{id}/submit", post(drafts::submit_draft))
+        .route("/api/v1/reports/{id}/withdraw", post(drafts::withdraw_report))
         .route("/api/v1/reports/{id}/status", patch(update_status))
+        .route("/api/v1/reports/{id}/claim", post(assignment::claim_report))
+        .route("/api/v1/reports/{id}/release", post(assignment::release_report))
+        .route(
+            "/api/v1/reports/{id}/approvals",
+            get(approvals::get_approvals).post(approvals::vote),
+        )
+        .route("/api/v1/reports/{id}/attestation", get(attestations::get_attestation))
+        .route("/api/v1/reports/{id}/conditions", get(conditions::list_conditions))
+        .route(
+            "/api/v1/reports/{id}/conditions/{condition_id}/fulfil",
+            post(conditions::fulfil),
+        )
+        .route(
+            "/api/v1/reports/{id}/comments",
+            get(comments::list_threads).post(comments::create_thread),
+        )
+        .route("/api/v1/reports/{id}/comments/{comment_id}/replies", post(comments::reply))
+        .route("/api/v1/reports/{id}/comments/{comment_id}/resolve", post(comments::resolve))
+        .route("/api/v1/reports/{id}/comments/{comment_id}/unresolve", post(comments::unresolve))
         // Phase 3: Analytics endpoints
         .route("/api/v1/reports/analytics/risk-distribution", get(get_risk_distribution))
         .route("/api/v1/reports/analytics/summary", get(get_analytics_summary))
         .route("/api/v1/reports/{id}/issues", get(get_report_issues))
+        .route(
+            "/api/v1/reports/{id}/waivers",
+            get(waivers::list_report_waivers).post(waivers::create_waiver),
+        )
+        .route("/api/v1/issues", get(issues::list_issues))
+        .route("/api/v1/issues/{issue_id}", get(issues::get_issue))
+        .route(
+            "/api/v1/servers/recertification-due",
+            get(recertification::recertification_due),
+        )
+        .route("/api/v1/servers/allowlist", get(allowlist::get_allowlist))
+        .route("/api/v1/signing-key", get(signing::get_signing_key))
+        .route("/api/v1/waivers", get(waivers::list_waivers))
+        .route("/api/v1/waivers/{waiver_id}", delete(waivers::revoke_waiver))
+        .route("/api/v1/admin/break-glass", get(separation::list_break_glass))
+        .route(
+            "/api/v1/admin/skill-keys",
+            get(origin::list_keys).post(origin::register_key),
+        )
+        .route("/api/v1/admin/skill-keys/{key_id}", delete(origin::revoke_key))
+        .route("/api/v1/admission/validate", post(admission::validate))
+        .route(
+            "/api/v1/admin/admission-decisions",
+            get(admission::list_decisions),
+        )
+        .route(
+            "/api/v1/webhooks",
+            get(webhooks::list_subscriptions).post(webhooks::create_subscription),
+        )
+        .route(
+            "/api/v1/webhooks/{id}",
+            get(webhooks::get_subscription).delete(webhooks::delete_subscription),
+        )
+        .route("/api/v1/webhooks/{id}/deliveries", get(webhooks::list_deliveries))
+        .route(
+            "/api/v1/webhooks/{id}/deliveries/{delivery_id}/redeliver",
+            post(webhooks::redeliver),
+        )
+        .route(
+            "/api/v1/me/notification-preferences",
+            get(notifications::get_preferences).put(notifications::put_preferences),
+        )
+        .route("/api/v1/admin/jobs", get(jobs::list_jobs))
+        .route("/api/v1/admin/jobs/{job_id}", get(jobs::get_job))
+        .route("/api/v1/admin/jobs/{job_id}/retry", post(jobs::retry_job))
+        .route("/api/v1/admin/job-schedules", get(jobs::list_schedules))
+        .layer(middleware::from_fn_with_state(state.clone(), error::render_errors))
+        .layer(DefaultBodyLimit::max(state.config.limits.max_body_bytes))
         .layer(CorsLayer::permissive())
         .layer(TraceLayer::new_for_http())
-        .with_state(pool);
+        .layer(PropagateRequestIdLayer::x_request_id())
+        .layer(SetRequestIdLayer::x_request_id(MakeRequestUuid))
+        .with_state(state);
 
     // Start server
     let port = std::env::var("PORT").unwrap_or_else(|_| "8080".to_string());
@@ -523,6 +1066,8 @@ mod tests {
         let json = r#"{"status":"approved","reviewed_by":"platform@test.com"}"#;
         let req: UpdateStatusRequest = serde_json::from_str(json).unwrap();
         assert_eq!(req.status, "approved");
+        let req: UpdateStatusRequest = serde_json::from_str(r#"{"status":"rejected"}"#).unwrap();
+        assert!(req.reviewed_by.is_none());
     }
 
     // New tests for JSON support
@@ -550,22 +1095,13 @@ mod tests {
     #[test]
     fn test_report_serialization_with_json() {
         let report = Report {
-            id: Uuid::new_v4(),
-            server_name: "test-server".to_string(),
-            repository_url: "https://github.com/test/test".to_string(),
-            developer_email: "test@example.com".to_string(),
-            report_data: "# Test".to_string(),
             report_json: serde_json::json!({
                 "report_version": "1.0",
                 "server_info": {
                     "server_name": "test-server"
                 }
             }),
-            status: "pending_review".to_string(),
-            submitted_at: Utc::now(),
-            reviewed_at: None,
-            reviewed_by: None,
-            review_notes: None,
+            ..Report::sample()
         };
 
         let json_str = serde_json::to_string(&report).unwrap();
@@ -621,9 +1157,12 @@ mod tests {
                 impact: Some("High".to_string()),
                 recommendation: None,
                 phase_id: Some("P1".to_string()),
+                issue_id: None,
+                status: None,
             }],
             warnings: vec![],
             recommendations: vec![],
+            waived: vec![],
         };
 
         let json_str = serde_json::to_string(&response).unwrap();
@@ -646,11 +1185,13 @@ mod tests {
             by_status,
             by_risk_level: by_risk,
             recent_submissions_24h: 2,
+            sla_breached: 1,
         };
 
         let json_str = serde_json::to_string(&summary).unwrap();
         assert!(json_str.contains("total_reports"));
         assert!(json_str.contains("by_status"));
         assert!(json_str.contains("by_risk_level"));
+        assert!(json_str.contains("\"sla_breached\":1"));
     }
 }
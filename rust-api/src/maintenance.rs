@@ -0,0 +1,80 @@
+use sqlx::PgPool;
+
+use crate::config::Config;
+use crate::{
+    admission, assignment, conditions, idempotency, issues, jobs, notifications, recertification,
+    sla, stream,
+};
+
+// ============================================================================
+// Periodic Maintenance
+// ============================================================================
+
+/// One pass of the periodic sweeps, run by the `maintenance` job. Each sweep
+/// is idempotent and runs even if an earlier one failed; failures are
+/// reported together so the job is retried.
+pub async fn run_once(pool: &PgPool, config: &Config) -> Result<(), String> {
+    let mut failures = Vec::new();
+
+    match assignment::release_expired_claims(pool).await {
+        Ok(0) => {}
+        Ok(n) => tracing::info!("Released {} expired review claim(s)", n),
+        Err(e) => failures.push(format!("releasing expired claims: {}", e)),
+    }
+    match conditions::sweep_overdue(pool, config).await {
+        Ok(0) => {}
+        Ok(n) => tracing::info!("Handled {} overdue approval condition(s)", n),
+        Err(e) => failures.push(format!("processing overdue conditions: {}", e.message())),
+    }
+    match recertification::expire_approvals(pool, config).await {
+        Ok(0) => {}
+        Ok(n) => tracing::info!("Expired {} approval(s) due for re-certification", n),
+        Err(e) => failures.push(format!("expiring approvals: {}", e.message())),
+    }
+    match notifications::send_reminders(pool, config).await {
+        Ok(0) => {}
+        Ok(n) => tracing::info!("Queued {} reminder email(s)", n),
+        Err(e) => failures.push(format!("queueing reminders: {}", e.message())),
+    }
+    match sla::send_reminders(pool, config).await {
+        Ok(0) => {}
+        Ok(n) => tracing::info!("Queued {} review SLA reminder(s)", n),
+        Err(e) => failures.push(format!("queueing review SLA reminders: {}", e.message())),
+    }
+    // Waivers lapse silently; bring their issues back to open.
+    if let Err(e) = issues::refresh_waived(pool, None).await {
+        failures.push(format!("refreshing waived issues: {}", e.message()));
+    }
+    match jobs::purge_finished(pool, config.jobs.retention_days).await {
+        Ok(0) => {}
+        Ok(n) => tracing::info!("Purged {} finished job(s)", n),
+        Err(e) => failures.push(format!("purging finished jobs: {}", e)),
+    }
+
+    match idempotency::purge_expired(pool, config.idempotency_ttl_hours).await {
+        Ok(0) => {}
+        Ok(n) => tracing::info!("Purged {} expired idempotency key(s)", n),
+        Err(e) => failures.push(format!("purging idempotency keys: {}", e)),
+    }
+
+    match stream::purge_events(pool, config.event_retention_days).await {
+        Ok(0) => {}
+        Ok(n) => tracing::info!("Purged {} old report event(s)", n),
+        Err(e) => failures.push(format!("purging report events: {}", e)),
+    }
+
+    match admission::purge_decisions(pool, config.admission.log_retention_days).await {
+        Ok(0) => {}
+        Ok(n) => tracing::info!("Purged {} old admission decision(s)", n),
+        Err(e) => failures.push(format!("purging admission decisions: {}", e)),
+    }
+
+    if failures.is_empty() {
+        Ok(())
+    } else {
+        for failure in &failures {
+            tracing::error!("Maintenance failed {}", failure);
+        }
+        Err(failures.join("; "))
+    }
+}
@@ -0,0 +1,806 @@
+use axum::{
+    body::{to_bytes, Bytes},
+    extract::{Path, Query, State},
+    http::{header, HeaderMap, HeaderValue, StatusCode},
+    response::{IntoResponse, Json, Response},
+};
+use serde::Deserialize;
+use serde_json::{json, Value as JsonValue};
+use sqlx::PgPool;
+use std::fmt::Write as _;
+use std::sync::Arc;
+use uuid::Uuid;
+
+use crate::auth::CurrentUser;
+use crate::config::Config;
+use crate::error::AppError;
+use crate::origin::SignedJson;
+use crate::signing::KEY_ID_HEADER;
+use crate::webhooks::SIGNATURE_HEADER;
+use crate::{CreateQuery, CreateReportRequest, ListQuery, Report};
+
+// ============================================================================
+// MCP Endpoint (Streamable HTTP)
+// ============================================================================
+
+/// Protocol revisions spoken, newest first.
+pub const PROTOCOL_VERSIONS: [&str; 2] = ["2025-06-18", "2025-03-26"];
+pub const PROTOCOL_VERSION_HEADER: &str = "mcp-protocol-version";
+
+// JSON-RPC 2.0 error codes
+const PARSE_ERROR: i64 = -32700;
+const INVALID_REQUEST: i64 = -32600;
+const METHOD_NOT_FOUND: i64 = -32601;
+const INVALID_PARAMS: i64 = -32602;
+
+/// Report fields `get_report_status` returns.
+const STATUS_FIELDS: [&str; 14] = [
+    "id",
+    "server_name",
+    "status",
+    "version",
+    "submitted_at",
+    "assignee",
+    "reviewed_at",
+    "reviewed_by",
+    "review_notes",
+    "expires_at",
+    "verified_origin",
+    "time_in_state_secs",
+    "sla_due_at",
+    "sla_breached",
+];
+
+#[derive(Debug, Clone, PartialEq)]
+struct RpcError {
+    code: i64,
+    message: String,
+}
+
+impl RpcError {
+    fn new(code: i64, message: impl Into<String>) -> Self {
+        Self {
+            code,
+            message: message.into(),
+        }
+    }
+
+    fn invalid_params(e: impl std::fmt::Display) -> Self {
+        Self::new(INVALID_PARAMS, format!("Invalid arguments: {}", e))
+    }
+}
+
+/// An incoming JSON-RPC message, classified.
+#[derive(Debug, PartialEq)]
+enum Message {
+    Request {
+        id: JsonValue,
+        method: String,
+        params: JsonValue,
+    },
+    /// Notifications and responses to our (nonexistent) requests need no answer.
+    NoReply,
+    Invalid {
+        id: JsonValue,
+        error: RpcError,
+    },
+}
+
+fn classify(message: JsonValue) -> Message {
+    let id = message.get("id").cloned().unwrap_or(JsonValue::Null);
+    let invalid = |id: JsonValue, message: &str| Message::Invalid {
+        id,
+        error: RpcError::new(INVALID_REQUEST, message),
+    };
+    if message.get("jsonrpc").and_then(JsonValue::as_str) != Some("2.0") {
+        return invalid(id, "Not a JSON-RPC 2.0 message");
+    }
+    let Some(method) = message.get("method") else {
+        return if message.get("result").is_some() || message.get("error").is_some() {
+            Message::NoReply
+        } else {
+            invalid(id, "Message has neither a method nor a result")
+        };
+    };
+    let Some(method) = method.as_str() else {
+        return invalid(id, "method must be a string");
+    };
+    if !(id.is_string() || id.is_number()) {
+        return if message.get("id").is_none() {
+            Message::NoReply
+        } else {
+            invalid(JsonValue::Null, "id must be a string or a number")
+        };
+    }
+    Message::Request {
+        id,
+        method: method.to_string(),
+        params: message.get("params").cloned().unwrap_or(json!({})),
+    }
+}
+
+fn reply(id: JsonValue, outcome: Result<JsonValue, RpcError>) -> JsonValue {
+    match outcome {
+        Ok(result) => json!({ "jsonrpc": "2.0", "id": id, "result": result }),
+        Err(e) => json!({
+            "jsonrpc": "2.0",
+            "id": id,
+            "error": { "code": e.code, "message": e.message },
+        }),
+    }
+}
+
+/// The client's revision if we speak it, our newest otherwise.
+fn negotiate(requested: Option<&str>) -> &'static str {
+    PROTOCOL_VERSIONS
+        .iter()
+        .find(|v| Some(**v) == requested)
+        .unwrap_or(&PROTOCOL_VERSIONS[0])
+}
+
+fn initialize(params: &JsonValue) -> JsonValue {
+    json!({
+        "protocolVersion": negotiate(params.get("protocolVersion").and_then(JsonValue::as_str)),
+        "capabilities": { "tools": { "listChanged": false } },
+        "serverInfo": { "name": "golive-api", "version": env!("CARGO_PKG_VERSION") },
+        "instructions": "Submit MCP server go-live reports for platform review and follow their status.",
+    })
+}
+
+fn tools() -> JsonValue {
+    let report_id = json!({
+        "type": "object",
+        "properties": { "report_id": { "type": "string", "format": "uuid" } },
+        "required": ["report_id"],
+    });
+    json!([
+        {
+            "name": "submit_report",
+            "title": "Submit go-live report",
+            "description": "Submit an MCP server go-live report to the platform team for review, \
+                after completing the go-live checklist. The report is pending_review until a \
+                reviewer approves or rejects it.",
+            "inputSchema": {
+                "type": "object",
+                "properties": {
+                    "server_name": { "type": "string", "description": "Name of the MCP server, e.g. mcp-servicenow" },
+                    "repository_url": { "type": "string", "description": "Full repository URL" },
+                    "developer_email": { "type": "string", "description": "Email address of the developer" },
+                    "report_markdown": { "type": "string", "description": "Complete go-live report in markdown" },
+                    "report_json": {
+                        "type": ["object", "string"],
+                        "description": "Structured report data, as an object or a JSON string",
+                    },
+                    "raw_json": { "type": "object", "description": "Unprocessed checklist output, kept for audit" },
+                    "idempotency_key": {
+                        "type": "string",
+                        "description": "Reuse on retries so a submission is only created once",
+                    },
+                },
+                "required": ["server_name", "repository_url", "developer_email", "report_markdown", "report_json"],
+            },
+        },
+        {
+            "name": "list_servers",
+            "title": "List submitted servers",
+            "description": "List submitted MCP servers and their review status.",
+            "inputSchema": {
+                "type": "object",
+                "properties": {
+                    "status": {
+                        "type": "string",
+                        "description": "pending_review, in_review, approved, approved_with_conditions, rejected, expired, or all",
+                    },
+                },
+            },
+            "annotations": { "readOnlyHint": true },
+        },
+        {
+            "name": "get_report_status",
+            "title": "Get report status",
+            "description": "Review status of one report: reviewer, notes, SLA and approval expiry.",
+            "inputSchema": report_id,
+            "annotations": { "readOnlyHint": true },
+        },
+        {
+            "name": "get_report_issues",
+            "title": "Get report issues",
+            "description": "Critical issues, warnings and recommendations found in one report.",
+            "inputSchema": report_id,
+            "annotations": { "readOnlyHint": true },
+        },
+    ])
+}
+
+#[derive(Debug, Deserialize)]
+struct SubmitArgs {
+    server_name: String,
+    repository_url: String,
+    developer_email: String,
+    report_markdown: String,
+    report_json: JsonValue,
+    raw_json: Option<JsonValue>,
+    idempotency_key: Option<String>,
+}
+
+impl SubmitArgs {
+    /// `report_json` may arrive as a JSON string, as the Python tool took it.
+    fn into_request(self) -> Result<(CreateReportRequest, Option<String>), RpcError> {
+        let report_json = match self.report_json {
+            JsonValue::String(raw) => serde_json::from_str(&raw)
+                .map_err(|e| RpcError::invalid_params(format!("report_json: {}", e)))?,
+            value => value,
+        };
+        let request = CreateReportRequest {
+            server_name: self.server_name,
+            repository_url: self.repository_url,
+            developer_email: self.developer_email,
+            report_data: self.report_markdown,
+            report_json,
+            raw_json: self.raw_json,
+        };
+        Ok((request, self.idempotency_key))
+    }
+}
+
+#[derive(Debug, Deserialize)]
+struct ListArgs {
+    status: Option<String>,
+}
+
+#[derive(Debug, Deserialize)]
+struct ReportArgs {
+    report_id: Uuid,
+}
+
+fn arguments<T: serde::de::DeserializeOwned>(params: &JsonValue) -> Result<T, RpcError> {
+    let arguments = params.get("arguments").cloned().unwrap_or(json!({}));
+    serde_json::from_value(arguments).map_err(RpcError::invalid_params)
+}
+
+/// A `tools/call` result: markdown for the model, JSON for programs.
+fn tool_result(text: String, structured: JsonValue) -> JsonValue {
+    json!({
+        "content": [{ "type": "text", "text": text }],
+        "structuredContent": structured,
+        "isError": false,
+    })
+}
+
+/// API errors are tool errors the model can act on, not protocol errors.
+fn tool_error(e: &AppError) -> JsonValue {
+    json!({
+        "content": [{ "type": "text", "text": format!("Error ({}): {}", e.code(), e.message()) }],
+        "structuredContent": { "error": e.message(), "code": e.code() },
+        "isError": true,
+    })
+}
+
+/// The JSON body of a handler's response.
+async fn body_json(response: Response) -> Result<JsonValue, AppError> {
+    let bytes = to_bytes(response.into_body(), usize::MAX)
+        .await
+        .map_err(|e| AppError::Internal(format!("Failed to read response: {}", e)))?;
+    serde_json::from_slice(&bytes)
+        .map_err(|e| AppError::Internal(format!("Failed to decode response: {}", e)))
+}
+
+fn text(value: &JsonValue, key: &str) -> String {
+    match value.get(key) {
+        Some(JsonValue::String(s)) => s.clone(),
+        Some(JsonValue::Null) | None => "—".to_string(),
+        Some(other) => other.to_string(),
+    }
+}
+
+fn submitted_text(report: &JsonValue, duplicate: bool) -> String {
+    let mut out = if duplicate {
+        "# Report Already Pending\n\nAn identical submission is still awaiting review.\n\n"
+            .to_string()
+    } else {
+        "# Report Submitted\n\n".to_string()
+    };
+    for (label, key) in [
+        ("Report ID", "id"),
+        ("Server Name", "server_name"),
+        ("Repository", "repository_url"),
+        ("Status", "status"),
+        ("Submitted", "submitted_at"),
+        ("Assignee", "assignee"),
+        ("Verified origin", "verified_origin"),
+    ] {
+        let _ = writeln!(out, "**{}:** {}", label, text(report, key));
+    }
+    out.push_str("\nUse `get_report_status` to follow the review.\n");
+    out
+}
+
+fn list_text(status: &str, reports: &[JsonValue]) -> String {
+    if reports.is_empty() {
+        return format!(
+            "# No Reports Found\n\nNo MCP servers with status **{}**.\n",
+            status
+        );
+    }
+    let mut out = format!(
+        "# MCP Servers - Status: {}\n\nFound **{}** report(s)\n\n\
+         | Server Name | Repository | Status | Submitted | Reviewed By | Report ID |\n\
+         |-------------|------------|--------|-----------|-------------|-----------|\n",
+        status,
+        reports.len()
+    );
+    for report in reports {
+        let submitted = text(report, "submitted_at");
+        let _ = writeln!(
+            out,
+            "| {} | {} | {} | {} | {} | {} |",
+            text(report, "server_name"),
+            text(report, "repository_url"),
+            text(report, "status"),
+            submitted.split('T').next().unwrap_or_default(),
+            text(report, "reviewed_by"),
+            text(report, "id"),
+        );
+    }
+    out
+}
+
+fn status_text(status: &JsonValue) -> String {
+    let mut out = format!(
+        "# {}: {}\n\n",
+        text(status, "server_name"),
+        text(status, "status")
+    );
+    let details = STATUS_FIELDS
+        .iter()
+        .filter(|key| !["server_name", "status"].contains(key))
+        .filter(|key| status.get(**key).is_some_and(|v| !v.is_null()));
+    for key in details {
+        let _ = writeln!(out, "- **{}:** {}", key, text(status, key));
+    }
+    out
+}
+
+fn issues_text(issues: &JsonValue) -> String {
+    let mut out = format!("# Issues in {}\n", text(issues, "server_name"));
+    for (title, key) in [
+        ("Critical Issues", "critical_issues"),
+        ("Warnings", "warnings"),
+        ("Recommendations", "recommendations"),
+    ] {
+        let listed = issues[key]
+            .as_array()
+            .map(Vec::as_slice)
+            .unwrap_or_default();
+        let _ = writeln!(out, "\n## {} ({})", title, listed.len());
+        if !listed.is_empty() {
+            out.push('\n');
+        }
+        for issue in listed {
+            let _ = write!(out, "- {}", text(issue, "description"));
+            if let Some(status) = issue.get("status").and_then(JsonValue::as_str) {
+                let _ = write!(out, " [{}]", status);
+            }
+            out.push('\n');
+        }
+    }
+    let waived = issues["waived"]
+        .as_array()
+        .map(Vec::len)
+        .unwrap_or_default();
+    if waived > 0 {
+        let _ = writeln!(out, "\n{} issue(s) are covered by a waiver.", waived);
+    }
+    out
+}
+
+/// What a tool call runs with: the caller and the raw MCP request, whose
+/// signature headers carry over to `submit_report`.
+struct Context {
+    pool: PgPool,
+    config: Arc<Config>,
+    user: Option<CurrentUser>,
+    headers: HeaderMap,
+    body: Bytes,
+}
+
+async fn call_tool(ctx: &Context, params: &JsonValue) -> Result<JsonValue, RpcError> {
+    let name = params
+        .get("name")
+        .and_then(JsonValue::as_str)
+        .ok_or_else(|| RpcError::invalid_params("name is required"))?;
+    let outcome = match name {
+        "submit_report" => submit_report(ctx, arguments(params)?).await,
+        "list_servers" => list_servers(ctx, arguments(params)?).await,
+        "get_report_status" => get_report_status(ctx, arguments(params)?).await,
+        "get_report_issues" => get_report_issues(ctx, arguments(params)?).await,
+        other => {
+            return Err(RpcError::new(
+                INVALID_PARAMS,
+                format!("Unknown tool: {}", other),
+            ))
+        }
+    };
+    Ok(outcome.unwrap_or_else(|e| {
+        tracing::info!("MCP tool {} failed: {}", name, e);
+        tool_error(&e)
+    }))
+}
+
+async fn submit_report(ctx: &Context, args: SubmitArgs) -> Result<JsonValue, AppError> {
+    let (request, idempotency_key) = args
+        .into_request()
+        .map_err(|e| AppError::Validation(e.message))?;
+    let mut headers = HeaderMap::new();
+    for name in [KEY_ID_HEADER, SIGNATURE_HEADER] {
+        if let Some(value) = ctx.headers.get(name) {
+            headers.insert(name, value.clone());
+        }
+    }
+    if let Some(key) = idempotency_key {
+        let value = HeaderValue::from_str(&key)
+            .map_err(|_| AppError::BadRequest("idempotency_key must be visible ASCII".into()))?;
+        headers.insert(crate::idempotency::IDEMPOTENCY_KEY_HEADER, value);
+    }
+
+    let response = crate::create_report(
+        State(ctx.pool.clone()),
+        State(ctx.config.clone()),
+        Query(CreateQuery { draft: false }),
+        ctx.user.clone(),
+        headers,
+        SignedJson(request, ctx.body.clone()),
+    )
+    .await?;
+    let duplicate = response.status() == StatusCode::OK;
+    let report = body_json(response).await?;
+    Ok(tool_result(submitted_text(&report, duplicate), report))
+}
+
+async fn list_servers(ctx: &Context, args: ListArgs) -> Result<JsonValue, AppError> {
+    let status = args.status.filter(|s| s != "all");
+    let query = ListQuery {
+        status: status.clone(),
+        assignee: None,
+        sla_breached: None,
+        verified_origin: None,
+    };
+    let Json(reports) = crate::list_reports(
+        State(ctx.pool.clone()),
+        State(ctx.config.clone()),
+        Query(query),
+        ctx.user.clone(),
+    )
+    .await?;
+    let summaries: Vec<JsonValue> = reports.iter().map(summary).collect();
+    Ok(tool_result(
+        list_text(status.as_deref().unwrap_or("all"), &summaries),
+        json!({ "reports": summaries }),
+    ))
+}
+
+/// A report without its content, for listings.
+fn summary(report: &Report) -> JsonValue {
+    json!({
+        "id": report.id,
+        "server_name": report.server_name,
+        "repository_url": report.repository_url,
+        "status": report.status,
+        "submitted_at": report.submitted_at,
+        "reviewed_by": report.reviewed_by,
+        "verified_origin": report.verified_origin,
+    })
+}
+
+async fn get_report_status(ctx: &Context, args: ReportArgs) -> Result<JsonValue, AppError> {
+    let response = crate::get_report(
+        State(ctx.pool.clone()),
+        State(ctx.config.clone()),
+        Path(args.report_id),
+        ctx.user.clone(),
+    )
+    .await?;
+    let report = body_json(response).await?;
+    let status: serde_json::Map<String, JsonValue> = STATUS_FIELDS
+        .iter()
+        .filter_map(|key| report.get(*key).map(|v| (key.to_string(), v.clone())))
+        .collect();
+    let status = JsonValue::Object(status);
+    Ok(tool_result(status_text(&status), status))
+}
+
+async fn get_report_issues(ctx: &Context, args: ReportArgs) -> Result<JsonValue, AppError> {
+    let Json(issues) = crate::get_report_issues(
+        State(ctx.pool.clone()),
+        Path(args.report_id),
+        ctx.user.clone(),
+    )
+    .await?;
+    let issues = serde_json::to_value(&issues)
+        .map_err(|e| AppError::Internal(format!("Failed to serialize issues: {}", e)))?;
+    Ok(tool_result(issues_text(&issues), issues))
+}
+
+/// Answers one message, or nothing for notifications and responses.
+async fn handle(ctx: &Context, message: JsonValue) -> Option<JsonValue> {
+    let (id, method, params) = match classify(message) {
+        Message::Request { id, method, params } => (id, method, params),
+        Message::NoReply => return None,
+        Message::Invalid { id, error } => return Some(reply(id, Err(error))),
+    };
+    let outcome = match method.as_str() {
+        "initialize" => Ok(initialize(&params)),
+        "ping" => Ok(json!({})),
+        "tools/list" => Ok(json!({ "tools": tools() })),
+        "tools/call" => call_tool(ctx, &params).await,
+        other => Err(RpcError::new(
+            METHOD_NOT_FOUND,
+            format!("Method not found: {}", other),
+        )),
+    };
+    Some(reply(id, outcome))
+}
+
+/// Browsers may only call the endpoint from `MCP_ALLOWED_ORIGINS`, which
+/// guards local deployments against DNS rebinding. Non-browser clients send
+/// no `Origin`.
+fn check_origin(headers: &HeaderMap, allowed: &[String]) -> Result<(), AppError> {
+    let Some(origin) = headers.get(header::ORIGIN) else {
+        return Ok(());
+    };
+    let origin = origin.to_str().unwrap_or_default().trim_end_matches('/');
+    if allowed.iter().any(|a| a.eq_ignore_ascii_case(origin)) {
+        Ok(())
+    } else {
+        Err(AppError::Forbidden(format!(
+            "Origin {} may not call the MCP endpoint",
+            origin
+        )))
+    }
+}
+
+fn check_protocol_version(headers: &HeaderMap) -> Result<(), AppError> {
+    match headers.get(PROTOCOL_VERSION_HEADER) {
+        None => Ok(()),
+        Some(v) if PROTOCOL_VERSIONS.contains(&v.to_str().unwrap_or_default()) => Ok(()),
+        Some(v) => Err(AppError::BadRequest(format!(
+            "Unsupported {} {:?}; expected one of {}",
+            PROTOCOL_VERSION_HEADER,
+            v,
+            PROTOCOL_VERSIONS.join(", ")
+        ))),
+    }
+}
+
+/// JSON unless the client only takes an event stream.
+fn wants_event_stream(headers: &HeaderMap) -> bool {
+    let accept = headers
+        .get(header::ACCEPT)
+        .and_then(|v| v.to_str().ok())
+        .unwrap_or_default();
+    accept.contains("text/event-stream")
+        && !accept.contains("application/json")
+        && !accept.contains("*/*")
+}
+
+fn respond(status: StatusCode, body: &JsonValue, event_stream: bool) -> Response {
+    if event_stream {
+        let event = format!("event: message\ndata: {}\n\n", body);
+        (
+            status,
+            [
+                (header::CONTENT_TYPE, "text/event-stream"),
+                (header::CACHE_CONTROL, "no-cache"),
+            ],
+            event,
+        )
+            .into_response()
+    } else {
+        (status, Json(body)).into_response()
+    }
+}
+
+/// `POST /mcp`: the MCP Streamable HTTP transport. Takes one JSON-RPC message
+/// or a batch, and answers requests in the response body, as JSON or as a
+/// single-event stream. The endpoint keeps no sessions and never sends
+/// requests of its own, so `GET` (server-initiated streams) is not offered.
+pub async fn post_mcp(
+    State(pool): State<PgPool>,
+    State(config): State<Arc<Config>>,
+    user: Option<CurrentUser>,
+    headers: HeaderMap,
+    body: Bytes,
+) -> Result<Response, AppError> {
+    check_origin(&headers, &config.mcp_allowed_origins)?;
+    check_protocol_version(&headers)?;
+    let event_stream = wants_event_stream(&headers);
+
+    let message: JsonValue = match serde_json::from_slice(&body) {
+        Ok(message) => message,
+        Err(e) => {
+            let error = RpcError::new(PARSE_ERROR, format!("Parse error: {}", e));
+            return Ok(respond(
+                StatusCode::BAD_REQUEST,
+                &reply(JsonValue::Null, Err(error)),
+                event_stream,
+            ));
+        }
+    };
+    let ctx = Context {
+        pool,
+        config,
+        user,
+        headers,
+        body,
+    };
+
+    let replies = match message {
+        JsonValue::Array(batch) if batch.is_empty() => {
+            let error = RpcError::new(INVALID_REQUEST, "Empty batch");
+            return Ok(respond(
+                StatusCode::BAD_REQUEST,
+                &reply(JsonValue::Null, Err(error)),
+                event_stream,
+            ));
+        }
+        JsonValue::Array(batch) => {
+            let mut replies = Vec::new();
+            for message in batch {
+                replies.extend(handle(&ctx, message).await);
+            }
+            (!replies.is_empty()).then_some(JsonValue::Array(replies))
+        }
+        message => handle(&ctx, message).await,
+    };
+    Ok(match replies {
+        Some(body) => respond(StatusCode::OK, &body, event_stream),
+        None => StatusCode::ACCEPTED.into_response(),
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_classify() {
+        assert_eq!(
+            classify(json!({"jsonrpc": "2.0", "id": 1, "method": "ping"})),
+            Message::Request {
+                id: json!(1),
+                method: "ping".to_string(),
+                params: json!({}),
+            }
+        );
+        assert_eq!(
+            classify(json!({"jsonrpc": "2.0", "method": "notifications/initialized"})),
+            Message::NoReply
+        );
+        assert_eq!(
+            classify(json!({"jsonrpc": "2.0", "id": "a", "result": {}})),
+            Message::NoReply
+        );
+        let Message::Invalid { id, error } = classify(json!({"id": 7, "method": "ping"})) else {
+            panic!("expected an invalid request");
+        };
+        assert_eq!((id, error.code), (json!(7), INVALID_REQUEST));
+        assert!(matches!(
+            classify(json!({"jsonrpc": "2.0", "id": null, "method": "ping"})),
+            Message::Invalid { .. }
+        ));
+    }
+
+    #[test]
+    fn test_negotiate_and_initialize() {
+        assert_eq!(negotiate(Some("2025-03-26")), "2025-03-26");
+        assert_eq!(negotiate(Some("2024-11-05")), PROTOCOL_VERSIONS[0]);
+        assert_eq!(negotiate(None), PROTOCOL_VERSIONS[0]);
+        let result = initialize(&json!({"protocolVersion": "2025-06-18"}));
+        assert_eq!(result["protocolVersion"], "2025-06-18");
+        assert_eq!(result["capabilities"]["tools"]["listChanged"], false);
+
+        let tools = tools();
+        let names: Vec<&str> = tools
+            .as_array()
+            .unwrap()
+            .iter()
+            .map(|t| t["name"].as_str().unwrap())
+            .collect();
+        assert_eq!(
+            names,
+            [
+                "submit_report",
+                "list_servers",
+                "get_report_status",
+                "get_report_issues"
+            ]
+        );
+    }
+
+    #[test]
+    fn test_submit_args() {
+        let args = |report_json: JsonValue| {
+            arguments::<SubmitArgs>(&json!({"arguments": {
+                "server_name": "weather-mcp",
+                "repository_url": "https://github.com/acme/weather-mcp",
+                "developer_email": "dev@acme.test",
+                "report_markdown": "# Weather",
+                "report_json": report_json,
+            }}))
+            .unwrap()
+        };
+        let (request, key) = args(json!("{\"a\": 1}")).into_request().unwrap();
+        assert_eq!(request.report_json, json!({"a": 1}));
+        assert_eq!(request.report_data, "# Weather");
+        assert!(key.is_none());
+        assert_eq!(
+            args(json!({"b": 2})).into_request().unwrap().0.report_json,
+            json!({"b": 2})
+        );
+        assert_eq!(
+            args(json!("{not json")).into_request().unwrap_err().code,
+            INVALID_PARAMS
+        );
+        assert_eq!(
+            arguments::<SubmitArgs>(&json!({"arguments": {}}))
+                .unwrap_err()
+                .code,
+            INVALID_PARAMS
+        );
+        assert!(arguments::<ReportArgs>(&json!({"arguments": {"report_id": "x"}})).is_err());
+    }
+
+    #[test]
+    fn test_transport_headers() {
+        let mut headers = HeaderMap::new();
+        assert!(check_origin(&headers, &[]).is_ok());
+        headers.insert(
+            header::ORIGIN,
+            HeaderValue::from_static("https://evil.test"),
+        );
+        assert!(check_origin(&headers, &["https://review.acme.test".to_string()]).is_err());
+        headers.insert(
+            header::ORIGIN,
+            HeaderValue::from_static("https://review.acme.test"),
+        );
+        assert!(check_origin(&headers, &["https://review.acme.test".to_string()]).is_ok());
+
+        assert!(check_protocol_version(&headers).is_ok());
+        headers.insert(
+            PROTOCOL_VERSION_HEADER,
+            HeaderValue::from_static("2025-03-26"),
+        );
+        assert!(check_protocol_version(&headers).is_ok());
+        headers.insert(
+            PROTOCOL_VERSION_HEADER,
+            HeaderValue::from_static("1999-01-01"),
+        );
+        assert!(check_protocol_version(&headers).is_err());
+
+        headers.insert(
+            header::ACCEPT,
+            HeaderValue::from_static("application/json, text/event-stream"),
+        );
+        assert!(!wants_event_stream(&headers));
+        headers.insert(
+            header::ACCEPT,
+            HeaderValue::from_static("text/event-stream"),
+        );
+        assert!(wants_event_stream(&headers));
+    }
+
+    #[test]
+    fn test_issues_text() {
+        let text = issues_text(&json!({
+            "server_name": "weather-mcp",
+            "critical_issues": [{"description": "Token in logs", "status": "open"}],
+            "warnings": [],
+            "recommendations": [{"description": "Add rate limits"}],
+            "waived": [{}],
+        }));
+        assert!(text.contains("## Critical Issues (1)\n\n- Token in logs [open]"));
+        assert!(text.contains("## Warnings (0)"));
+        assert!(text.contains("- Add rate limits\n"));
+        assert!(text.contains("1 issue(s) are covered by a waiver."));
+    }
+}
@@ -0,0 +1,370 @@
+use axum::{
+    body::{Body, Bytes},
+    extract::{FromRequest, Path, Request, State},
+    http::{HeaderMap, StatusCode},
+    response::{IntoResponse, Json, Response},
+};
+use chrono::{DateTime, Utc};
+use serde::de::DeserializeOwned;
+use serde::{Deserialize, Serialize};
+use sqlx::PgPool;
+use std::str::FromStr;
+use std::sync::Arc;
+
+use crate::auth::CurrentUser;
+use crate::config::Config;
+use crate::error::AppError;
+use crate::signing::{self, ALGORITHM, KEY_ID_HEADER};
+use crate::webhooks::SIGNATURE_HEADER;
+
+// ============================================================================
+// Verified Origin (submissions signed by the go-live skill)
+// ============================================================================
+
+/// What to do with submissions that are not signed by a registered skill key
+/// (`SKILL_SIGNATURE_MODE`). Signatures that are present are always checked.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum SignatureMode {
+    /// Ignore signatures; no report has a verified origin.
+    Off,
+    /// Accept unsigned submissions, marking signed ones `verified_origin`.
+    #[default]
+    Mark,
+    /// Reject unsigned submissions.
+    Require,
+}
+
+impl FromStr for SignatureMode {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.to_ascii_lowercase().as_str() {
+            "off" => Ok(SignatureMode::Off),
+            "mark" => Ok(SignatureMode::Mark),
+            "require" => Ok(SignatureMode::Require),
+            other => Err(format!("unknown signature mode: {}", other)),
+        }
+    }
+}
+
+/// A JSON body together with the exact bytes it was parsed from, which are
+/// what a submission signature covers. Rejects like `Json` does.
+pub struct SignedJson<T>(pub T, pub Bytes);
+
+impl<T, S> FromRequest<S> for SignedJson<T>
+where
+    T: DeserializeOwned,
+    S: Send + Sync,
+{
+    type Rejection = Response;
+
+    async fn from_request(req: Request, state: &S) -> Result<Self, Self::Rejection> {
+        let headers = req.headers().clone();
+        let bytes = Bytes::from_request(req, state)
+            .await
+            .map_err(IntoResponse::into_response)?;
+        let mut json = Request::new(Body::from(bytes.clone()));
+        *json.headers_mut() = headers;
+        let Json(value) = Json::<T>::from_request(json, state)
+            .await
+            .map_err(IntoResponse::into_response)?;
+        Ok(SignedJson(value, bytes))
+    }
+}
+
+#[derive(Debug, Clone, Serialize, sqlx::FromRow)]
+pub struct SkillKey {
+    pub key_id: String,
+    pub name: String,
+    /// Raw 32-byte Ed25519 key, base64.
+    pub public_key: String,
+    /// The only server this key may sign reports for, if restricted.
+    pub server_name: Option<String>,
+    pub created_by: String,
+    pub created_at: DateTime<Utc>,
+    pub revoked_at: Option<DateTime<Utc>>,
+    pub revoked_by: Option<String>,
+}
+
+/// How a submission's origin was established.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct Origin {
+    pub verified: bool,
+    pub key_id: Option<String>,
+}
+
+/// The key id and base64 signature of a signed request, `None` if unsigned.
+fn signature_headers(headers: &HeaderMap) -> Result<Option<(String, String)>, AppError> {
+    let read = |name: &str| {
+        headers
+            .get(name)
+            .map(|v| v.to_str().map(|s| s.trim().to_string()))
+            .transpose()
+            .map_err(|_| AppError::InvalidSignature(format!("{} must be visible ASCII", name)))
+    };
+    match (read(KEY_ID_HEADER)?, read(SIGNATURE_HEADER)?) {
+        (None, None) => Ok(None),
+        (Some(key_id), Some(signature)) => {
+            let signature = signature
+                .strip_prefix(ALGORITHM)
+                .and_then(|s| s.strip_prefix('='))
+                .ok_or_else(|| {
+                    AppError::InvalidSignature(format!(
+                        "{} must be {}=<base64>",
+                        SIGNATURE_HEADER, ALGORITHM
+                    ))
+                })?;
+            Ok(Some((key_id, signature.to_string())))
+        }
+        _ => Err(AppError::InvalidSignature(format!(
+            "Signed submissions need both {} and {}",
+            KEY_ID_HEADER, SIGNATURE_HEADER
+        ))),
+    }
+}
+
+/// Checks `signature` over `body` against a registered, unrevoked `key`.
+fn check(key: &SkillKey, server_name: &str, body: &[u8], signature: &str) -> Result<(), AppError> {
+    if let Some(allowed) = &key.server_name {
+        if allowed != server_name {
+            return Err(AppError::InvalidSignature(format!(
+                "Key {} may only sign reports for {}",
+                key.key_id, allowed
+            )));
+        }
+    }
+    let public = signing::parse_public_key(&key.public_key).map_err(|e| {
+        tracing::error!("Stored skill key {} is unusable: {}", key.key_id, e);
+        AppError::Internal("Failed to verify submission signature".to_string())
+    })?;
+    if signing::verify(&public, body, signature) {
+        Ok(())
+    } else {
+        Err(AppError::InvalidSignature(format!(
+            "Signature does not match the submitted body for key {}",
+            key.key_id
+        )))
+    }
+}
+
+/// Establishes where a submission of `server_name` came from: a valid
+/// signature over `body` marks it verified, an invalid one is refused, and an
+/// unsigned one is refused only in `require` mode.
+pub async fn verify(
+    pool: &PgPool,
+    config: &Config,
+    headers: &HeaderMap,
+    body: &[u8],
+    server_name: &str,
+) -> Result<Origin, AppError> {
+    if config.signature_mode == SignatureMode::Off {
+        return Ok(Origin::default());
+    }
+    let Some((key_id, signature)) = signature_headers(headers)? else {
+        if config.signature_mode == SignatureMode::Require {
+            return Err(AppError::InvalidSignature(format!(
+                "Submissions must be signed by a registered skill key ({}, {})",
+                KEY_ID_HEADER, SIGNATURE_HEADER
+            )));
+        }
+        return Ok(Origin::default());
+    };
+
+    let key = sqlx::query_as::<_, SkillKey>(
+        "SELECT * FROM skill_keys WHERE key_id = $1 AND revoked_at IS NULL",
+    )
+    .bind(&key_id)
+    .fetch_optional(pool)
+    .await
+    .map_err(AppError::db("Failed to look up skill key"))?
+    .ok_or_else(|| AppError::InvalidSignature(format!("Unknown or revoked key {}", key_id)))?;
+    check(&key, server_name, body, &signature).inspect_err(|e| {
+        tracing::warn!(
+            "Rejected signed submission for {}: {}",
+            server_name,
+            e.message()
+        );
+    })?;
+
+    Ok(Origin {
+        verified: true,
+        key_id: Some(key.key_id),
+    })
+}
+
+fn require_admin(user: &CurrentUser, config: &Config) -> Result<(), AppError> {
+    if user.is_admin(&config.auth) {
+        Ok(())
+    } else {
+        Err(AppError::Forbidden(
+            "Skill keys are restricted to admins".to_string(),
+        ))
+    }
+}
+
+#[derive(Debug, Deserialize)]
+pub struct RegisterKeyRequest {
+    name: String,
+    /// SPKI PEM or base64 raw Ed25519 public key.
+    public_key: String,
+    server_name: Option<String>,
+}
+
+pub async fn register_key(
+    State(pool): State<PgPool>,
+    State(config): State<Arc<Config>>,
+    user: CurrentUser,
+    Json(req): Json<RegisterKeyRequest>,
+) -> Result<(StatusCode, Json<SkillKey>), AppError> {
+    require_admin(&user, &config)?;
+    if req.name.trim().is_empty() {
+        return Err(AppError::Validation("name is required".to_string()));
+    }
+    let public = signing::parse_public_key(&req.public_key).map_err(AppError::Validation)?;
+    let key_id = signing::key_id(&public);
+    let server_name = req
+        .server_name
+        .as_deref()
+        .map(str::trim)
+        .filter(|s| !s.is_empty());
+
+    let key = sqlx::query_as::<_, SkillKey>(
+        r#"
+        INSERT INTO skill_keys (key_id, name, public_key, server_name, created_by)
+        VALUES ($1, $2, $3, $4, $5)
+        RETURNING *
+        "#,
+    )
+    .bind(&key_id)
+    .bind(req.name.trim())
+    .bind(signing::encode_public_key(&public))
+    .bind(server_name)
+    .bind(&user.email)
+    .fetch_one(&pool)
+    .await
+    .map_err(AppError::db("Failed to register skill key"))?;
+
+    tracing::info!(
+        "Skill key {} ({}) registered by {}",
+        key.key_id,
+        key.name,
+        user.email
+    );
+    Ok((StatusCode::CREATED, Json(key)))
+}
+
+pub async fn list_keys(
+    State(pool): State<PgPool>,
+    State(config): State<Arc<Config>>,
+    user: CurrentUser,
+) -> Result<Json<Vec<SkillKey>>, AppError> {
+    require_admin(&user, &config)?;
+    let keys = sqlx::query_as::<_, SkillKey>("SELECT * FROM skill_keys ORDER BY created_at DESC")
+        .fetch_all(&pool)
+        .await
+        .map_err(AppError::db("Failed to list skill keys"))?;
+    Ok(Json(keys))
+}
+
+/// Revokes a key. Reports it already verified keep their `verified_origin`.
+pub async fn revoke_key(
+    State(pool): State<PgPool>,
+    State(config): State<Arc<Config>>,
+    Path(key_id): Path<String>,
+    user: CurrentUser,
+) -> Result<Json<SkillKey>, AppError> {
+    require_admin(&user, &config)?;
+    let key = sqlx::query_as::<_, SkillKey>(
+        r#"
+        UPDATE skill_keys SET revoked_at = NOW(), revoked_by = $2
+        WHERE key_id = $1 AND revoked_at IS NULL
+        RETURNING *
+        "#,
+    )
+    .bind(&key_id)
+    .bind(&user.email)
+    .fetch_optional(&pool)
+    .await
+    .map_err(AppError::db("Failed to revoke skill key"))?
+    .ok_or_else(|| AppError::NotFound(format!("Active skill key {} not found", key_id)))?;
+
+    tracing::info!("Skill key {} revoked by {}", key_id, user.email);
+    Ok(Json(key))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::signing::Signer;
+    use axum::http::HeaderValue;
+
+    fn key(signer: &Signer, server_name: Option<&str>) -> SkillKey {
+        SkillKey {
+            key_id: signer.key_id().to_string(),
+            name: "go-live skill".to_string(),
+            public_key: signing::encode_public_key(&signer.verifying_key()),
+            server_name: server_name.map(String::from),
+            created_by: "admin@acme.test".to_string(),
+            created_at: Utc::now(),
+            revoked_at: None,
+            revoked_by: None,
+        }
+    }
+
+    #[test]
+    fn test_signature_headers() {
+        let mut headers = HeaderMap::new();
+        assert!(signature_headers(&headers).unwrap().is_none());
+
+        headers.insert(KEY_ID_HEADER, HeaderValue::from_static("0123456789abcdef"));
+        assert!(signature_headers(&headers).is_err());
+
+        headers.insert(SIGNATURE_HEADER, HeaderValue::from_static("sha256=abc"));
+        assert_eq!(
+            signature_headers(&headers).unwrap_err().code(),
+            "invalid_signature"
+        );
+
+        headers.insert(SIGNATURE_HEADER, HeaderValue::from_static("ed25519=c2ln"));
+        assert_eq!(
+            signature_headers(&headers).unwrap(),
+            Some(("0123456789abcdef".to_string(), "c2ln".to_string()))
+        );
+    }
+
+    #[test]
+    fn test_check_signature() {
+        let signer = Signer::parse("nWGxne/9WmC6hEr0kuwsxERJxWl7MmkZcDusAxyuf2A=").unwrap();
+        let body = br#"{"server_name":"weather-mcp"}"#;
+        let signature = signer.sign(body);
+
+        assert!(check(&key(&signer, None), "weather-mcp", body, &signature).is_ok());
+        assert!(check(&key(&signer, None), "weather-mcp", b"{}", &signature).is_err());
+        assert!(check(
+            &key(&signer, Some("weather-mcp")),
+            "weather-mcp",
+            body,
+            &signature
+        )
+        .is_ok());
+        assert!(check(
+            &key(&signer, Some("other-mcp")),
+            "weather-mcp",
+            body,
+            &signature
+        )
+        .is_err());
+        let other = Signer::generate();
+        assert!(check(&key(&other, None), "weather-mcp", body, &signature).is_err());
+    }
+
+    #[test]
+    fn test_signature_mode() {
+        assert_eq!(
+            "REQUIRE".parse::<SignatureMode>(),
+            Ok(SignatureMode::Require)
+        );
+        assert_eq!(SignatureMode::default(), SignatureMode::Mark);
+        assert!("strict".parse::<SignatureMode>().is_err());
+    }
+}
@@ -0,0 +1,263 @@
+use axum::{
+    extract::{Query, State},
+    response::Json,
+};
+use chrono::{DateTime, Duration, Utc};
+use serde::{Deserialize, Serialize};
+use serde_json::Value as JsonValue;
+use sqlx::PgPool;
+use std::sync::Arc;
+use uuid::Uuid;
+
+use crate::config::Config;
+use crate::error::AppError;
+use crate::events;
+use crate::Report;
+
+// ============================================================================
+// Approval Expiry and Re-certification
+// ============================================================================
+
+/// How long an approval stays valid, by the report's risk level.
+#[derive(Debug, Clone)]
+pub struct ValidityConfig {
+    /// `APPROVAL_VALIDITY_DAYS=CRITICAL:30,HIGH:90,MEDIUM:180,LOW:365`.
+    pub days_by_risk: Vec<(String, i64)>,
+    /// For reports without a configured risk level (`APPROVAL_VALIDITY_DEFAULT_DAYS`).
+    pub default_days: i64,
+    /// How far ahead `recertification-due` looks by default
+    /// (`RECERTIFICATION_NOTICE_DAYS`).
+    pub notice_days: i64,
+}
+
+impl Default for ValidityConfig {
+    fn default() -> Self {
+        Self {
+            days_by_risk: vec![
+                ("CRITICAL".to_string(), 30),
+                ("HIGH".to_string(), 90),
+                ("MEDIUM".to_string(), 180),
+                ("LOW".to_string(), 365),
+            ],
+            default_days: 365,
+            notice_days: 30,
+        }
+    }
+}
+
+impl ValidityConfig {
+    pub fn days_for(&self, risk_level: Option<&str>) -> i64 {
+        risk_level
+            .and_then(|risk| {
+                self.days_by_risk
+                    .iter()
+                    .find(|(level, _)| level.eq_ignore_ascii_case(risk.trim()))
+            })
+            .map_or(self.default_days, |(_, days)| *days)
+    }
+
+    /// When an approval of a report with `report_json` granted at
+    /// `approved_at` lapses.
+    pub fn expires_at(&self, report_json: &JsonValue, approved_at: DateTime<Utc>) -> DateTime<Utc> {
+        approved_at + Duration::days(self.days_for(risk_level(report_json)))
+    }
+}
+
+/// Parses `HIGH:90,LOW:365` into upper-cased risk levels and days.
+pub fn parse_validity(raw: &str) -> Result<Vec<(String, i64)>, String> {
+    raw.split(',')
+        .map(str::trim)
+        .filter(|p| !p.is_empty())
+        .map(|part| {
+            let (level, days) = part
+                .split_once(':')
+                .ok_or_else(|| format!("{:?} is missing ':days'", part))?;
+            let days: i64 = days
+                .trim()
+                .parse()
+                .map_err(|_| format!("{:?} has an invalid number of days", part))?;
+            if days < 1 {
+                return Err(format!("{:?} must be valid for at least one day", part));
+            }
+            Ok((level.trim().to_ascii_uppercase(), days))
+        })
+        .collect()
+}
+
+/// The risk level the skill recorded, from `phase1_security` or, for older
+/// reports, the executive summary.
+pub fn risk_level(report_json: &JsonValue) -> Option<&str> {
+    ["phase1_security", "executive_summary"]
+        .iter()
+        .find_map(|section| report_json.get(section)?.get("risk_level")?.as_str())
+}
+
+/// Gives approvals granted before expiry existed their `expires_at`,
+/// counted from when they were reviewed.
+pub async fn backfill(pool: &PgPool, config: &ValidityConfig) -> Result<usize, AppError> {
+    let failed = || AppError::db("Failed to backfill approval expiry");
+    let approvals: Vec<(Uuid, JsonValue, DateTime<Utc>)> = sqlx::query_as(
+        r#"
+        SELECT id, report_json, COALESCE(reviewed_at, submitted_at)
+        FROM mcp_server_reports
+        WHERE status IN ('approved', 'approved_with_conditions') AND expires_at IS NULL
+        "#,
+    )
+    .fetch_all(pool)
+    .await
+    .map_err(failed())?;
+
+    for (id, report_json, approved_at) in &approvals {
+        sqlx::query("UPDATE mcp_server_reports SET expires_at = $2 WHERE id = $1")
+            .bind(id)
+            .bind(config.expires_at(report_json, *approved_at))
+            .execute(pool)
+            .await
+            .map_err(failed())?;
+    }
+    Ok(approvals.len())
+}
+
+/// Moves approvals past their `expires_at` to `expired`. Conditions still
+/// open on them are withdrawn; the next approval sets its own.
+pub async fn expire_approvals(pool: &PgPool, config: &Config) -> Result<u64, AppError> {
+    let failed = || AppError::db("Failed to expire approvals");
+    let mut tx = pool.begin().await.map_err(failed())?;
+    let expired = sqlx::query_as::<_, Report>(
+        r#"
+        UPDATE mcp_server_reports
+        SET status = 'expired', version = version + 1
+        WHERE status IN ('approved', 'approved_with_conditions') AND expires_at <= NOW()
+        RETURNING *
+        "#,
+    )
+    .fetch_all(&mut *tx)
+    .await
+    .map_err(failed())?;
+
+    for report in &expired {
+        sqlx::query(
+            r#"
+            UPDATE report_conditions SET withdrawn_at = NOW()
+            WHERE report_id = $1 AND fulfilled_at IS NULL AND withdrawn_at IS NULL
+            "#,
+        )
+        .bind(report.id)
+        .execute(&mut *tx)
+        .await
+        .map_err(failed())?;
+        events::publish(&mut tx, config, "report.expired", report).await?;
+        tracing::warn!(
+            "Approval of report {} for {} expired; a fresh report is needed",
+            report.id,
+            report.server_name
+        );
+    }
+    tx.commit().await.map_err(failed())?;
+    Ok(expired.len() as u64)
+}
+
+#[derive(Debug, Deserialize)]
+pub struct DueQuery {
+    /// Also list approvals that lapse within this many days.
+    within_days: Option<i64>,
+}
+
+/// A server whose latest approval has lapsed or is about to.
+#[derive(Debug, Serialize, sqlx::FromRow)]
+pub struct RecertificationDue {
+    server_name: String,
+    report_id: Uuid,
+    status: String,
+    risk_level: Option<String>,
+    approved_at: Option<DateTime<Utc>>,
+    expires_at: DateTime<Utc>,
+    days_remaining: i64,
+    /// A newer submission already waiting for review, if any.
+    pending_report_id: Option<Uuid>,
+}
+
+/// Servers needing a fresh report, soonest-expiring first.
+pub async fn recertification_due(
+    State(pool): State<PgPool>,
+    State(config): State<Arc<Config>>,
+    Query(query): Query<DueQuery>,
+) -> Result<Json<Vec<RecertificationDue>>, AppError> {
+    let within_days = query.within_days.unwrap_or(config.validity.notice_days);
+    if !(0..=3650).contains(&within_days) {
+        return Err(AppError::BadRequest(
+            "within_days must be between 0 and 3650".to_string(),
+        ));
+    }
+
+    let due = sqlx::query_as::<_, RecertificationDue>(
+        r#"
+        WITH latest AS (
+            SELECT DISTINCT ON (server_name) *
+            FROM mcp_server_reports
+            WHERE status IN ('approved', 'approved_with_conditions', 'expired')
+            ORDER BY server_name, submitted_at DESC
+        )
+        SELECT l.server_name, l.id AS report_id, l.status,
+               COALESCE(l.report_json->'phase1_security'->>'risk_level',
+                        l.report_json->'executive_summary'->>'risk_level') AS risk_level,
+               l.reviewed_at AS approved_at, l.expires_at,
+               FLOOR(EXTRACT(EPOCH FROM l.expires_at - NOW()) / 86400)::bigint AS days_remaining,
+               (SELECT p.id FROM mcp_server_reports p
+                WHERE p.server_name = l.server_name AND p.submitted_at > l.submitted_at
+                  AND p.status IN ('pending_review', 'in_review')
+                ORDER BY p.submitted_at DESC LIMIT 1) AS pending_report_id
+        FROM latest l
+        WHERE l.expires_at <= NOW() + make_interval(days => $1::int)
+        ORDER BY l.expires_at, l.server_name
+        "#,
+    )
+    .bind(within_days as i32)
+    .fetch_all(&pool)
+    .await
+    .map_err(AppError::db(
+        "Failed to list servers due for re-certification",
+    ))?;
+
+    tracing::info!(
+        "{} server(s) due for re-certification within {} days",
+        due.len(),
+        within_days
+    );
+    Ok(Json(due))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn test_parse_validity() {
+        let parsed = parse_validity("high:90, LOW:365").unwrap();
+        assert_eq!(
+            parsed,
+            vec![("HIGH".to_string(), 90), ("LOW".to_string(), 365)]
+        );
+        assert!(parse_validity("HIGH").is_err());
+        assert!(parse_validity("HIGH:0").is_err());
+        assert!(parse_validity("").unwrap().is_empty());
+    }
+
+    #[test]
+    fn test_expiry_follows_risk_level() {
+        let config = ValidityConfig::default();
+        let approved_at = Utc::now();
+        let high = json!({"phase1_security": {"risk_level": "High"}});
+        let legacy = json!({"executive_summary": {"risk_level": "LOW"}});
+        let unknown = json!({"phase1_security": {"risk_level": "SEVERE"}});
+
+        assert_eq!(
+            config.expires_at(&high, approved_at),
+            approved_at + Duration::days(90)
+        );
+        assert_eq!(config.days_for(risk_level(&legacy)), 365);
+        assert_eq!(config.days_for(risk_level(&unknown)), config.default_days);
+        assert_eq!(config.days_for(risk_level(&json!({}))), config.default_days);
+    }
+}
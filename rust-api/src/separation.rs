@@ -0,0 +1,239 @@
+use axum::{
+    extract::{Query, State},
+    response::Json,
+};
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use sqlx::{PgPool, Postgres, Transaction};
+use std::sync::Arc;
+use uuid::Uuid;
+
+use crate::auth::{parse_emails, CurrentUser};
+use crate::config::Config;
+use crate::error::AppError;
+use crate::Report;
+
+// ============================================================================
+// Separation of Duties (Four-Eyes Rule)
+// ============================================================================
+
+const MAX_REASON_CHARS: usize = 1000;
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Team {
+    pub name: String,
+    pub members: Vec<String>,
+}
+
+/// Who counts as "the submitter" when deciding on a report.
+#[derive(Debug, Clone, Default)]
+pub struct SeparationConfig {
+    /// `TEAMS=payments=a@x.no|b@x.no,platform=c@x.no`.
+    pub teams: Vec<Team>,
+    /// `FOUR_EYES_SAME_TEAM`: also bar the developer's teammates.
+    pub same_team: bool,
+}
+
+impl SeparationConfig {
+    /// The teams `email` is a member of.
+    pub fn team_names(&self, email: &str) -> Vec<&str> {
+        let email = email.to_lowercase();
+        self.teams
+            .iter()
+            .filter(|t| t.members.contains(&email))
+            .map(|t| t.name.as_str())
+            .collect()
+    }
+
+    /// Why `actor` may not decide on a report submitted by `developer`, if
+    /// they may not.
+    fn conflict(&self, developer: &str, actor: &str) -> Option<String> {
+        if actor.eq_ignore_ascii_case(developer) {
+            return Some(format!("{} submitted this report", actor));
+        }
+        if !self.same_team {
+            return None;
+        }
+        let developer = developer.to_lowercase();
+        let actor = actor.to_lowercase();
+        self.teams
+            .iter()
+            .find(|t| t.members.contains(&developer) && t.members.contains(&actor))
+            .map(|t| {
+                format!(
+                    "{} is on the {} team with submitter {}",
+                    actor, t.name, developer
+                )
+            })
+    }
+}
+
+/// Parses `name=email|email,name=email` into teams.
+pub fn parse_teams(raw: &str) -> Result<Vec<Team>, String> {
+    raw.split(',')
+        .map(str::trim)
+        .filter(|t| !t.is_empty())
+        .map(|team| {
+            let (name, members) = team
+                .split_once('=')
+                .ok_or_else(|| format!("team {:?} is missing '=members'", team))?;
+            Ok(Team {
+                name: name.trim().to_string(),
+                members: parse_emails(&members.replace('|', ",")),
+            })
+        })
+        .collect()
+}
+
+/// Enforces the four-eyes rule for a decision on `report`.
+///
+/// The authenticated caller must be independent of the submitter, both the
+/// developer the report names and the caller who created it; the
+/// reviewer recorded on the report is always the caller. An admin may override
+/// with a break-glass reason, which is written to the audit log inside `tx`
+/// so it commits together with the decision.
+pub async fn enforce(
+    tx: &mut Transaction<'_, Postgres>,
+    config: &Config,
+    report: &Report,
+    user: &CurrentUser,
+    break_glass: Option<&str>,
+    action: &str,
+) -> Result<(), AppError> {
+    // Check the recorded submitter too, in case it differs from the address
+    // the report names.
+    let conflict = config
+        .separation
+        .conflict(&report.developer_email, &user.email)
+        .or_else(|| {
+            report
+                .submitted_by
+                .as_deref()
+                .and_then(|submitter| config.separation.conflict(submitter, &user.email))
+        });
+
+    let Some(reason) = break_glass.map(str::trim) else {
+        return match conflict {
+            Some(conflict) => Err(AppError::Forbidden(format!(
+                "Four-eyes rule: {}; another reviewer must {} it",
+                conflict, action
+            ))),
+            None => Ok(()),
+        };
+    };
+
+    if !user.is_admin(&config.auth) {
+        return Err(AppError::Forbidden(
+            "Only admins may use break_glass_reason".to_string(),
+        ));
+    }
+    if reason.is_empty() || reason.chars().count() > MAX_REASON_CHARS || reason.contains('\0') {
+        return Err(AppError::Validation(format!(
+            "break_glass_reason must be 1-{} characters",
+            MAX_REASON_CHARS
+        )));
+    }
+    let Some(conflict) = conflict else {
+        // Nothing to override; do not leave a misleading audit entry.
+        return Ok(());
+    };
+
+    sqlx::query(
+        r#"
+        INSERT INTO break_glass_audit (report_id, actor, action, conflict, reason)
+        VALUES ($1, $2, $3, $4, $5)
+        "#,
+    )
+    .bind(report.id)
+    .bind(&user.email)
+    .bind(action)
+    .bind(&conflict)
+    .bind(reason)
+    .execute(&mut **tx)
+    .await
+    .map_err(AppError::db("Failed to write break-glass audit entry"))?;
+
+    tracing::warn!(
+        "Break-glass: {} overrode four-eyes rule to {} report {} ({}): {}",
+        user.email,
+        action,
+        report.id,
+        conflict,
+        reason
+    );
+    Ok(())
+}
+
+#[derive(Debug, Serialize, sqlx::FromRow)]
+pub struct BreakGlassEntry {
+    id: Uuid,
+    report_id: Uuid,
+    actor: String,
+    action: String,
+    conflict: String,
+    reason: String,
+    created_at: DateTime<Utc>,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct AuditQuery {
+    report_id: Option<Uuid>,
+}
+
+/// Admin-only view of break-glass overrides, newest first.
+pub async fn list_break_glass(
+    State(pool): State<PgPool>,
+    State(config): State<Arc<Config>>,
+    user: CurrentUser,
+    Query(query): Query<AuditQuery>,
+) -> Result<Json<Vec<BreakGlassEntry>>, AppError> {
+    if !user.is_admin(&config.auth) {
+        return Err(AppError::Forbidden(
+            "The break-glass audit log is restricted to admins".to_string(),
+        ));
+    }
+    let entries = sqlx::query_as::<_, BreakGlassEntry>(
+        r#"
+        SELECT * FROM break_glass_audit
+        WHERE ($1::uuid IS NULL OR report_id = $1)
+        ORDER BY created_at DESC
+        "#,
+    )
+    .bind(query.report_id)
+    .fetch_all(&pool)
+    .await
+    .map_err(AppError::db("Failed to list break-glass audit entries"))?;
+    Ok(Json(entries))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_teams() {
+        let teams = parse_teams("payments=A@x.no|b@x.no, platform=c@x.no").unwrap();
+        assert_eq!(teams.len(), 2);
+        assert_eq!(teams[0].name, "payments");
+        assert_eq!(teams[0].members, vec!["a@x.no", "b@x.no"]);
+        assert!(parse_teams("payments").is_err());
+        assert!(parse_teams("").unwrap().is_empty());
+    }
+
+    #[test]
+    fn test_submitter_and_teammates_conflict() {
+        let mut config = SeparationConfig {
+            teams: parse_teams("payments=dev@x.no|mate@x.no").unwrap(),
+            same_team: false,
+        };
+        assert!(config.conflict("dev@x.no", "DEV@x.no").is_some());
+        assert!(config.conflict("dev@x.no", "mate@x.no").is_none());
+
+        config.same_team = true;
+        assert!(config
+            .conflict("dev@x.no", "mate@x.no")
+            .unwrap()
+            .contains("payments"));
+        assert!(config.conflict("dev@x.no", "other@x.no").is_none());
+    }
+}
@@ -0,0 +1,371 @@
+use chrono::{DateTime, Duration, Utc};
+use serde::{Deserialize, Serialize};
+use sqlx::PgPool;
+
+use crate::config::Config;
+use crate::error::AppError;
+use crate::notifications;
+use crate::recertification::risk_level;
+use crate::separation::Team;
+use crate::Report;
+
+// ============================================================================
+// Review SLAs
+// ============================================================================
+
+/// Statuses in which a report waits for a decision and its SLA runs.
+pub const OPEN_REVIEW: [&str; 2] = ["pending_review", "in_review"];
+
+/// How long a review may take, by the report's risk level, and who is
+/// reminded as it runs late.
+#[derive(Debug, Clone)]
+pub struct SlaConfig {
+    /// `REVIEW_SLA_HOURS=CRITICAL:24,HIGH:48,MEDIUM:72,LOW:120`.
+    pub hours_by_risk: Vec<(String, i64)>,
+    /// For reports without a configured risk level (`REVIEW_SLA_DEFAULT_HOURS`).
+    pub default_hours: i64,
+    /// The assignee is first reminded once this share of the SLA has passed
+    /// (`REVIEW_SLA_REMIND_PERCENT`).
+    pub remind_percent: i64,
+    /// Team leads are told once a breach is this old
+    /// (`REVIEW_SLA_ESCALATE_AFTER_HOURS`).
+    pub escalate_after_hours: i64,
+    /// `TEAM_LEADS=security=a@x.no|b@x.no`, in the `TEAMS` format.
+    pub leads: Vec<Team>,
+}
+
+impl Default for SlaConfig {
+    fn default() -> Self {
+        Self {
+            hours_by_risk: vec![
+                ("CRITICAL".to_string(), 24),
+                ("HIGH".to_string(), 48),
+                ("MEDIUM".to_string(), 72),
+                ("LOW".to_string(), 120),
+            ],
+            default_hours: 72,
+            remind_percent: 75,
+            escalate_after_hours: 24,
+            leads: Vec::new(),
+        }
+    }
+}
+
+impl SlaConfig {
+    pub fn hours_for(&self, risk_level: Option<&str>) -> i64 {
+        risk_level
+            .and_then(|risk| {
+                self.hours_by_risk
+                    .iter()
+                    .find(|(level, _)| level.eq_ignore_ascii_case(risk.trim()))
+            })
+            .map_or(self.default_hours, |(_, hours)| *hours)
+    }
+
+    /// Time in state and, while the report awaits a decision, its deadline.
+    fn status(&self, report: &Report, now: DateTime<Utc>) -> SlaStatus {
+        let sla_due_at = report
+            .review_started_at
+            .filter(|_| OPEN_REVIEW.contains(&report.status.as_str()))
+            .map(|started| {
+                started + Duration::hours(self.hours_for(risk_level(&report.report_json)))
+            });
+        SlaStatus {
+            time_in_state_secs: (now - report.status_changed_at).num_seconds().max(0),
+            sla_due_at,
+            sla_breached: sla_due_at.is_some_and(|due| now > due),
+        }
+    }
+
+    /// How far reminders about `report` should have escalated by `now`.
+    fn reminder_level(&self, report: &Report, now: DateTime<Utc>) -> Level {
+        let (Some(started), Some(due)) = (
+            report.review_started_at,
+            self.status(report, now).sla_due_at,
+        ) else {
+            return Level::None;
+        };
+        let remind_at = started + (due - started) * self.remind_percent as i32 / 100;
+        if now >= due + Duration::hours(self.escalate_after_hours) {
+            Level::Escalated
+        } else if now >= due {
+            Level::Breached
+        } else if now >= remind_at {
+            Level::DueSoon
+        } else {
+            Level::None
+        }
+    }
+
+    /// Leads of `teams`; all leads when none of them has any.
+    fn leads_for(&self, teams: &[&str]) -> Vec<String> {
+        let own: Vec<String> = self
+            .leads
+            .iter()
+            .filter(|lead| teams.contains(&lead.name.as_str()))
+            .flat_map(|lead| lead.members.clone())
+            .collect();
+        let mut leads = if own.is_empty() {
+            self.leads.iter().flat_map(|t| t.members.clone()).collect()
+        } else {
+            own
+        };
+        leads.sort();
+        leads.dedup();
+        leads
+    }
+}
+
+/// Parses `HIGH:48,LOW:120` into upper-cased risk levels and hours.
+pub fn parse_hours(raw: &str) -> Result<Vec<(String, i64)>, String> {
+    raw.split(',')
+        .map(str::trim)
+        .filter(|p| !p.is_empty())
+        .map(|part| {
+            let (level, hours) = part
+                .split_once(':')
+                .ok_or_else(|| format!("{:?} is missing ':hours'", part))?;
+            let hours: i64 = hours
+                .trim()
+                .parse()
+                .map_err(|_| format!("{:?} has an invalid number of hours", part))?;
+            if hours < 1 {
+                return Err(format!("{:?} must allow at least one hour", part));
+            }
+            Ok((level.trim().to_ascii_uppercase(), hours))
+        })
+        .collect()
+}
+
+/// Time in state and review SLA of a report, added to it on reads.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct SlaStatus {
+    pub time_in_state_secs: i64,
+    /// When the review is due; only set while a decision is awaited.
+    pub sla_due_at: Option<DateTime<Utc>>,
+    pub sla_breached: bool,
+}
+
+/// Adds [`SlaStatus`] to `reports` as of now.
+pub fn annotate(config: &SlaConfig, reports: &mut [Report]) {
+    let now = Utc::now();
+    for report in reports {
+        report.sla = Some(config.status(report, now));
+    }
+}
+
+/// Escalation steps, stored as `sla_reminder_level`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+enum Level {
+    None = 0,
+    /// `remind_percent` of the SLA has passed; the assignee is reminded.
+    DueSoon = 1,
+    /// The SLA is breached; the assignee is reminded again.
+    Breached = 2,
+    /// The breach is `escalate_after_hours` old; team leads are told.
+    Escalated = 3,
+}
+
+#[derive(sqlx::FromRow)]
+struct OpenReview {
+    #[sqlx(flatten)]
+    report: Report,
+    sla_reminder_level: i16,
+}
+
+/// Queues reminders for reviews running late, escalating from the assignee
+/// to team leads. Each step is sent once per review; when several are due
+/// at once only the latest is sent.
+pub async fn send_reminders(pool: &PgPool, config: &Config) -> Result<u64, AppError> {
+    if !config.email.enabled() {
+        return Ok(0);
+    }
+    let failed = || AppError::db("Failed to queue review reminders");
+    let mut tx = pool.begin().await.map_err(failed())?;
+    let open = sqlx::query_as::<_, OpenReview>(
+        r#"
+        SELECT * FROM mcp_server_reports
+        WHERE status IN ('pending_review', 'in_review') AND review_started_at IS NOT NULL
+          AND sla_reminder_level < 3
+        FOR UPDATE SKIP LOCKED
+        "#,
+    )
+    .fetch_all(&mut *tx)
+    .await
+    .map_err(failed())?;
+
+    let now = Utc::now();
+    let mut sent = 0;
+    for OpenReview {
+        report,
+        sla_reminder_level,
+    } in &open
+    {
+        let level = config.sla.reminder_level(report, now);
+        if (level as i16) <= *sla_reminder_level {
+            continue;
+        }
+        let due_at = config.sla.status(report, now).sla_due_at.unwrap_or(now);
+        let link = config.report_link(report.id);
+        let emails = match level {
+            Level::None => Vec::new(),
+            Level::DueSoon | Level::Breached => {
+                let recipients = match &report.assignee {
+                    Some(assignee) => vec![assignee.clone()],
+                    None => config.auth.reviewers.clone(),
+                };
+                recipients
+                    .iter()
+                    .map(|to| {
+                        notifications::review_reminder(
+                            to,
+                            report,
+                            due_at,
+                            level == Level::Breached,
+                            link.clone(),
+                        )
+                    })
+                    .collect()
+            }
+            Level::Escalated => {
+                let teams = report
+                    .assignee
+                    .as_deref()
+                    .map(|a| config.separation.team_names(a))
+                    .unwrap_or_default();
+                let mut leads = config.sla.leads_for(&teams);
+                if leads.is_empty() {
+                    leads = config.auth.admins.clone();
+                }
+                leads
+                    .iter()
+                    .map(|to| notifications::review_escalation(to, report, due_at, link.clone()))
+                    .collect::<Vec<_>>()
+            }
+        };
+        for email in &emails {
+            notifications::queue(&mut *tx, config, email).await?;
+        }
+        sqlx::query("UPDATE mcp_server_reports SET sla_reminder_level = $2 WHERE id = $1")
+            .bind(report.id)
+            .bind(level as i16)
+            .execute(&mut *tx)
+            .await
+            .map_err(failed())?;
+        if level >= Level::Breached {
+            tracing::warn!(
+                "Review of report {} for {} is past its SLA (due {})",
+                report.id,
+                report.server_name,
+                due_at
+            );
+        }
+        sent += emails.len() as u64;
+    }
+    tx.commit().await.map_err(failed())?;
+    Ok(sent)
+}
+
+/// Reviews awaiting a decision past their SLA, for analytics.
+pub async fn count_breached(pool: &PgPool, config: &SlaConfig) -> Result<i64, AppError> {
+    let mut open = sqlx::query_as::<_, Report>(
+        "SELECT * FROM mcp_server_reports WHERE status IN ('pending_review', 'in_review')",
+    )
+    .fetch_all(pool)
+    .await
+    .map_err(AppError::db("Failed to count SLA breaches"))?;
+    annotate(config, &mut open);
+    Ok(open
+        .iter()
+        .filter(|r| r.sla.as_ref().is_some_and(|s| s.sla_breached))
+        .count() as i64)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    fn report(status: &str, risk: &str, started_hours_ago: i64, now: DateTime<Utc>) -> Report {
+        let started = now - Duration::hours(started_hours_ago);
+        Report {
+            report_json: json!({"phase1_security": {"risk_level": risk}}),
+            status: status.to_string(),
+            submitted_at: started,
+            assignee: Some("rev@acme.test".to_string()),
+            status_changed_at: started,
+            review_started_at: Some(started),
+            ..Report::sample()
+        }
+    }
+
+    #[test]
+    fn test_parse_hours() {
+        assert_eq!(
+            parse_hours("critical:12, LOW:96").unwrap(),
+            vec![("CRITICAL".to_string(), 12), ("LOW".to_string(), 96)]
+        );
+        assert!(parse_hours("HIGH").is_err());
+        assert!(parse_hours("HIGH:0").is_err());
+    }
+
+    #[test]
+    fn test_status_follows_risk_level() {
+        let config = SlaConfig::default();
+        let now = Utc::now();
+
+        let critical = config.status(&report("pending_review", "CRITICAL", 30, now), now);
+        assert!(critical.sla_breached);
+        assert_eq!(critical.time_in_state_secs, 30 * 3600);
+
+        let low = config.status(&report("in_review", "LOW", 30, now), now);
+        assert!(!low.sla_breached);
+        assert_eq!(
+            low.sla_due_at,
+            Some(now - Duration::hours(30) + Duration::hours(120))
+        );
+
+        let decided = config.status(&report("approved", "CRITICAL", 30, now), now);
+        assert_eq!(decided.sla_due_at, None);
+        assert!(!decided.sla_breached);
+    }
+
+    #[test]
+    fn test_reminders_escalate() {
+        let config = SlaConfig::default();
+        let now = Utc::now();
+        // HIGH: 48h SLA, reminded from 36h, escalated from 72h.
+        let level =
+            |hours| config.reminder_level(&report("pending_review", "HIGH", hours, now), now);
+        assert_eq!(level(10), Level::None);
+        assert_eq!(level(40), Level::DueSoon);
+        assert_eq!(level(50), Level::Breached);
+        assert_eq!(level(80), Level::Escalated);
+        assert_eq!(
+            config.reminder_level(&report("rejected", "HIGH", 80, now), now),
+            Level::None
+        );
+    }
+
+    #[test]
+    fn test_leads_for_teams() {
+        let config = SlaConfig {
+            leads: vec![
+                Team {
+                    name: "security".to_string(),
+                    members: vec!["lead@acme.test".to_string()],
+                },
+                Team {
+                    name: "platform".to_string(),
+                    members: vec!["plat@acme.test".to_string()],
+                },
+            ],
+            ..SlaConfig::default()
+        };
+        assert_eq!(config.leads_for(&["security"]), vec!["lead@acme.test"]);
+        assert_eq!(
+            config.leads_for(&["payments"]),
+            vec!["lead@acme.test", "plat@acme.test"]
+        );
+    }
+}
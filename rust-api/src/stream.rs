@@ -0,0 +1,370 @@
+use axum::{
+    extract::{Query, State},
+    http::HeaderMap,
+    response::sse::{Event, KeepAlive, Sse},
+};
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use sqlx::postgres::PgListener;
+use sqlx::PgPool;
+use std::convert::Infallible;
+use std::sync::Arc;
+use std::time::Duration;
+use tokio::sync::{broadcast, mpsc};
+use tokio_stream::{wrappers::ReceiverStream, Stream, StreamExt};
+use uuid::Uuid;
+
+use crate::auth::CurrentUser;
+use crate::error::AppError;
+
+// ============================================================================
+// Live Report Stream
+// ============================================================================
+
+/// The Postgres channel the `report_events` trigger notifies on.
+pub const CHANNEL: &str = "report_events";
+pub const LAST_EVENT_ID_HEADER: &str = "last-event-id";
+
+const BROADCAST_CAPACITY: usize = 1024;
+const CLIENT_BUFFER: usize = 64;
+const REPLAY_PAGE: i64 = 500;
+/// How often the listener also checks for events that were held back behind
+/// a transaction that was still open.
+const PUMP_INTERVAL: Duration = Duration::from_secs(1);
+
+/// Position in the stream: the writing transaction, then the event id.
+/// Ids are drawn before commit, so id order alone can skip an event that
+/// commits late; events are streamed in this order instead, and only once
+/// every transaction that could still add an earlier one has finished.
+type Cursor = (i64, i64);
+
+/// A report was created, changed status or was deleted.
+#[derive(Debug, Clone, Serialize, sqlx::FromRow)]
+pub struct ReportEvent {
+    id: i64,
+    #[serde(skip_serializing)]
+    xact_id: i64,
+    event_type: String,
+    report_id: Uuid,
+    server_name: String,
+    developer_email: String,
+    status: String,
+    previous_status: Option<String>,
+    version: i32,
+    created_at: DateTime<Utc>,
+}
+
+impl ReportEvent {
+    /// Drafts are only visible to their developer, as in the report list.
+    /// A draft that is submitted becomes visible with that status change.
+    fn visible_to(&self, user: Option<&CurrentUser>) -> bool {
+        self.status != "draft"
+            || user.is_some_and(|u| u.email.eq_ignore_ascii_case(&self.developer_email))
+    }
+
+    fn cursor(&self) -> Cursor {
+        (self.xact_id, self.id)
+    }
+
+    fn to_sse(&self) -> Event {
+        Event::default()
+            .id(self.id.to_string())
+            .event(&self.event_type)
+            .data(serde_json::to_string(self).expect("events serialize"))
+    }
+}
+
+/// Fans report events out to the SSE clients of this process. One listener
+/// per process is woken by the notifications, whichever replica made the
+/// change, and broadcasts the events in stream order.
+#[derive(Clone)]
+pub struct ReportEvents {
+    sender: broadcast::Sender<Arc<ReportEvent>>,
+}
+
+impl Default for ReportEvents {
+    fn default() -> Self {
+        Self {
+            sender: broadcast::channel(BROADCAST_CAPACITY).0,
+        }
+    }
+}
+
+impl ReportEvents {
+    /// Listens for notifications until the process exits. The listener
+    /// reconnects by itself and then broadcasts what committed meanwhile.
+    pub fn spawn_listener(&self, pool: PgPool) {
+        let sender = self.sender.clone();
+        tokio::spawn(async move {
+            let mut cursor = loop {
+                match latest_cursor(&pool).await {
+                    Ok(cursor) => break cursor,
+                    Err(e) => {
+                        tracing::error!("Failed to find the latest report event: {}", e);
+                        tokio::time::sleep(Duration::from_secs(5)).await;
+                    }
+                }
+            };
+            loop {
+                let mut listener = match PgListener::connect_with(&pool).await {
+                    Ok(listener) => listener,
+                    Err(e) => {
+                        tracing::error!("Failed to connect report event listener: {}", e);
+                        tokio::time::sleep(Duration::from_secs(5)).await;
+                        continue;
+                    }
+                };
+                if let Err(e) = listener.listen(CHANNEL).await {
+                    tracing::error!("Failed to listen on {}: {}", CHANNEL, e);
+                    tokio::time::sleep(Duration::from_secs(5)).await;
+                    continue;
+                }
+                tracing::info!("Listening for report events");
+                loop {
+                    if let Ok(Err(e)) = tokio::time::timeout(PUMP_INTERVAL, listener.recv()).await {
+                        tracing::error!("Report event listener failed: {}", e);
+                        break;
+                    }
+                    if let Err(e) = pump(&pool, &sender, &mut cursor).await {
+                        tracing::error!("Failed to load report events: {}", e);
+                    }
+                }
+                tokio::time::sleep(Duration::from_secs(1)).await;
+            }
+        });
+    }
+}
+
+/// Broadcasts the events after `cursor` that can be streamed yet.
+async fn pump(
+    pool: &PgPool,
+    sender: &broadcast::Sender<Arc<ReportEvent>>,
+    cursor: &mut Cursor,
+) -> Result<(), sqlx::Error> {
+    loop {
+        let page = events_after(pool, *cursor).await?;
+        let full = page.len() as i64 == REPLAY_PAGE;
+        for event in page {
+            *cursor = event.cursor();
+            // No receivers is not an error.
+            drop(sender.send(Arc::new(event)));
+        }
+        if !full {
+            return Ok(());
+        }
+    }
+}
+
+/// Events after `after` in stream order, leaving out any that an open
+/// transaction could still precede.
+async fn events_after(pool: &PgPool, after: Cursor) -> Result<Vec<ReportEvent>, sqlx::Error> {
+    sqlx::query_as::<_, ReportEvent>(
+        r#"
+        SELECT * FROM report_events
+        WHERE (xact_id, id) > ($1, $2)
+          AND xact_id < pg_snapshot_xmin(pg_current_snapshot())::text::bigint
+        ORDER BY xact_id, id
+        LIMIT $3
+        "#,
+    )
+    .bind(after.0)
+    .bind(after.1)
+    .bind(REPLAY_PAGE)
+    .fetch_all(pool)
+    .await
+}
+
+/// The cursor of the last event that can be streamed now.
+async fn latest_cursor(pool: &PgPool) -> Result<Cursor, sqlx::Error> {
+    let latest: Option<Cursor> = sqlx::query_as(
+        r#"
+        SELECT xact_id, id FROM report_events
+        WHERE xact_id < pg_snapshot_xmin(pg_current_snapshot())::text::bigint
+        ORDER BY xact_id DESC, id DESC
+        LIMIT 1
+        "#,
+    )
+    .fetch_optional(pool)
+    .await?;
+    Ok(latest.unwrap_or_default())
+}
+
+/// The cursor of event `id`. Once the event is purged, everything left is
+/// after it.
+async fn cursor_of(pool: &PgPool, id: i64) -> Result<Cursor, sqlx::Error> {
+    let xact_id: Option<i64> =
+        sqlx::query_scalar("SELECT xact_id FROM report_events WHERE id = $1")
+            .bind(id)
+            .fetch_optional(pool)
+            .await?;
+    Ok((xact_id.unwrap_or(0), id))
+}
+
+/// Where a client resumes: the `Last-Event-ID` header a reconnecting
+/// `EventSource` sends, or `?last_event_id=` on the first connection.
+fn resume_from(headers: &HeaderMap, query: &StreamQuery) -> Result<Option<i64>, AppError> {
+    let raw = match headers.get(LAST_EVENT_ID_HEADER) {
+        Some(value) => Some(value.to_str().unwrap_or_default().trim().to_string()),
+        None => query.last_event_id.clone(),
+    };
+    raw.filter(|v| !v.is_empty())
+        .map(|v| {
+            v.parse::<i64>()
+                .ok()
+                .filter(|id| *id >= 0)
+                .ok_or_else(|| AppError::BadRequest(format!("Invalid Last-Event-ID {:?}", v)))
+        })
+        .transpose()
+}
+
+#[derive(Debug, Deserialize)]
+pub struct StreamQuery {
+    last_event_id: Option<String>,
+}
+
+/// `GET /api/v1/events`: a Server-Sent Events stream of report changes. Each
+/// event's `id` can be passed back as `Last-Event-ID` to resume after it.
+pub async fn stream_events(
+    State(pool): State<PgPool>,
+    State(events): State<ReportEvents>,
+    Query(query): Query<StreamQuery>,
+    headers: HeaderMap,
+    user: Option<CurrentUser>,
+) -> Result<Sse<impl Stream<Item = Result<Event, Infallible>>>, AppError> {
+    let resume = match resume_from(&headers, &query)? {
+        Some(id) => Some(
+            cursor_of(&pool, id)
+                .await
+                .map_err(AppError::db("Failed to resume the event stream"))?,
+        ),
+        None => None,
+    };
+    // Subscribe before replaying so nothing committed in between is lost.
+    let mut live = events.sender.subscribe();
+    let (tx, rx) = mpsc::channel::<Event>(CLIENT_BUFFER);
+
+    tokio::spawn(async move {
+        let mut cursor = resume;
+        // Replays from the cursor; also used to catch up after lagging.
+        let mut catching_up = resume.is_some();
+        loop {
+            if catching_up {
+                let after = cursor.unwrap_or_default();
+                let page = match events_after(&pool, after).await {
+                    Ok(page) => page,
+                    Err(e) => {
+                        tracing::error!("Failed to replay report events: {}", e);
+                        return;
+                    }
+                };
+                catching_up = page.len() as i64 == REPLAY_PAGE;
+                for event in page {
+                    cursor = Some(event.cursor());
+                    if event.visible_to(user.as_ref()) && tx.send(event.to_sse()).await.is_err() {
+                        return;
+                    }
+                }
+                continue;
+            }
+
+            let event = tokio::select! {
+                _ = tx.closed() => return,
+                received = live.recv() => received,
+            };
+            match event {
+                Ok(event) => {
+                    // Already replayed; both arrive in stream order.
+                    if cursor.is_some_and(|c| event.cursor() <= c) {
+                        continue;
+                    }
+                    cursor = Some(event.cursor());
+                    if event.visible_to(user.as_ref()) && tx.send(event.to_sse()).await.is_err() {
+                        return;
+                    }
+                }
+                Err(broadcast::error::RecvError::Lagged(missed)) => {
+                    tracing::warn!("Event stream client lagged by {} events; replaying", missed);
+                    catching_up = cursor.is_some();
+                }
+                Err(broadcast::error::RecvError::Closed) => return,
+            }
+        }
+    });
+
+    Ok(Sse::new(ReceiverStream::new(rx).map(Ok)).keep_alive(KeepAlive::default()))
+}
+
+/// Drops events older than `retention_days`; clients resuming from before
+/// that receive what is left.
+pub async fn purge_events(pool: &PgPool, retention_days: i64) -> Result<u64, sqlx::Error> {
+    let purged = sqlx::query(
+        "DELETE FROM report_events WHERE created_at < NOW() - make_interval(days => $1::int)",
+    )
+    .bind(retention_days as i32)
+    .execute(pool)
+    .await?;
+    Ok(purged.rows_affected())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use axum::http::HeaderValue;
+
+    fn event(status: &str) -> ReportEvent {
+        ReportEvent {
+            id: 7,
+            xact_id: 900,
+            event_type: "report.created".to_string(),
+            report_id: Uuid::nil(),
+            server_name: "weather-mcp".to_string(),
+            developer_email: "dev@acme.test".to_string(),
+            status: status.to_string(),
+            previous_status: None,
+            version: 1,
+            created_at: Utc::now(),
+        }
+    }
+
+    #[test]
+    fn test_drafts_are_only_visible_to_their_developer() {
+        let developer = CurrentUser {
+            email: "dev@acme.test".to_string(),
+        };
+        let reviewer = CurrentUser {
+            email: "rev@acme.test".to_string(),
+        };
+        assert!(event("pending_review").visible_to(None));
+        assert!(event("draft").visible_to(Some(&developer)));
+        assert!(!event("draft").visible_to(Some(&reviewer)));
+        assert!(!event("draft").visible_to(None));
+    }
+
+    #[test]
+    fn test_resume_from() {
+        let query = |id: Option<&str>| StreamQuery {
+            last_event_id: id.map(str::to_string),
+        };
+        let mut headers = HeaderMap::new();
+        assert_eq!(resume_from(&headers, &query(None)).unwrap(), None);
+        assert_eq!(resume_from(&headers, &query(Some("12"))).unwrap(), Some(12));
+        assert!(resume_from(&headers, &query(Some("abc"))).is_err());
+
+        headers.insert(LAST_EVENT_ID_HEADER, HeaderValue::from_static("42"));
+        assert_eq!(resume_from(&headers, &query(Some("12"))).unwrap(), Some(42));
+        headers.insert(LAST_EVENT_ID_HEADER, HeaderValue::from_static("-1"));
+        assert!(resume_from(&headers, &query(None)).is_err());
+    }
+
+    #[test]
+    fn test_late_commit_streams_after_earlier_transactions() {
+        // Transaction 901 drew id 5 but committed after 900's id 7.
+        let late = ReportEvent {
+            id: 5,
+            xact_id: 901,
+            ..event("approved")
+        };
+        assert!(event("approved").cursor() < late.cursor());
+        assert!(!serde_json::to_string(&late).unwrap().contains("xact_id"));
+    }
+}
@@ -0,0 +1,267 @@
+use serde_json::Value as JsonValue;
+
+use crate::config::IngestLimits;
+use crate::{CreateReportRequest, UpdateStatusRequest};
+
+// ============================================================================
+// Ingestion Guards
+// ============================================================================
+
+/// Column widths from migration 001.
+const MAX_SERVER_NAME_CHARS: usize = 255;
+const MAX_REPOSITORY_URL_CHARS: usize = 500;
+const MAX_EMAIL_CHARS: usize = 255;
+const MAX_COMMENT_CHARS: usize = 10_000;
+
+#[derive(Debug, PartialEq)]
+pub enum ValidationError {
+    /// A field is larger than the configured limit (maps to 413).
+    TooLarge(String),
+    /// A field is malformed or does not fit the schema (maps to 422).
+    Invalid(String),
+}
+
+impl ValidationError {
+    pub fn message(&self) -> &str {
+        match self {
+            ValidationError::TooLarge(msg) | ValidationError::Invalid(msg) => msg,
+        }
+    }
+}
+
+pub fn validate_create_report(
+    req: &CreateReportRequest,
+    limits: &IngestLimits,
+) -> Result<(), ValidationError> {
+    check_varchar("server_name", &req.server_name, MAX_SERVER_NAME_CHARS)?;
+    check_varchar(
+        "repository_url",
+        &req.repository_url,
+        MAX_REPOSITORY_URL_CHARS,
+    )?;
+    check_varchar("developer_email", &req.developer_email, MAX_EMAIL_CHARS)?;
+    if !req.developer_email.contains('@') {
+        return Err(ValidationError::Invalid(
+            "developer_email must be an email address".to_string(),
+        ));
+    }
+
+    if req.report_data.trim().is_empty() {
+        return Err(ValidationError::Invalid(
+            "report_data must not be empty".to_string(),
+        ));
+    }
+    if req.report_data.len() > limits.max_report_data_bytes {
+        return Err(ValidationError::TooLarge(format!(
+            "report_data is {} bytes, maximum is {} bytes",
+            req.report_data.len(),
+            limits.max_report_data_bytes
+        )));
+    }
+    check_no_nul("report_data", &req.report_data)?;
+
+    if !req.report_json.is_object() {
+        return Err(ValidationError::Invalid(
+            "report_json must be a JSON object".to_string(),
+        ));
+    }
+    check_json("report_json", &req.report_json, limits)?;
+    if let Some(raw) = &req.raw_json {
+        check_json("raw_json", raw, limits)?;
+    }
+
+    Ok(())
+}
+
+pub fn validate_update_status(req: &UpdateStatusRequest) -> Result<(), ValidationError> {
+    if let Some(reviewed_by) = &req.reviewed_by {
+        check_varchar("reviewed_by", reviewed_by, MAX_EMAIL_CHARS)?;
+    }
+    if let Some(notes) = &req.review_notes {
+        check_no_nul("review_notes", notes)?;
+    }
+    Ok(())
+}
+
+/// A comment body: required and at most `MAX_COMMENT_CHARS` characters.
+pub fn validate_comment(body: &str) -> Result<(), ValidationError> {
+    check_varchar("body", body, MAX_COMMENT_CHARS)
+}
+
+/// Required, single-line text bounded by a `VARCHAR(n)` column.
+fn check_varchar(field: &str, value: &str, max_chars: usize) -> Result<(), ValidationError> {
+    if value.trim().is_empty() {
+        return Err(ValidationError::Invalid(format!(
+            "{} must not be empty",
+            field
+        )));
+    }
+    let chars = value.chars().count();
+    if chars > max_chars {
+        return Err(ValidationError::Invalid(format!(
+            "{} is {} characters, maximum is {}",
+            field, chars, max_chars
+        )));
+    }
+    check_no_nul(field, value)
+}
+
+/// PostgreSQL rejects NUL in both `TEXT` and `JSONB` strings.
+fn check_no_nul(field: &str, value: &str) -> Result<(), ValidationError> {
+    if value.contains('\0') {
+        return Err(ValidationError::Invalid(format!(
+            "{} must not contain NUL characters",
+            field
+        )));
+    }
+    Ok(())
+}
+
+fn check_json(
+    field: &str,
+    value: &JsonValue,
+    limits: &IngestLimits,
+) -> Result<(), ValidationError> {
+    // Values were just deserialized from the request, so serialization cannot fail.
+    let size = serde_json::to_vec(value).map(|v| v.len()).unwrap_or(0);
+    if size > limits.max_json_bytes {
+        return Err(ValidationError::TooLarge(format!(
+            "{} is {} bytes, maximum is {} bytes",
+            field, size, limits.max_json_bytes
+        )));
+    }
+    check_json_shape(field, value, "", 0, limits)
+}
+
+/// Walks the document rejecting excessive nesting, oversized arrays and NUL
+/// characters. `path` is a JSON Pointer to `value` for error messages.
+fn check_json_shape(
+    field: &str,
+    value: &JsonValue,
+    path: &str,
+    depth: usize,
+    limits: &IngestLimits,
+) -> Result<(), ValidationError> {
+    let location = || {
+        if path.is_empty() {
+            "/".to_string()
+        } else {
+            path.to_string()
+        }
+    };
+
+    match value {
+        JsonValue::Array(_) | JsonValue::Object(_) if depth >= limits.max_json_depth => {
+            Err(ValidationError::Invalid(format!(
+                "{} exceeds maximum nesting depth of {} at {}",
+                field,
+                limits.max_json_depth,
+                location()
+            )))
+        }
+        JsonValue::Array(items) => {
+            if items.len() > limits.max_json_array_len {
+                return Err(ValidationError::Invalid(format!(
+                    "{} has an array of {} elements at {}, maximum is {}",
+                    field,
+                    items.len(),
+                    location(),
+                    limits.max_json_array_len
+                )));
+            }
+            for (i, item) in items.iter().enumerate() {
+                check_json_shape(field, item, &format!("{}/{}", path, i), depth + 1, limits)?;
+            }
+            Ok(())
+        }
+        JsonValue::Object(map) => {
+            for (key, item) in map {
+                check_no_nul(field, key)?;
+                let child = format!("{}/{}", path, key.replace('~', "~0").replace('/', "~1"));
+                check_json_shape(field, item, &child, depth + 1, limits)?;
+            }
+            Ok(())
+        }
+        JsonValue::String(s) => check_no_nul(field, s),
+        _ => Ok(()),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn test_valid_report_passes() {
+        let req = CreateReportRequest::sample(
+            json!({"report_version": "1.0", "warnings": [{"severity": "WARNING"}]}),
+        );
+        assert_eq!(
+            validate_create_report(&req, &IngestLimits::default()),
+            Ok(())
+        );
+    }
+
+    #[test]
+    fn test_varchar_overflow_is_unprocessable() {
+        let mut req = CreateReportRequest::sample(json!({}));
+        req.server_name = "x".repeat(256);
+        let err = validate_create_report(&req, &IngestLimits::default()).unwrap_err();
+        assert!(matches!(err, ValidationError::Invalid(_)));
+        assert_eq!(
+            err.message(),
+            "server_name is 256 characters, maximum is 255"
+        );
+    }
+
+    #[test]
+    fn test_oversized_report_data_is_too_large() {
+        let mut req = CreateReportRequest::sample(json!({}));
+        req.report_data = "a".repeat(101);
+        let limits = IngestLimits {
+            max_report_data_bytes: 100,
+            ..IngestLimits::default()
+        };
+        let err = validate_create_report(&req, &limits).unwrap_err();
+        assert!(matches!(err, ValidationError::TooLarge(_)));
+    }
+
+    #[test]
+    fn test_deep_nesting_rejected_with_path() {
+        let mut nested = json!("leaf");
+        for _ in 0..5 {
+            nested = json!({ "a": nested });
+        }
+        let mut req = CreateReportRequest::sample(json!({}));
+        req.raw_json = Some(nested);
+        let limits = IngestLimits {
+            max_json_depth: 3,
+            ..IngestLimits::default()
+        };
+        let err = validate_create_report(&req, &limits).unwrap_err();
+        assert_eq!(
+            err.message(),
+            "raw_json exceeds maximum nesting depth of 3 at /a/a/a"
+        );
+    }
+
+    #[test]
+    fn test_huge_array_rejected() {
+        let req = CreateReportRequest::sample(json!({ "warnings": vec![1; 11] }));
+        let limits = IngestLimits {
+            max_json_array_len: 10,
+            ..IngestLimits::default()
+        };
+        let err = validate_create_report(&req, &limits).unwrap_err();
+        assert!(matches!(err, ValidationError::Invalid(_)));
+        assert!(err.message().contains("at /warnings"));
+    }
+
+    #[test]
+    fn test_nul_characters_rejected() {
+        let req = CreateReportRequest::sample(json!({ "note": "a\u{0}b" }));
+        let err = validate_create_report(&req, &IngestLimits::default()).unwrap_err();
+        assert_eq!(err.message(), "report_json must not contain NUL characters");
+    }
+}
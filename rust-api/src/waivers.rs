@@ -0,0 +1,386 @@
+use axum::{
+    extract::{Path, Query, State},
+    http::StatusCode,
+    response::Json,
+};
+use chrono::{DateTime, Duration, Utc};
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+use sqlx::PgPool;
+use std::sync::Arc;
+use uuid::Uuid;
+
+use crate::auth::CurrentUser;
+use crate::config::Config;
+use crate::error::AppError;
+use crate::issues;
+use crate::separation;
+use crate::{fetch_report, find_issue, Issue, Report};
+
+// ============================================================================
+// Issue Waivers (Risk Acceptance)
+// ============================================================================
+
+const MAX_JUSTIFICATION_CHARS: usize = 5000;
+
+/// Identifies an issue across submissions: SHA-256 of its description with
+/// case and whitespace normalized, so re-wrapped text still matches.
+pub fn description_hash(description: &str) -> String {
+    let normalized = description
+        .split_whitespace()
+        .collect::<Vec<_>>()
+        .join(" ")
+        .to_lowercase();
+    hex::encode(Sha256::digest(normalized.as_bytes()))
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum WaiverScope {
+    /// Only the report the waiver was granted on.
+    #[default]
+    Report,
+    /// That report and every later report of the same server.
+    Server,
+}
+
+impl WaiverScope {
+    fn as_str(self) -> &'static str {
+        match self {
+            WaiverScope::Report => "report",
+            WaiverScope::Server => "server",
+        }
+    }
+}
+
+#[derive(Debug, Serialize, sqlx::FromRow)]
+pub struct Waiver {
+    id: Uuid,
+    /// The report it was granted on; `None` once that report is deleted.
+    report_id: Option<Uuid>,
+    server_name: String,
+    scope: String,
+    phase_id: Option<String>,
+    description_hash: String,
+    issue_description: String,
+    severity: String,
+    approver: String,
+    justification: String,
+    expires_at: DateTime<Utc>,
+    /// Server-scoped waivers cover reports submitted from this time on.
+    effective_from: DateTime<Utc>,
+    revoked_at: Option<DateTime<Utc>>,
+    revoked_by: Option<String>,
+    created_at: DateTime<Utc>,
+}
+
+impl Waiver {
+    fn covers(&self, issue: &Issue) -> bool {
+        self.phase_id == issue.phase_id
+            && self.description_hash == description_hash(&issue.description)
+    }
+}
+
+/// An issue hidden from the active lists by a waiver.
+#[derive(Debug, Serialize)]
+pub struct WaivedIssue {
+    #[serde(flatten)]
+    issue: Issue,
+    waiver_id: Uuid,
+    waiver_expires_at: DateTime<Utc>,
+}
+
+/// Waivers that currently apply to `report`.
+pub async fn active_for_report(pool: &PgPool, report: &Report) -> Result<Vec<Waiver>, AppError> {
+    sqlx::query_as::<_, Waiver>(
+        r#"
+        SELECT * FROM issue_waivers
+        WHERE revoked_at IS NULL AND expires_at > NOW()
+          AND (report_id = $1
+               OR (scope = 'server' AND server_name = $2 AND effective_from <= $3))
+        ORDER BY created_at
+        "#,
+    )
+    .bind(report.id)
+    .bind(&report.server_name)
+    .bind(report.submitted_at)
+    .fetch_all(pool)
+    .await
+    .map_err(AppError::db("Failed to load waivers"))
+}
+
+/// Splits `issues` into those still open and those covered by a waiver.
+pub fn apply(waivers: &[Waiver], issues: Vec<Issue>) -> (Vec<Issue>, Vec<WaivedIssue>) {
+    let mut open = Vec::new();
+    let mut waived = Vec::new();
+    for issue in issues {
+        match waivers.iter().find(|w| w.covers(&issue)) {
+            Some(w) => waived.push(WaivedIssue {
+                issue,
+                waiver_id: w.id,
+                waiver_expires_at: w.expires_at,
+            }),
+            None => open.push(issue),
+        }
+    }
+    (open, waived)
+}
+
+#[derive(Debug, Deserialize)]
+pub struct CreateWaiverRequest {
+    /// `<list>/<index>` as returned by the issues endpoint, e.g. `warnings/2`.
+    issue: String,
+    justification: String,
+    #[serde(default)]
+    scope: WaiverScope,
+    expires_at: Option<DateTime<Utc>>,
+    expires_in_days: Option<i64>,
+}
+
+impl CreateWaiverRequest {
+    fn expiry(&self, now: DateTime<Utc>, max_days: i64) -> Result<DateTime<Utc>, AppError> {
+        let expires = match (self.expires_at, self.expires_in_days) {
+            (Some(at), None) => at,
+            (None, Some(days)) => now + Duration::days(days),
+            _ => {
+                return Err(AppError::Validation(
+                    "a waiver needs exactly one of expires_at or expires_in_days".to_string(),
+                ))
+            }
+        };
+        if expires <= now || expires > now + Duration::days(max_days) {
+            return Err(AppError::Validation(format!(
+                "a waiver must expire within {} days",
+                max_days
+            )));
+        }
+        Ok(expires)
+    }
+}
+
+#[derive(Debug, Deserialize)]
+pub struct WaiverQuery {
+    server_name: Option<String>,
+    /// Only unrevoked, unexpired waivers.
+    active: Option<bool>,
+}
+
+pub async fn create_waiver(
+    State(pool): State<PgPool>,
+    State(config): State<Arc<Config>>,
+    Path(id): Path<Uuid>,
+    user: CurrentUser,
+    Json(req): Json<CreateWaiverRequest>,
+) -> Result<(StatusCode, Json<Waiver>), AppError> {
+    user.require_reviewer(&config.auth)?;
+    let justification = req.justification.trim();
+    let chars = justification.chars().count();
+    if chars == 0 || chars > MAX_JUSTIFICATION_CHARS || justification.contains('\0') {
+        return Err(AppError::Validation(format!(
+            "justification must be 1-{} characters",
+            MAX_JUSTIFICATION_CHARS
+        )));
+    }
+    let expires_at = req.expiry(Utc::now(), config.waiver_max_days)?;
+
+    let report = fetch_report(&pool, id).await?;
+    let issue = find_issue(&report.report_json, &req.issue)
+        .ok_or_else(|| AppError::Validation(format!("issue {} not found in report", req.issue)))?;
+
+    let mut tx = pool
+        .begin()
+        .await
+        .map_err(AppError::db("Failed to start transaction"))?;
+    // Accepting a risk is a decision like any other: not on your own report.
+    separation::enforce(&mut tx, &config, &report, &user, None, "waive").await?;
+
+    let waiver = sqlx::query_as::<_, Waiver>(
+        r#"
+        INSERT INTO issue_waivers
+            (report_id, server_name, scope, phase_id, description_hash, issue_description,
+             severity, approver, justification, expires_at, effective_from)
+        VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9, $10, $11)
+        RETURNING *
+        "#,
+    )
+    .bind(report.id)
+    .bind(&report.server_name)
+    .bind(req.scope.as_str())
+    .bind(&issue.phase_id)
+    .bind(description_hash(&issue.description))
+    .bind(&issue.description)
+    .bind(&issue.severity)
+    .bind(&user.email)
+    .bind(justification)
+    .bind(expires_at)
+    .bind(report.submitted_at)
+    .fetch_one(&mut *tx)
+    .await
+    .map_err(AppError::db("Failed to create waiver"))?;
+    issues::refresh_waived(&mut *tx, Some(&report.server_name)).await?;
+    tx.commit()
+        .await
+        .map_err(AppError::db("Failed to commit waiver"))?;
+
+    tracing::info!(
+        "{} waived {} issue on {} ({} scope) until {}",
+        user.email,
+        waiver.severity,
+        waiver.server_name,
+        waiver.scope,
+        waiver.expires_at
+    );
+    Ok((StatusCode::CREATED, Json(waiver)))
+}
+
+/// Waivers that apply to a report, active or not.
+pub async fn list_report_waivers(
+    State(pool): State<PgPool>,
+    Path(id): Path<Uuid>,
+) -> Result<Json<Vec<Waiver>>, AppError> {
+    let report = fetch_report(&pool, id).await?;
+    let waivers = sqlx::query_as::<_, Waiver>(
+        r#"
+        SELECT * FROM issue_waivers
+        WHERE report_id = $1
+           OR (scope = 'server' AND server_name = $2 AND effective_from <= $3)
+        ORDER BY created_at DESC
+        "#,
+    )
+    .bind(report.id)
+    .bind(&report.server_name)
+    .bind(report.submitted_at)
+    .fetch_all(&pool)
+    .await
+    .map_err(AppError::db("Failed to list waivers"))?;
+    Ok(Json(waivers))
+}
+
+pub async fn list_waivers(
+    State(pool): State<PgPool>,
+    Query(query): Query<WaiverQuery>,
+) -> Result<Json<Vec<Waiver>>, AppError> {
+    let waivers = sqlx::query_as::<_, Waiver>(
+        r#"
+        SELECT * FROM issue_waivers
+        WHERE ($1::text IS NULL OR server_name = $1)
+          AND ($2::bool IS NOT TRUE OR (revoked_at IS NULL AND expires_at > NOW()))
+        ORDER BY created_at DESC
+        "#,
+    )
+    .bind(&query.server_name)
+    .bind(query.active)
+    .fetch_all(&pool)
+    .await
+    .map_err(AppError::db("Failed to list waivers"))?;
+    Ok(Json(waivers))
+}
+
+pub async fn revoke_waiver(
+    State(pool): State<PgPool>,
+    State(config): State<Arc<Config>>,
+    Path(waiver_id): Path<Uuid>,
+    user: CurrentUser,
+) -> Result<Json<Waiver>, AppError> {
+    user.require_reviewer(&config.auth)?;
+    let waiver = sqlx::query_as::<_, Waiver>(
+        r#"
+        UPDATE issue_waivers SET revoked_at = NOW(), revoked_by = $2
+        WHERE id = $1 AND revoked_at IS NULL
+        RETURNING *
+        "#,
+    )
+    .bind(waiver_id)
+    .bind(&user.email)
+    .fetch_optional(&pool)
+    .await
+    .map_err(AppError::db("Failed to revoke waiver"))?
+    .ok_or_else(|| AppError::NotFound(format!("Active waiver {} not found", waiver_id)))?;
+    issues::refresh_waived(&pool, Some(&waiver.server_name)).await?;
+
+    tracing::info!("Waiver {} revoked by {}", waiver_id, user.email);
+    Ok(Json(waiver))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn issue(phase: Option<&str>, description: &str) -> Issue {
+        Issue {
+            severity: "WARNING".to_string(),
+            description: description.to_string(),
+            impact: None,
+            recommendation: None,
+            phase_id: phase.map(String::from),
+            issue_id: None,
+            status: None,
+        }
+    }
+
+    fn waiver(phase: Option<&str>, description: &str) -> Waiver {
+        Waiver {
+            id: Uuid::new_v4(),
+            report_id: Some(Uuid::new_v4()),
+            server_name: "srv".to_string(),
+            scope: "server".to_string(),
+            phase_id: phase.map(String::from),
+            description_hash: description_hash(description),
+            issue_description: description.to_string(),
+            severity: "WARNING".to_string(),
+            approver: "rev@x.no".to_string(),
+            justification: "Accepted".to_string(),
+            expires_at: Utc::now() + Duration::days(30),
+            effective_from: Utc::now(),
+            revoked_at: None,
+            revoked_by: None,
+            created_at: Utc::now(),
+        }
+    }
+
+    #[test]
+    fn test_description_hash_normalizes_whitespace_and_case() {
+        assert_eq!(
+            description_hash("No  rate\nlimiting"),
+            description_hash("no rate limiting ")
+        );
+        assert_ne!(
+            description_hash("No rate limiting"),
+            description_hash("No auth")
+        );
+    }
+
+    #[test]
+    fn test_apply_matches_phase_and_description() {
+        let waivers = [waiver(Some("P1"), "No rate limiting")];
+        let (open, waived) = apply(
+            &waivers,
+            vec![
+                issue(Some("P1"), "No rate limiting"),
+                issue(Some("P2"), "No rate limiting"),
+                issue(Some("P1"), "Missing auth"),
+            ],
+        );
+        assert_eq!(waived.len(), 1);
+        assert_eq!(waived[0].waiver_id, waivers[0].id);
+        assert_eq!(open.len(), 2);
+    }
+
+    #[test]
+    fn test_waiver_expiry_bounds() {
+        let now = Utc::now();
+        let req = |at: Option<DateTime<Utc>>, days: Option<i64>| CreateWaiverRequest {
+            issue: "warnings/0".to_string(),
+            justification: "ok".to_string(),
+            scope: WaiverScope::Report,
+            expires_at: at,
+            expires_in_days: days,
+        };
+        assert!(req(None, Some(30)).expiry(now, 365).is_ok());
+        assert!(req(None, Some(400)).expiry(now, 365).is_err());
+        assert!(req(Some(now - Duration::days(1)), None)
+            .expiry(now, 365)
+            .is_err());
+        assert!(req(None, None).expiry(now, 365).is_err());
+    }
+}
@@ -0,0 +1,723 @@
+use axum::{
+    extract::{Path, Query, State},
+    http::StatusCode,
+    response::Json,
+};
+use chrono::{DateTime, Utc};
+use hmac::{Hmac, Mac};
+use serde::{Deserialize, Serialize};
+use serde_json::{json, Value as JsonValue};
+use sha2::Sha256;
+use sqlx::{PgPool, Postgres, Transaction};
+use std::sync::{Arc, OnceLock};
+use std::time::{Duration, Instant};
+use uuid::Uuid;
+
+use crate::auth::CurrentUser;
+use crate::config::Config;
+use crate::error::AppError;
+use crate::jobs::{self, Job, NewJob};
+use crate::Report;
+
+// ============================================================================
+// Outgoing Webhooks
+// ============================================================================
+
+pub const EVENT_TYPES: [&str; 7] = [
+    "report.created",
+    "report.approved",
+    "report.rejected",
+    "report.withdrawn",
+    "report.expired",
+    "report.demoted",
+    "report.reopened",
+];
+pub const DELIVERY_JOB: &str = "webhook_delivery";
+
+pub const EVENT_HEADER: &str = "x-golive-event";
+pub const DELIVERY_HEADER: &str = "x-golive-delivery";
+pub const TIMESTAMP_HEADER: &str = "x-golive-timestamp";
+pub const SIGNATURE_HEADER: &str = "x-golive-signature";
+
+const MAX_URL_CHARS: usize = 2000;
+const MAX_ERROR_CHARS: usize = 1000;
+
+#[derive(Debug, Clone)]
+pub struct WebhookConfig {
+    /// Per-attempt request timeout (`WEBHOOK_TIMEOUT_SECS`).
+    pub timeout_secs: u64,
+    /// Attempts per delivery before it is marked failed (`WEBHOOK_MAX_ATTEMPTS`).
+    pub max_attempts: i32,
+}
+
+impl Default for WebhookConfig {
+    fn default() -> Self {
+        Self {
+            timeout_secs: 10,
+            max_attempts: 8,
+        }
+    }
+}
+
+/// `sha256=<hex>` HMAC of `<timestamp>.<body>` under the subscription secret.
+/// Receivers recompute it and reject stale timestamps to stop replays.
+pub fn sign(secret: &str, timestamp: i64, body: &[u8]) -> String {
+    let mut mac =
+        Hmac::<Sha256>::new_from_slice(secret.as_bytes()).expect("HMAC accepts any key length");
+    mac.update(timestamp.to_string().as_bytes());
+    mac.update(b".");
+    mac.update(body);
+    format!("sha256={}", hex::encode(mac.finalize().into_bytes()))
+}
+
+/// The report as seen by subscribers; the markdown and raw JSON are left out.
+fn report_summary(report: &Report) -> JsonValue {
+    json!({
+        "id": report.id,
+        "server_name": report.server_name,
+        "repository_url": report.repository_url,
+        "developer_email": report.developer_email,
+        "status": report.status,
+        "version": report.version,
+        "submitted_at": report.submitted_at,
+        "reviewed_at": report.reviewed_at,
+        "reviewed_by": report.reviewed_by,
+        "review_notes": report.review_notes,
+        "expires_at": report.expires_at,
+    })
+}
+
+/// The event announcing that a report moved to `status`, if any.
+pub fn status_event(status: &str) -> Option<&'static str> {
+    match status {
+        "approved" | "approved_with_conditions" => Some("report.approved"),
+        "rejected" => Some("report.rejected"),
+        "withdrawn" => Some("report.withdrawn"),
+        "expired" => Some("report.expired"),
+        "pending_review" => Some("report.reopened"),
+        _ => None,
+    }
+}
+
+/// Records `event_type` for `report` and queues a delivery to every
+/// subscription that wants it. Runs in the caller's transaction, so events
+/// are sent if and only if the change they describe commits.
+pub async fn emit(
+    tx: &mut Transaction<'_, Postgres>,
+    config: &Config,
+    event_type: &str,
+    report: &Report,
+) -> Result<(), AppError> {
+    debug_assert!(EVENT_TYPES.contains(&event_type));
+    let failed = || AppError::db("Failed to queue webhook event");
+
+    let event_id = Uuid::new_v4();
+    let payload = json!({
+        "id": event_id,
+        "type": event_type,
+        "created_at": Utc::now(),
+        "data": { "report": report_summary(report) },
+    });
+    sqlx::query(
+        "INSERT INTO webhook_events (id, event_type, report_id, payload) VALUES ($1, $2, $3, $4)",
+    )
+    .bind(event_id)
+    .bind(event_type)
+    .bind(report.id)
+    .bind(&payload)
+    .execute(&mut **tx)
+    .await
+    .map_err(failed())?;
+
+    let deliveries: Vec<Uuid> = sqlx::query_scalar(
+        r#"
+        INSERT INTO webhook_deliveries (subscription_id, event_id)
+        SELECT id, $1 FROM webhook_subscriptions
+        WHERE $2 = ANY(event_types)
+        RETURNING id
+        "#,
+    )
+    .bind(event_id)
+    .bind(event_type)
+    .fetch_all(&mut **tx)
+    .await
+    .map_err(failed())?;
+
+    for delivery_id in &deliveries {
+        queue_delivery(tx, config, *delivery_id).await?;
+    }
+    if !deliveries.is_empty() {
+        tracing::debug!(
+            "Queued {} for report {} to {} subscription(s)",
+            event_type,
+            report.id,
+            deliveries.len()
+        );
+    }
+    Ok(())
+}
+
+async fn queue_delivery(
+    tx: &mut Transaction<'_, Postgres>,
+    config: &Config,
+    delivery_id: Uuid,
+) -> Result<(), AppError> {
+    let mut job = NewJob::new(DELIVERY_JOB, json!({ "delivery_id": delivery_id }));
+    job.max_attempts = Some(config.webhooks.max_attempts);
+    jobs::enqueue(&mut **tx, &config.jobs, job)
+        .await
+        .map_err(AppError::db("Failed to queue webhook delivery"))?;
+    Ok(())
+}
+
+/// The outbound HTTP client, also used to post chat messages.
+pub fn client() -> &'static reqwest::Client {
+    static CLIENT: OnceLock<reqwest::Client> = OnceLock::new();
+    CLIENT.get_or_init(|| {
+        reqwest::Client::builder()
+            .redirect(reqwest::redirect::Policy::none())
+            .user_agent(concat!("golive-api/", env!("CARGO_PKG_VERSION")))
+            .build()
+            .expect("Failed to build webhook HTTP client")
+    })
+}
+
+/// What a subscriber answered to one delivery attempt.
+#[derive(Debug)]
+pub struct AttemptOutcome {
+    pub status_code: Option<u16>,
+    pub error: Option<String>,
+    pub duration_ms: i64,
+}
+
+impl AttemptOutcome {
+    pub fn succeeded(&self) -> bool {
+        self.error.is_none()
+    }
+}
+
+/// Posts one signed event. Any 2xx answer counts as delivered.
+pub async fn post_event(
+    url: &str,
+    secret: &str,
+    event_type: &str,
+    delivery_id: Uuid,
+    body: &[u8],
+    timeout: Duration,
+) -> AttemptOutcome {
+    let timestamp = Utc::now().timestamp();
+    let started = Instant::now();
+    let result = client()
+        .post(url)
+        .timeout(timeout)
+        .header(reqwest::header::CONTENT_TYPE, "application/json")
+        .header(EVENT_HEADER, event_type)
+        .header(DELIVERY_HEADER, delivery_id.to_string())
+        .header(TIMESTAMP_HEADER, timestamp.to_string())
+        .header(SIGNATURE_HEADER, sign(secret, timestamp, body))
+        .body(body.to_vec())
+        .send()
+        .await;
+    let duration_ms = started.elapsed().as_millis() as i64;
+
+    match result {
+        Ok(response) if response.status().is_success() => AttemptOutcome {
+            status_code: Some(response.status().as_u16()),
+            error: None,
+            duration_ms,
+        },
+        Ok(response) => AttemptOutcome {
+            status_code: Some(response.status().as_u16()),
+            error: Some(format!("subscriber answered {}", response.status())),
+            duration_ms,
+        },
+        Err(e) => AttemptOutcome {
+            status_code: None,
+            error: Some(e.to_string().chars().take(MAX_ERROR_CHARS).collect()),
+            duration_ms,
+        },
+    }
+}
+
+fn delivery_id(job: &Job) -> Option<Uuid> {
+    job.payload
+        .get("delivery_id")
+        .and_then(|v| v.as_str())
+        .and_then(|v| v.parse().ok())
+}
+
+/// Puts a failed delivery back to `pending` when an admin retries its job,
+/// so the retry makes a fresh attempt instead of finding it settled.
+pub async fn reopen(tx: &mut Transaction<'_, Postgres>, job: &Job) -> Result<(), AppError> {
+    let Some(delivery_id) = delivery_id(job) else {
+        return Ok(());
+    };
+    sqlx::query(
+        "UPDATE webhook_deliveries SET status = 'pending' WHERE id = $1 AND status = 'failed'",
+    )
+    .bind(delivery_id)
+    .execute(&mut **tx)
+    .await
+    .map_err(AppError::db("Failed to reopen webhook delivery"))?;
+    Ok(())
+}
+
+/// Runs a `webhook_delivery` job: one attempt, logged on the delivery.
+/// A failure is returned so the job queue retries it with backoff.
+pub async fn deliver(pool: &PgPool, config: &Config, job: &Job) -> Result<(), String> {
+    let delivery_id = delivery_id(job).ok_or("job payload has no delivery_id")?;
+
+    let target: Option<(String, String, String, JsonValue)> = sqlx::query_as(
+        r#"
+        SELECT s.url, s.secret, e.event_type, e.payload
+        FROM webhook_deliveries d
+        JOIN webhook_subscriptions s ON s.id = d.subscription_id
+        JOIN webhook_events e ON e.id = d.event_id
+        WHERE d.id = $1 AND d.status = 'pending'
+        "#,
+    )
+    .bind(delivery_id)
+    .fetch_optional(pool)
+    .await
+    .map_err(|e| e.to_string())?;
+    let Some((url, secret, event_type, payload)) = target else {
+        // Deleted with its subscription, or already settled.
+        return Ok(());
+    };
+
+    let body = serde_json::to_vec(&payload).map_err(|e| e.to_string())?;
+    let outcome = post_event(
+        &url,
+        &secret,
+        &event_type,
+        delivery_id,
+        &body,
+        Duration::from_secs(config.webhooks.timeout_secs),
+    )
+    .await;
+    let status = match (outcome.succeeded(), job.attempts >= job.max_attempts) {
+        (true, _) => "delivered",
+        (false, true) => "failed",
+        (false, false) => "pending",
+    };
+
+    sqlx::query(
+        r#"
+        UPDATE webhook_deliveries
+        SET status = $2, attempts = attempts + 1, last_attempt_at = NOW(),
+            last_status_code = $3, last_error = $4, last_duration_ms = $5,
+            delivered_at = CASE WHEN $2 = 'delivered' THEN NOW() END
+        WHERE id = $1
+        "#,
+    )
+    .bind(delivery_id)
+    .bind(status)
+    .bind(outcome.status_code.map(i32::from))
+    .bind(&outcome.error)
+    .bind(outcome.duration_ms)
+    .execute(pool)
+    .await
+    .map_err(|e| e.to_string())?;
+
+    match outcome.error {
+        None => {
+            tracing::info!("Delivered {} ({}) to {}", event_type, delivery_id, url);
+            Ok(())
+        }
+        Some(error) => Err(format!("delivery to {} failed: {}", url, error)),
+    }
+}
+
+// ----------------------------------------------------------------------------
+// Subscription endpoints
+// ----------------------------------------------------------------------------
+
+fn require_admin(user: &CurrentUser, config: &Config) -> Result<(), AppError> {
+    if user.is_admin(&config.auth) {
+        Ok(())
+    } else {
+        Err(AppError::Forbidden(
+            "Webhook subscriptions are restricted to admins".to_string(),
+        ))
+    }
+}
+
+#[derive(Debug, Serialize, sqlx::FromRow)]
+pub struct Subscription {
+    id: Uuid,
+    url: String,
+    event_types: Vec<String>,
+    description: Option<String>,
+    created_by: String,
+    created_at: DateTime<Utc>,
+    /// Only returned when the subscription is created.
+    #[sqlx(skip)]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    secret: Option<String>,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct CreateSubscriptionRequest {
+    url: String,
+    events: Vec<String>,
+    /// Generated when omitted.
+    secret: Option<String>,
+    description: Option<String>,
+}
+
+fn validate_subscription(req: &CreateSubscriptionRequest) -> Result<(), AppError> {
+    let url = req.url.trim();
+    if !(url.starts_with("https://") || url.starts_with("http://"))
+        || url.chars().count() > MAX_URL_CHARS
+        || url.contains(char::is_whitespace)
+    {
+        return Err(AppError::Validation(format!(
+            "url must be an http(s) URL of at most {} characters",
+            MAX_URL_CHARS
+        )));
+    }
+    if req.events.is_empty() {
+        return Err(AppError::Validation(
+            "events must list at least one event type".to_string(),
+        ));
+    }
+    if let Some(unknown) = req
+        .events
+        .iter()
+        .find(|e| !EVENT_TYPES.contains(&e.as_str()))
+    {
+        return Err(AppError::Validation(format!(
+            "Unknown event type {}; expected one of {}",
+            unknown,
+            EVENT_TYPES.join(", ")
+        )));
+    }
+    if let Some(secret) = &req.secret {
+        if !(16..=256).contains(&secret.len()) {
+            return Err(AppError::Validation(
+                "secret must be 16-256 bytes".to_string(),
+            ));
+        }
+    }
+    Ok(())
+}
+
+pub async fn create_subscription(
+    State(pool): State<PgPool>,
+    State(config): State<Arc<Config>>,
+    user: CurrentUser,
+    Json(req): Json<CreateSubscriptionRequest>,
+) -> Result<(StatusCode, Json<Subscription>), AppError> {
+    require_admin(&user, &config)?;
+    validate_subscription(&req)?;
+    let secret = req.secret.clone().unwrap_or_else(|| {
+        format!(
+            "whsec_{}{}",
+            Uuid::new_v4().simple(),
+            Uuid::new_v4().simple()
+        )
+    });
+    let mut events = req.events.clone();
+    events.sort();
+    events.dedup();
+
+    let mut subscription = sqlx::query_as::<_, Subscription>(
+        r#"
+        INSERT INTO webhook_subscriptions (url, event_types, secret, description, created_by)
+        VALUES ($1, $2, $3, $4, $5)
+        RETURNING *
+        "#,
+    )
+    .bind(req.url.trim())
+    .bind(&events)
+    .bind(&secret)
+    .bind(&req.description)
+    .bind(&user.email)
+    .fetch_one(&pool)
+    .await
+    .map_err(AppError::db("Failed to create webhook subscription"))?;
+    subscription.secret = Some(secret);
+
+    tracing::info!(
+        "Webhook subscription {} to {} created by {}",
+        subscription.id,
+        subscription.url,
+        user.email
+    );
+    Ok((StatusCode::CREATED, Json(subscription)))
+}
+
+pub async fn list_subscriptions(
+    State(pool): State<PgPool>,
+    State(config): State<Arc<Config>>,
+    user: CurrentUser,
+) -> Result<Json<Vec<Subscription>>, AppError> {
+    require_admin(&user, &config)?;
+    let subscriptions = sqlx::query_as::<_, Subscription>(
+        "SELECT * FROM webhook_subscriptions ORDER BY created_at DESC",
+    )
+    .fetch_all(&pool)
+    .await
+    .map_err(AppError::db("Failed to list webhook subscriptions"))?;
+    Ok(Json(subscriptions))
+}
+
+async fn fetch_subscription(pool: &PgPool, id: Uuid) -> Result<Subscription, AppError> {
+    sqlx::query_as::<_, Subscription>("SELECT * FROM webhook_subscriptions WHERE id = $1")
+        .bind(id)
+        .fetch_optional(pool)
+        .await
+        .map_err(AppError::db("Failed to get webhook subscription"))?
+        .ok_or_else(|| AppError::NotFound(format!("Webhook subscription {} not found", id)))
+}
+
+pub async fn get_subscription(
+    State(pool): State<PgPool>,
+    State(config): State<Arc<Config>>,
+    user: CurrentUser,
+    Path(id): Path<Uuid>,
+) -> Result<Json<Subscription>, AppError> {
+    require_admin(&user, &config)?;
+    Ok(Json(fetch_subscription(&pool, id).await?))
+}
+
+/// Removes a subscription together with its delivery log.
+pub async fn delete_subscription(
+    State(pool): State<PgPool>,
+    State(config): State<Arc<Config>>,
+    user: CurrentUser,
+    Path(id): Path<Uuid>,
+) -> Result<StatusCode, AppError> {
+    require_admin(&user, &config)?;
+    let result = sqlx::query("DELETE FROM webhook_subscriptions WHERE id = $1")
+        .bind(id)
+        .execute(&pool)
+        .await
+        .map_err(AppError::db("Failed to delete webhook subscription"))?;
+    if result.rows_affected() == 0 {
+        return Err(AppError::NotFound(format!(
+            "Webhook subscription {} not found",
+            id
+        )));
+    }
+    tracing::info!("Webhook subscription {} deleted by {}", id, user.email);
+    Ok(StatusCode::NO_CONTENT)
+}
+
+#[derive(Debug, Serialize, sqlx::FromRow)]
+pub struct Delivery {
+    id: Uuid,
+    subscription_id: Uuid,
+    event_id: Uuid,
+    event_type: String,
+    report_id: Uuid,
+    status: String,
+    attempts: i32,
+    last_attempt_at: Option<DateTime<Utc>>,
+    last_status_code: Option<i32>,
+    last_error: Option<String>,
+    last_duration_ms: Option<i64>,
+    delivered_at: Option<DateTime<Utc>>,
+    redelivery_of: Option<Uuid>,
+    created_at: DateTime<Utc>,
+}
+
+const DELIVERY_COLUMNS: &str = r#"
+    d.id, d.subscription_id, d.event_id, e.event_type, e.report_id, d.status, d.attempts,
+    d.last_attempt_at, d.last_status_code, d.last_error, d.last_duration_ms, d.delivered_at,
+    d.redelivery_of, d.created_at
+"#;
+
+#[derive(Debug, Deserialize)]
+pub struct DeliveryQuery {
+    status: Option<String>,
+    limit: Option<i64>,
+}
+
+/// The delivery log of a subscription, newest first.
+pub async fn list_deliveries(
+    State(pool): State<PgPool>,
+    State(config): State<Arc<Config>>,
+    user: CurrentUser,
+    Path(id): Path<Uuid>,
+    Query(query): Query<DeliveryQuery>,
+) -> Result<Json<Vec<Delivery>>, AppError> {
+    require_admin(&user, &config)?;
+    fetch_subscription(&pool, id).await?;
+    let deliveries = sqlx::query_as::<_, Delivery>(&format!(
+        r#"
+        SELECT {}
+        FROM webhook_deliveries d JOIN webhook_events e ON e.id = d.event_id
+        WHERE d.subscription_id = $1 AND ($2::text IS NULL OR d.status = $2)
+        ORDER BY d.created_at DESC
+        LIMIT $3
+        "#,
+        DELIVERY_COLUMNS
+    ))
+    .bind(id)
+    .bind(&query.status)
+    .bind(query.limit.unwrap_or(100).clamp(1, 500))
+    .fetch_all(&pool)
+    .await
+    .map_err(AppError::db("Failed to list webhook deliveries"))?;
+    Ok(Json(deliveries))
+}
+
+/// Sends an event again as a new delivery, e.g. after the subscriber fixed
+/// an outage that exhausted the retries.
+pub async fn redeliver(
+    State(pool): State<PgPool>,
+    State(config): State<Arc<Config>>,
+    user: CurrentUser,
+    Path((id, delivery_id)): Path<(Uuid, Uuid)>,
+) -> Result<(StatusCode, Json<Delivery>), AppError> {
+    require_admin(&user, &config)?;
+    let mut tx = pool
+        .begin()
+        .await
+        .map_err(AppError::db("Failed to start transaction"))?;
+    let new_id: Option<Uuid> = sqlx::query_scalar(
+        r#"
+        INSERT INTO webhook_deliveries (subscription_id, event_id, redelivery_of)
+        SELECT subscription_id, event_id, id FROM webhook_deliveries
+        WHERE id = $1 AND subscription_id = $2
+        RETURNING id
+        "#,
+    )
+    .bind(delivery_id)
+    .bind(id)
+    .fetch_optional(&mut *tx)
+    .await
+    .map_err(AppError::db("Failed to redeliver webhook"))?;
+    let Some(new_id) = new_id else {
+        return Err(AppError::NotFound(format!(
+            "Delivery {} not found for subscription {}",
+            delivery_id, id
+        )));
+    };
+    queue_delivery(&mut tx, &config, new_id).await?;
+    let delivery = sqlx::query_as::<_, Delivery>(&format!(
+        "SELECT {} FROM webhook_deliveries d JOIN webhook_events e ON e.id = d.event_id WHERE d.id = $1",
+        DELIVERY_COLUMNS
+    ))
+    .bind(new_id)
+    .fetch_one(&mut *tx)
+    .await
+    .map_err(AppError::db("Failed to redeliver webhook"))?;
+    tx.commit()
+        .await
+        .map_err(AppError::db("Failed to commit redelivery"))?;
+
+    tracing::info!(
+        "Delivery {} redelivered as {} by {}",
+        delivery_id,
+        new_id,
+        user.email
+    );
+    Ok((StatusCode::ACCEPTED, Json(delivery)))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use axum::{body::Bytes, http::HeaderMap, routing::post, Router};
+    use tokio::sync::mpsc;
+
+    #[test]
+    fn test_sign_is_stable_and_keyed() {
+        let body = br#"{"type":"report.approved"}"#;
+        let signature = sign("s3cret-s3cret-s3cret", 1_700_000_000, body);
+        assert!(signature.starts_with("sha256="));
+        assert_eq!(signature.len(), "sha256=".len() + 64);
+        assert_eq!(signature, sign("s3cret-s3cret-s3cret", 1_700_000_000, body));
+        assert_ne!(signature, sign("other-secret-value", 1_700_000_000, body));
+        assert_ne!(signature, sign("s3cret-s3cret-s3cret", 1_700_000_001, body));
+    }
+
+    #[test]
+    fn test_status_event() {
+        assert_eq!(
+            status_event("approved_with_conditions"),
+            Some("report.approved")
+        );
+        assert_eq!(status_event("rejected"), Some("report.rejected"));
+        assert_eq!(status_event("pending_review"), Some("report.reopened"));
+        assert_eq!(status_event("in_review"), None);
+        assert!(EVENT_TYPES.contains(&status_event("expired").unwrap()));
+    }
+
+    #[test]
+    fn test_validate_subscription() {
+        let req = |url: &str, events: &[&str]| CreateSubscriptionRequest {
+            url: url.to_string(),
+            events: events.iter().map(|e| e.to_string()).collect(),
+            secret: None,
+            description: None,
+        };
+        assert!(
+            validate_subscription(&req("https://ci.example/hook", &["report.approved"])).is_ok()
+        );
+        assert!(validate_subscription(&req("ftp://ci.example", &["report.approved"])).is_err());
+        assert!(validate_subscription(&req("https://ci.example", &[])).is_err());
+        assert!(validate_subscription(&req("https://ci.example", &["report.deleted"])).is_err());
+    }
+
+    /// Delivers to a local stand-in receiver and checks it can verify the
+    /// signature the way a subscriber would.
+    #[tokio::test]
+    async fn test_post_event_to_local_receiver() {
+        let (sent, mut received) = mpsc::unbounded_channel::<(HeaderMap, Bytes)>();
+        let app = Router::new()
+            .route(
+                "/ok",
+                post(move |headers: HeaderMap, body: Bytes| async move {
+                    sent.send((headers, body)).unwrap();
+                    StatusCode::NO_CONTENT
+                }),
+            )
+            .route("/down", post(|| async { StatusCode::SERVICE_UNAVAILABLE }));
+        let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let addr = listener.local_addr().unwrap();
+        tokio::spawn(async move { axum::serve(listener, app).await.unwrap() });
+
+        let secret = "test-secret-0123456789";
+        let delivery_id = Uuid::new_v4();
+        let body = br#"{"type":"report.created"}"#;
+        let timeout = Duration::from_secs(5);
+
+        let outcome = post_event(
+            &format!("http://{}/ok", addr),
+            secret,
+            "report.created",
+            delivery_id,
+            body,
+            timeout,
+        )
+        .await;
+        assert!(outcome.succeeded(), "{:?}", outcome.error);
+        assert_eq!(outcome.status_code, Some(204));
+
+        let (headers, received_body) = received.recv().await.unwrap();
+        let header = |name: &str| headers.get(name).unwrap().to_str().unwrap().to_string();
+        assert_eq!(received_body.as_ref(), body);
+        assert_eq!(header(EVENT_HEADER), "report.created");
+        assert_eq!(header(DELIVERY_HEADER), delivery_id.to_string());
+        let timestamp: i64 = header(TIMESTAMP_HEADER).parse().unwrap();
+        assert_eq!(
+            header(SIGNATURE_HEADER),
+            sign(secret, timestamp, &received_body)
+        );
+
+        let outcome = post_event(
+            &format!("http://{}/down", addr),
+            secret,
+            "report.created",
+            delivery_id,
+            body,
+            timeout,
+        )
+        .await;
+        assert!(!outcome.succeeded());
+        assert_eq!(outcome.status_code, Some(503));
+    }
+}